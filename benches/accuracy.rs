@@ -0,0 +1,340 @@
+//! Accuracy report: maximum and mean errors in ULPs of every function for each float type and
+//! input distribution, for our functions, `std` and the C++ libraries enabled by the features.
+//!
+//! The errors are measured against `std` functions of `f64`, so `f32` results are compared with
+//! unrounded values and `std` itself isn't reported for `f64`. A NaN where a number is expected
+//! and vice versa is an infinite error.
+//!
+//! Run `cargo bench --bench accuracy` to print markdown tables, add `-- --json` to print JSON
+//! instead.
+#![feature(portable_simd)]
+
+#[allow(dead_code)]
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+#[allow(dead_code, unused_macros)]
+#[macro_use]
+mod sample;
+use sample::{Distribution, BENCH_POINTS};
+
+use simd_addons::math::{Exponent, Trigonometry};
+use std::{
+    collections::BTreeMap,
+    simd::{prelude::*, LaneCount, SimdElement, SupportedLaneCount},
+};
+
+#[cfg(feature = "vectorclass_bench")]
+#[path = "cpp/vclbench.rs"]
+mod vclbench;
+
+#[cfg(feature = "libmvec_bench")]
+#[path = "cpp/libmvecbench.rs"]
+mod libmvecbench;
+
+#[cfg(feature = "sleef_bench")]
+#[path = "cpp/sleefbench.rs"]
+mod sleefbench;
+
+/// Columns of the tables, in order.
+const LIBRARIES: [&str; 6] = ["vec", "std", "vcl", "libmvec", "sleefu10", "sleefu35"];
+
+trait UlpError: Copy {
+    /// Error of `self` in ULPs of the type of `self`, relative to `expected`.
+    fn ulp_error(self, expected: f64) -> f64;
+}
+
+macro_rules! impl_ulp_error {
+    ($ftype: ident) => {
+        impl UlpError for $ftype {
+            fn ulp_error(self, expected: f64) -> f64 {
+                #[allow(clippy::unnecessary_cast)]
+                let actual = self as f64;
+                if actual.is_nan() || expected.is_nan() {
+                    return if actual.is_nan() && expected.is_nan() {
+                        0.0
+                    } else {
+                        f64::INFINITY
+                    };
+                }
+                let rounded = expected as $ftype;
+                if self.is_infinite() || rounded.is_infinite() {
+                    return if self == rounded { 0.0 } else { f64::INFINITY };
+                }
+                // ULP of the binade of `expected`, the smallest subnormal for subnormals
+                let exp_bits = (rounded.abs().to_bits() >> ($ftype::MANTISSA_DIGITS - 1)).max(1);
+                let binade = $ftype::from_bits(exp_bits << ($ftype::MANTISSA_DIGITS - 1));
+                (actual - expected).abs() / (binade * $ftype::EPSILON) as f64
+            }
+        }
+    };
+}
+
+impl_ulp_error!(f32);
+impl_ulp_error!(f64);
+
+/// Errors of a single function of a single library on a single sample.
+struct Report {
+    function: &'static str,
+    ftype: &'static str,
+    distribution: Distribution,
+    library: &'static str,
+    max_ulps: f64,
+    mean_ulps: f64,
+}
+
+/// Applies `func` to `x` by vectors of `N` lanes.
+fn map_simd<T, const N: usize>(x: &[T], func: impl Fn(Simd<T, N>) -> Simd<T, N>) -> Vec<T>
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    x.chunks_exact(N)
+        .flat_map(|x| func(Simd::from_slice(x)).to_array())
+        .collect()
+}
+
+/// Applies `func` to `x` and `y` by vectors of `N` lanes.
+fn map_simd2<T, const N: usize>(
+    x: &[T],
+    y: &[T],
+    func: impl Fn(Simd<T, N>, Simd<T, N>) -> Simd<T, N>,
+) -> Vec<T>
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    std::iter::zip(x.chunks_exact(N), y.chunks_exact(N))
+        .flat_map(|(x, y)| func(Simd::from_slice(x), Simd::from_slice(y)).to_array())
+        .collect()
+}
+
+/// Collects the results of a C++ benchmark function.
+#[allow(dead_code)]
+fn call_cpp<T: Copy + Default>(func: impl FnOnce(*mut T)) -> Vec<T> {
+    let mut result = vec![T::default(); BENCH_POINTS];
+    func(result.as_mut_ptr());
+    result
+}
+
+/// Adds the errors of every library to `$reports`. `$sample` generates the inputs of `$ftype`,
+/// `$reference`, `$scalar` and `$vec` evaluate the function on it in `f64`, with `std` and with
+/// ours respectively, `$cpp` calls a C++ function on it with the given output pointer.
+macro_rules! accuracy {
+    (
+        $reports: ident, $func: tt, $ftype: ident, $sample: expr,
+        reference: $reference: expr,
+        scalar: $scalar: expr,
+        vec: $vec: expr,
+        cpp: |$cpp_func: ident, $cpp_sample: ident, $result: ident| $cpp: expr
+    ) => {
+        paste::paste! {
+        let (sample, reference, scalar, vec) = ($sample, $reference, $scalar, $vec);
+        for distribution in Distribution::ALL {
+            let $cpp_sample = sample(distribution);
+            let expected: Vec<f64> = reference(&$cpp_sample);
+            let mut report = |library, result: Vec<$ftype>| {
+                let errors: Vec<f64> = std::iter::zip(&result, &expected)
+                    .map(|(actual, &expected)| actual.ulp_error(expected))
+                    .collect();
+                $reports.push(Report {
+                    function: stringify!($func),
+                    ftype: stringify!($ftype),
+                    distribution,
+                    library,
+                    max_ulps: errors.iter().copied().fold(0.0, f64::max),
+                    mean_ulps: errors.iter().sum::<f64>() / errors.len() as f64,
+                });
+            };
+
+            report("vec", vec(&$cpp_sample));
+            // `std` is the reference for `f64`
+            if $ftype::MANTISSA_DIGITS < f64::MANTISSA_DIGITS {
+                report("std", scalar(&$cpp_sample));
+            }
+
+            #[cfg(feature = "vectorclass_bench")]
+            {
+                let $cpp_func = vclbench::[< $func _ $ftype _vcl >];
+                report("vcl", call_cpp(|$result| unsafe { $cpp }));
+            }
+
+            #[cfg(feature = "libmvec_bench")]
+            {
+                let $cpp_func = libmvecbench::[< $func _ $ftype _libmvec >];
+                report("libmvec", call_cpp(|$result| unsafe { $cpp }));
+            }
+
+            #[cfg(feature = "sleef_bench")]
+            {
+                let $cpp_func = sleefbench::[< $func _ $ftype _sleefu10 >];
+                report("sleefu10", call_cpp(|$result| unsafe { $cpp }));
+                let $cpp_func = sleefbench::[< $func _ $ftype _sleefu35 >];
+                report("sleefu35", call_cpp(|$result| unsafe { $cpp }));
+            }
+        }
+        }
+    };
+}
+
+macro_rules! accuracy_func {
+    ($reports: ident, $range: expr, $func: tt, $ftype: ident) => {
+        accuracy!(
+            $reports, $func, $ftype,
+            |distribution: Distribution| sample_of!(distribution, $range, $ftype),
+            reference: |x: &Vec<$ftype>| x.iter().map(|&x| (x as f64).$func()).collect(),
+            scalar: |x: &Vec<$ftype>| x.iter().map(|x| x.$func()).collect(),
+            vec: |x: &Vec<$ftype>| map_simd::<_, 16>(x, |x| x.$func()),
+            cpp: |func, sample, result| func(sample.as_ptr(), result)
+        )
+    };
+}
+
+/// Same as `accuracy_func!`, but for a function of two arguments, see `bench_func2!` in `math.rs`.
+macro_rules! accuracy_func2 {
+    ($reports: ident, $range: expr, $func: tt, $ftype: ident, |$x: ident, $y: ident| $call: expr) => {
+        accuracy!(
+            $reports, $func, $ftype,
+            |distribution: Distribution| sample2_of!(distribution, $range, $ftype),
+            reference: |(x, y): &(Vec<$ftype>, Vec<$ftype>)| {
+                std::iter::zip(x, y)
+                    .map(|(&$x, &$y)| {
+                        let ($x, $y) = ($x as f64, $y as f64);
+                        $call
+                    })
+                    .collect()
+            },
+            scalar: |(x, y): &(Vec<$ftype>, Vec<$ftype>)| {
+                std::iter::zip(x, y).map(|(&$x, &$y)| $call).collect()
+            },
+            vec: |(x, y): &(Vec<$ftype>, Vec<$ftype>)| map_simd2::<_, 16>(x, y, |$x, $y| $call),
+            cpp: |func, sample, result| func(sample.0.as_ptr(), sample.1.as_ptr(), result)
+        )
+    };
+}
+
+fn accuracy_exp(reports: &mut Vec<Report>) {
+    accuracy_func!(reports, -50.0..50.0, exp, f32);
+    accuracy_func!(reports, -50.0..50.0, exp, f64);
+    accuracy_func!(reports, -50.0..50.0, exp2, f32);
+    accuracy_func!(reports, -50.0..50.0, exp2, f64);
+    accuracy_func!(reports, -50.0..50.0, exp_m1, f32);
+    accuracy_func!(reports, -50.0..50.0, exp_m1, f64);
+}
+
+fn accuracy_trig(reports: &mut Vec<Report>) {
+    accuracy_func!(reports, -1e4..1e4, sin, f32);
+    accuracy_func!(reports, -1e4..1e4, sin, f64);
+    accuracy_func!(reports, -1e4..1e4, cos, f32);
+    accuracy_func!(reports, -1e4..1e4, cos, f64);
+    accuracy_func!(reports, -1e4..1e4, tan, f32);
+    accuracy_func!(reports, -1e4..1e4, tan, f64);
+
+    accuracy_func!(reports, -1.0..1.0, asin, f32);
+    accuracy_func!(reports, -1.0..1.0, asin, f64);
+    accuracy_func!(reports, -1.0..1.0, acos, f32);
+    accuracy_func!(reports, -1.0..1.0, acos, f64);
+    accuracy_func!(reports, -1e3..1e3, atan, f32);
+    accuracy_func!(reports, -1e3..1e3, atan, f64);
+
+    accuracy_func2!(reports, -1e3..1e3, atan2, f32, |x, y| y.atan2(x));
+    accuracy_func2!(reports, -1e3..1e3, atan2, f64, |x, y| y.atan2(x));
+}
+
+fn format_ulps(ulps: f64) -> String {
+    if ulps < 10.0 {
+        format!("{ulps:.2}")
+    } else {
+        format!("{ulps:.0}")
+    }
+}
+
+/// Prints a table per float type and distribution in the format of `benchformat.py`. A cell is
+/// `max / mean`, the lowest maximum in a row is highlighted.
+fn print_markdown(reports: &[Report]) {
+    let mut tables: BTreeMap<_, BTreeMap<_, BTreeMap<_, _>>> = BTreeMap::new();
+    for report in reports {
+        tables
+            .entry((report.ftype, report.distribution.name()))
+            .or_default()
+            .entry(report.function)
+            .or_default()
+            .insert(report.library, (report.max_ulps, report.mean_ulps));
+    }
+
+    for ((ftype, distribution), rows) in tables {
+        let libraries: Vec<_> = LIBRARIES
+            .into_iter()
+            .filter(|library| rows.values().any(|row| row.contains_key(library)))
+            .collect();
+        let mut table = vec![std::iter::once(format!("{ftype} ({distribution})"))
+            .chain(libraries.iter().map(|library| library.to_string()))
+            .collect::<Vec<_>>()];
+        for (function, row) in rows {
+            let best = row
+                .values()
+                .map(|&(max, _)| max)
+                .fold(f64::INFINITY, f64::min);
+            let cells = libraries.iter().map(|library| match row.get(library) {
+                Some(&(max, mean)) => {
+                    let cell = format!("{} / {}", format_ulps(max), format_ulps(mean));
+                    if max == best {
+                        format!("`{cell}`")
+                    } else {
+                        cell
+                    }
+                }
+                None => String::new(),
+            });
+            table.push(
+                std::iter::once(format!("`{function}`"))
+                    .chain(cells)
+                    .collect(),
+            );
+        }
+
+        let widths: Vec<_> = (0..table[0].len())
+            .map(|column| table.iter().map(|row| row[column].len()).max().unwrap())
+            .collect();
+        let print_row = |cells: Vec<String>| {
+            let cells: Vec<_> = std::iter::zip(cells, &widths)
+                .map(|(cell, &width)| format!("{cell:width$}"))
+                .collect();
+            println!("| {} |", cells.join(" | "));
+        };
+        let mut table = table.into_iter();
+        print_row(table.next().unwrap());
+        print_row(widths.iter().map(|&width| "-".repeat(width)).collect());
+        table.for_each(print_row);
+        println!();
+    }
+}
+
+fn print_json(reports: &[Report]) {
+    let reports: Vec<_> = reports
+        .iter()
+        .map(|report| {
+            serde_json::json!({
+                "function": report.function,
+                "ftype": report.ftype,
+                "distribution": report.distribution.name(),
+                "library": report.library,
+                "max_ulps": report.max_ulps,
+                "mean_ulps": report.mean_ulps,
+            })
+        })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&reports).unwrap());
+}
+
+fn main() {
+    let mut reports = Vec::new();
+    accuracy_exp(&mut reports);
+    accuracy_trig(&mut reports);
+
+    if std::env::args().any(|arg| arg == "--json") {
+        print_json(&reports);
+    } else {
+        print_markdown(&reports);
+    }
+}
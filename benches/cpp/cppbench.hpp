@@ -9,7 +9,15 @@ static constexpr size_t BENCH_POINTS = 200'000;
 template <class X>
 using Func = X (*)(X);
 
+template <class X>
+using Func2 = X (*)(X, X);
+
 #define CPP_BENCH_FUNC_NAME(func, ftype, suffix) func##_##ftype##_##suffix
 
 #define CPP_BENCH_FUNC_DECL(func, ftype, suffix) \
     void CPP_BENCH_FUNC_NAME(func, ftype, suffix)(const ftype *__restrict__ x, ftype *__restrict__ result)
+
+// Benchmarks of binary functions take coordinates of the points of a 2D sample
+#define CPP_BENCH_FUNC2_DECL(func, ftype, suffix)  \
+    void CPP_BENCH_FUNC_NAME(func, ftype, suffix)( \
+        const ftype *__restrict__ x, const ftype *__restrict__ y, ftype *__restrict__ result)
@@ -9,6 +9,17 @@ void libmvec_bench_impl(const T *__restrict__ x, T *__restrict__ result) {
         result[i] = func(x[i]);
     }
 }
+
+template <class T, Func2<T> func>
+void libmvec_bench2_impl(const T *__restrict__ x, const T *__restrict__ y, T *__restrict__ result) {
+    for (size_t i = 0; i < BENCH_POINTS; ++i) {
+        result[i] = func(x[i], y[i]);
+    }
+}
+
+// angle of the point (x, y)
+f32 atan2f_xy(f32 x, f32 y) { return atan2f(y, x); }
+f64 atan2_xy(f64 x, f64 y) { return atan2(y, x); }
 }  // namespace
 
 namespace bench {
@@ -28,5 +39,8 @@ IMPL_LIBMVEC_BENCHES(asin)
 IMPL_LIBMVEC_BENCHES(acos)
 IMPL_LIBMVEC_BENCHES(atan)
 
+CPP_BENCH_FUNC2_DECL(atan2, f32, libmvec) { libmvec_bench2_impl<f32, atan2f_xy>(x, y, result); }
+CPP_BENCH_FUNC2_DECL(atan2, f64, libmvec) { libmvec_bench2_impl<f64, atan2_xy>(x, y, result); }
+
 #undef IMPL_LIBMVEC_BENCHES
 }  // namespace benches
@@ -17,5 +17,8 @@ DEF_VECLIB_BENCHES(asin)
 DEF_VECLIB_BENCHES(acos)
 DEF_VECLIB_BENCHES(atan)
 
+CPP_BENCH_FUNC2_DECL(atan2, f32, libmvec);
+CPP_BENCH_FUNC2_DECL(atan2, f64, libmvec);
+
 #undef DEF_VECLIB_BENCHES
 }  // namespace bench
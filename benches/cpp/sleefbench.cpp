@@ -0,0 +1,120 @@
+#include "sleefbench.hpp"
+
+#include <sleef.h>
+
+// Widest vectors supported by the target, functions are chosen by SLEEF's dispatchers
+#if defined(__AVX512F__)
+#define SLEEF_F32(func, ulps) Sleef_##func##f16_##ulps
+#define SLEEF_F64(func, ulps) Sleef_##func##d8_##ulps
+using VecF32 = __m512;
+using VecF64 = __m512d;
+#elif defined(__AVX__)
+#define SLEEF_F32(func, ulps) Sleef_##func##f8_##ulps
+#define SLEEF_F64(func, ulps) Sleef_##func##d4_##ulps
+using VecF32 = __m256;
+using VecF64 = __m256d;
+#elif defined(__SSE2__) || defined(__ARM_NEON)
+#define SLEEF_F32(func, ulps) Sleef_##func##f4_##ulps
+#define SLEEF_F64(func, ulps) Sleef_##func##d2_##ulps
+#if defined(__SSE2__)
+using VecF32 = __m128;
+using VecF64 = __m128d;
+#else
+using VecF32 = float32x4_t;
+using VecF64 = float64x2_t;
+#endif
+#else
+#error "SLEEF benchmarks require SSE2 or NEON"
+#endif
+
+namespace {
+#if defined(__AVX512F__)
+VecF32 load(const f32 *x) { return _mm512_loadu_ps(x); }
+VecF64 load(const f64 *x) { return _mm512_loadu_pd(x); }
+void store(f32 *result, VecF32 vec) { _mm512_storeu_ps(result, vec); }
+void store(f64 *result, VecF64 vec) { _mm512_storeu_pd(result, vec); }
+#elif defined(__AVX__)
+VecF32 load(const f32 *x) { return _mm256_loadu_ps(x); }
+VecF64 load(const f64 *x) { return _mm256_loadu_pd(x); }
+void store(f32 *result, VecF32 vec) { _mm256_storeu_ps(result, vec); }
+void store(f64 *result, VecF64 vec) { _mm256_storeu_pd(result, vec); }
+#elif defined(__SSE2__)
+VecF32 load(const f32 *x) { return _mm_loadu_ps(x); }
+VecF64 load(const f64 *x) { return _mm_loadu_pd(x); }
+void store(f32 *result, VecF32 vec) { _mm_storeu_ps(result, vec); }
+void store(f64 *result, VecF64 vec) { _mm_storeu_pd(result, vec); }
+#else
+VecF32 load(const f32 *x) { return vld1q_f32(x); }
+VecF64 load(const f64 *x) { return vld1q_f64(x); }
+void store(f32 *result, VecF32 vec) { vst1q_f32(result, vec); }
+void store(f64 *result, VecF64 vec) { vst1q_f64(result, vec); }
+#endif
+
+template <class Scalar, class Vec, Func<Vec> func>
+void sleef_bench_impl(const Scalar *__restrict__ x, Scalar *__restrict__ result) {
+    for (size_t i = 0; i < BENCH_POINTS; i += sizeof(Vec) / sizeof(Scalar)) {
+        store(result + i, func(load(x + i)));
+    }
+}
+
+template <class Scalar, class Vec, Func2<Vec> func>
+void sleef_bench2_impl(const Scalar *__restrict__ x, const Scalar *__restrict__ y, Scalar *__restrict__ result) {
+    for (size_t i = 0; i < BENCH_POINTS; i += sizeof(Vec) / sizeof(Scalar)) {
+        store(result + i, func(load(x + i), load(y + i)));
+    }
+}
+
+// angle of the point (x, y)
+template <class Vec, Func2<Vec> atan2>
+Vec atan2_xy(Vec x, Vec y) {
+    return atan2(y, x);
+}
+}  // namespace
+
+namespace bench {
+#define IMPL_SLEEF_BENCHES(func, sleef_func, ulps)                             \
+    CPP_BENCH_FUNC_DECL(func, f32, sleef##ulps) {                              \
+        sleef_bench_impl<f32, VecF32, SLEEF_F32(sleef_func, ulps)>(x, result); \
+    }                                                                          \
+    CPP_BENCH_FUNC_DECL(func, f64, sleef##ulps) {                              \
+        sleef_bench_impl<f64, VecF64, SLEEF_F64(sleef_func, ulps)>(x, result); \
+    }
+
+IMPL_SLEEF_BENCHES(exp, exp, u10)
+IMPL_SLEEF_BENCHES(exp2, exp2, u10)
+IMPL_SLEEF_BENCHES(exp2, exp2, u35)
+IMPL_SLEEF_BENCHES(exp_m1, expm1, u10)
+// SLEEF provides only 1.0-ULP versions of exp and expm1, so they are used in place of 3.5-ULP ones
+CPP_BENCH_FUNC_DECL(exp, f32, sleefu35) { sleef_bench_impl<f32, VecF32, SLEEF_F32(exp, u10)>(x, result); }
+CPP_BENCH_FUNC_DECL(exp, f64, sleefu35) { sleef_bench_impl<f64, VecF64, SLEEF_F64(exp, u10)>(x, result); }
+CPP_BENCH_FUNC_DECL(exp_m1, f32, sleefu35) { sleef_bench_impl<f32, VecF32, SLEEF_F32(expm1, u10)>(x, result); }
+CPP_BENCH_FUNC_DECL(exp_m1, f64, sleefu35) { sleef_bench_impl<f64, VecF64, SLEEF_F64(expm1, u10)>(x, result); }
+
+IMPL_SLEEF_BENCHES(sin, sin, u10)
+IMPL_SLEEF_BENCHES(sin, sin, u35)
+IMPL_SLEEF_BENCHES(cos, cos, u10)
+IMPL_SLEEF_BENCHES(cos, cos, u35)
+IMPL_SLEEF_BENCHES(tan, tan, u10)
+IMPL_SLEEF_BENCHES(tan, tan, u35)
+
+IMPL_SLEEF_BENCHES(asin, asin, u10)
+IMPL_SLEEF_BENCHES(asin, asin, u35)
+IMPL_SLEEF_BENCHES(acos, acos, u10)
+IMPL_SLEEF_BENCHES(acos, acos, u35)
+IMPL_SLEEF_BENCHES(atan, atan, u10)
+IMPL_SLEEF_BENCHES(atan, atan, u35)
+
+#define IMPL_SLEEF_BENCHES2(func, sleef_func, ulps)                                                   \
+    CPP_BENCH_FUNC2_DECL(func, f32, sleef##ulps) {                                                    \
+        sleef_bench2_impl<f32, VecF32, func##_xy<VecF32, SLEEF_F32(sleef_func, ulps)>>(x, y, result); \
+    }                                                                                                 \
+    CPP_BENCH_FUNC2_DECL(func, f64, sleef##ulps) {                                                    \
+        sleef_bench2_impl<f64, VecF64, func##_xy<VecF64, SLEEF_F64(sleef_func, ulps)>>(x, y, result); \
+    }
+
+IMPL_SLEEF_BENCHES2(atan2, atan2, u10)
+IMPL_SLEEF_BENCHES2(atan2, atan2, u35)
+
+#undef IMPL_SLEEF_BENCHES
+#undef IMPL_SLEEF_BENCHES2
+}  // namespace bench
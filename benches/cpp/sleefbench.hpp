@@ -0,0 +1,28 @@
+#pragma once
+
+#include "cppbench.hpp"
+
+namespace bench {
+#define DEF_SLEEF_BENCHES(func)                \
+    CPP_BENCH_FUNC_DECL(func, f32, sleefu10); \
+    CPP_BENCH_FUNC_DECL(func, f64, sleefu10); \
+    CPP_BENCH_FUNC_DECL(func, f32, sleefu35); \
+    CPP_BENCH_FUNC_DECL(func, f64, sleefu35);
+
+DEF_SLEEF_BENCHES(exp)
+DEF_SLEEF_BENCHES(exp2)
+DEF_SLEEF_BENCHES(exp_m1)
+DEF_SLEEF_BENCHES(sin)
+DEF_SLEEF_BENCHES(cos)
+DEF_SLEEF_BENCHES(tan)
+DEF_SLEEF_BENCHES(asin)
+DEF_SLEEF_BENCHES(acos)
+DEF_SLEEF_BENCHES(atan)
+
+CPP_BENCH_FUNC2_DECL(atan2, f32, sleefu10);
+CPP_BENCH_FUNC2_DECL(atan2, f64, sleefu10);
+CPP_BENCH_FUNC2_DECL(atan2, f32, sleefu35);
+CPP_BENCH_FUNC2_DECL(atan2, f64, sleefu35);
+
+#undef DEF_SLEEF_BENCHES
+}  // namespace bench
@@ -13,6 +13,23 @@ void vcl_bench_impl(const Scalar *__restrict__ x, Scalar *__restrict__ result) {
         func(x_vec).store(result + i);
     }
 }
+
+template <class Scalar, class Vec, Func2<Vec> func>
+void vcl_bench2_impl(const Scalar *__restrict__ x, const Scalar *__restrict__ y, Scalar *__restrict__ result) {
+    Vec x_vec;
+    Vec y_vec;
+    for (size_t i = 0; i < BENCH_POINTS; i += Vec::size()) {
+        x_vec.load(x + i);
+        y_vec.load(y + i);
+        func(x_vec, y_vec).store(result + i);
+    }
+}
+
+// angle of the point (x, y)
+template <class Vec>
+Vec atan2_xy(Vec x, Vec y) {
+    return vcl::atan2(y, x);
+}
 }  // namespace
 
 namespace bench {
@@ -32,15 +49,8 @@ IMPL_VCL_BECHES(asin)
 IMPL_VCL_BECHES(acos)
 IMPL_VCL_BECHES(atan)
 
-void atan2_f32_vcl(const float *x, const float *y, float *result) {
-    vcl::Vec16f x_vec;
-    vcl::Vec16f y_vec;
-    for (size_t i = 0; i < BENCH_POINTS; i += vcl::Vec16f::size()) {
-        x_vec.load(x + i);
-        y_vec.load(y + i);
-        vcl::atan2(y_vec, x_vec).store(result + i);
-    }
-}
+CPP_BENCH_FUNC2_DECL(atan2, f32, vcl) { vcl_bench2_impl<f32, vcl::Vec16f, atan2_xy>(x, y, result); }
+CPP_BENCH_FUNC2_DECL(atan2, f64, vcl) { vcl_bench2_impl<f64, vcl::Vec8d, atan2_xy>(x, y, result); }
 
 #undef IMPL_VCL_BECHES
 }  // namespace bench
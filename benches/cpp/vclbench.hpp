@@ -17,5 +17,8 @@ DEF_VCL_BENCHES(asin)
 DEF_VCL_BENCHES(acos)
 DEF_VCL_BENCHES(atan)
 
+CPP_BENCH_FUNC2_DECL(atan2, f32, vcl);
+CPP_BENCH_FUNC2_DECL(atan2, f64, vcl);
+
 #undef DEF_VCL_BENCHES
 }  // namespace bench
@@ -17,6 +17,7 @@ pub mod ffi {
         unsafe fn asin_f32_vcl(x: *const f32, result: *mut f32);
         unsafe fn acos_f32_vcl(x: *const f32, result: *mut f32);
         unsafe fn atan_f32_vcl(x: *const f32, result: *mut f32);
+        unsafe fn atan2_f32_vcl(x: *const f32, y: *const f32, result: *mut f32);
 
         unsafe fn exp_f64_vcl(x: *const f64, result: *mut f64);
         unsafe fn exp2_f64_vcl(x: *const f64, result: *mut f64);
@@ -29,6 +30,7 @@ pub mod ffi {
         unsafe fn asin_f64_vcl(x: *const f64, result: *mut f64);
         unsafe fn acos_f64_vcl(x: *const f64, result: *mut f64);
         unsafe fn atan_f64_vcl(x: *const f64, result: *mut f64);
+        unsafe fn atan2_f64_vcl(x: *const f64, y: *const f64, result: *mut f64);
     }
 }
 
@@ -0,0 +1,345 @@
+//! Criterion benchmarks reporting throughput of every function for each float type and lane count.
+//!
+//! Every function is benchmarked on inputs from each of `sample::Distribution`s, the ID of a
+//! benchmark is `function/ftype/distribution/variant`.
+//!
+//! Besides the usual Criterion reports, the latest results of all the benchmarks found in the
+//! output directory are collected into `criterion/summary.json` in the target directory, so runs
+//! on different commits can be compared. Use `benchformat.py --json` to turn it into tables.
+#![feature(portable_simd)]
+
+#[allow(dead_code)]
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+#[allow(dead_code, unused_macros)]
+#[macro_use]
+mod sample;
+use sample::{Distribution, BENCH_POINTS};
+
+use criterion::{black_box, BenchmarkId, Criterion, Throughput};
+use simd_addons::math::{Exponent, Trigonometry};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    simd::{prelude::*, LaneCount, SimdElement, SupportedLaneCount},
+};
+
+#[cfg(feature = "vectorclass_bench")]
+#[path = "cpp/vclbench.rs"]
+mod vclbench;
+
+#[cfg(feature = "libmvec_bench")]
+#[path = "cpp/libmvecbench.rs"]
+mod libmvecbench;
+
+#[cfg(feature = "sleef_bench")]
+#[path = "cpp/sleefbench.rs"]
+mod sleefbench;
+
+/// Applies `func` to `x` by vectors of `N` lanes.
+#[inline(always)]
+fn map_simd<T, const N: usize>(x: &[T], result: &mut [T], func: impl Fn(Simd<T, N>) -> Simd<T, N>)
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    for (x, res) in std::iter::zip(x.chunks_exact(N), result.chunks_exact_mut(N)) {
+        func(Simd::from_slice(x)).copy_to_slice(res);
+    }
+}
+
+/// Applies `func` to `x` and `y` by vectors of `N` lanes.
+#[inline(always)]
+fn map_simd2<T, const N: usize>(
+    x: &[T],
+    y: &[T],
+    result: &mut [T],
+    func: impl Fn(Simd<T, N>, Simd<T, N>) -> Simd<T, N>,
+) where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    for ((x, y), res) in std::iter::zip(
+        std::iter::zip(x.chunks_exact(N), y.chunks_exact(N)),
+        result.chunks_exact_mut(N),
+    ) {
+        func(Simd::from_slice(x), Simd::from_slice(y)).copy_to_slice(res);
+    }
+}
+
+macro_rules! bench_func {
+    ($c: expr, $range: expr, $func: tt, $ftype: ident) => {
+        paste::paste! {
+        for distribution in Distribution::ALL {
+            let data = sample_of!(distribution, $range, $ftype);
+            let mut result: Vec<$ftype> = vec![0.0; BENCH_POINTS];
+            let mut group = $c.benchmark_group(format!(
+                "{}/{}/{distribution}",
+                stringify!($func),
+                stringify!($ftype)
+            ));
+            group.throughput(Throughput::Elements(BENCH_POINTS as u64));
+
+            bench_func!(@lanes group, data, result, $func, 4, 8, 16, 32, 64);
+
+            group.bench_function("scalar", |b| {
+                b.iter(|| {
+                    for (x, res) in std::iter::zip(black_box(&data), &mut result) {
+                        *res = x.$func();
+                    }
+                })
+            });
+
+            #[cfg(feature = "vectorclass_bench")]
+            group.bench_function("vcl", |b| {
+                b.iter(|| unsafe {
+                    vclbench::[< $func _ $ftype _vcl >](black_box(data.as_ptr()), result.as_mut_ptr())
+                })
+            });
+
+            #[cfg(feature = "libmvec_bench")]
+            group.bench_function("libmvec", |b| {
+                b.iter(|| unsafe {
+                    libmvecbench::[< $func _ $ftype _libmvec >](black_box(data.as_ptr()), result.as_mut_ptr())
+                })
+            });
+
+            #[cfg(feature = "sleef_bench")]
+            group.bench_function("sleefu10", |b| {
+                b.iter(|| unsafe {
+                    sleefbench::[< $func _ $ftype _sleefu10 >](black_box(data.as_ptr()), result.as_mut_ptr())
+                })
+            });
+
+            #[cfg(feature = "sleef_bench")]
+            group.bench_function("sleefu35", |b| {
+                b.iter(|| unsafe {
+                    sleefbench::[< $func _ $ftype _sleefu35 >](black_box(data.as_ptr()), result.as_mut_ptr())
+                })
+            });
+
+            group.finish();
+        }
+        }
+    };
+
+    (@lanes $group: ident, $data: ident, $result: ident, $func: tt, $( $lanes: literal ),*) => {
+        $(
+        $group.bench_function(BenchmarkId::new("vec", $lanes), |b| {
+            b.iter(|| map_simd::<_, $lanes>(black_box(&$data), &mut $result, |x| x.$func()))
+        });
+        )*
+    };
+}
+
+/// Same as `bench_func!`, but for a function of two arguments, see `bench_func2!` in `math.rs`.
+macro_rules! bench_func2 {
+    ($c: expr, $range: expr, $func: tt, $ftype: ident, |$x: ident, $y: ident| $call: expr) => {
+        paste::paste! {
+        for distribution in Distribution::ALL {
+            let (x_data, y_data) = sample2_of!(distribution, $range, $ftype);
+            let mut result: Vec<$ftype> = vec![0.0; BENCH_POINTS];
+            let mut group = $c.benchmark_group(format!(
+                "{}/{}/{distribution}",
+                stringify!($func),
+                stringify!($ftype)
+            ));
+            group.throughput(Throughput::Elements(BENCH_POINTS as u64));
+
+            bench_func2!(@lanes group, x_data, y_data, result, |$x, $y| $call, 4, 8, 16, 32, 64);
+
+            group.bench_function("scalar", |b| {
+                b.iter(|| {
+                    let points = std::iter::zip(black_box(&x_data), black_box(&y_data));
+                    for ((&$x, &$y), res) in std::iter::zip(points, &mut result) {
+                        *res = $call;
+                    }
+                })
+            });
+
+            #[cfg(feature = "vectorclass_bench")]
+            group.bench_function("vcl", |b| {
+                b.iter(|| unsafe {
+                    vclbench::[< $func _ $ftype _vcl >](
+                        black_box(x_data.as_ptr()),
+                        black_box(y_data.as_ptr()),
+                        result.as_mut_ptr(),
+                    )
+                })
+            });
+
+            #[cfg(feature = "libmvec_bench")]
+            group.bench_function("libmvec", |b| {
+                b.iter(|| unsafe {
+                    libmvecbench::[< $func _ $ftype _libmvec >](
+                        black_box(x_data.as_ptr()),
+                        black_box(y_data.as_ptr()),
+                        result.as_mut_ptr(),
+                    )
+                })
+            });
+
+            #[cfg(feature = "sleef_bench")]
+            group.bench_function("sleefu10", |b| {
+                b.iter(|| unsafe {
+                    sleefbench::[< $func _ $ftype _sleefu10 >](
+                        black_box(x_data.as_ptr()),
+                        black_box(y_data.as_ptr()),
+                        result.as_mut_ptr(),
+                    )
+                })
+            });
+
+            #[cfg(feature = "sleef_bench")]
+            group.bench_function("sleefu35", |b| {
+                b.iter(|| unsafe {
+                    sleefbench::[< $func _ $ftype _sleefu35 >](
+                        black_box(x_data.as_ptr()),
+                        black_box(y_data.as_ptr()),
+                        result.as_mut_ptr(),
+                    )
+                })
+            });
+
+            group.finish();
+        }
+        }
+    };
+
+    (
+        @lanes $group: ident, $x_data: ident, $y_data: ident, $result: ident,
+        |$x: ident, $y: ident| $call: expr, $( $lanes: literal ),*
+    ) => {
+        $(
+        $group.bench_function(BenchmarkId::new("vec", $lanes), |b| {
+            b.iter(|| {
+                map_simd2::<_, $lanes>(
+                    black_box(&$x_data),
+                    black_box(&$y_data),
+                    &mut $result,
+                    |$x, $y| $call,
+                )
+            })
+        });
+        )*
+    };
+}
+
+fn bench_exp(c: &mut Criterion) {
+    bench_func!(c, -50.0..50.0, exp, f32);
+    bench_func!(c, -50.0..50.0, exp, f64);
+    bench_func!(c, -50.0..50.0, exp2, f32);
+    bench_func!(c, -50.0..50.0, exp2, f64);
+    bench_func!(c, -50.0..50.0, exp_m1, f32);
+    bench_func!(c, -50.0..50.0, exp_m1, f64);
+}
+
+fn bench_trig(c: &mut Criterion) {
+    bench_func!(c, -1e4..1e4, sin, f32);
+    bench_func!(c, -1e4..1e4, sin, f64);
+    bench_func!(c, -1e4..1e4, cos, f32);
+    bench_func!(c, -1e4..1e4, cos, f64);
+    bench_func!(c, -1e4..1e4, tan, f32);
+    bench_func!(c, -1e4..1e4, tan, f64);
+
+    bench_func!(c, -1.0..1.0, asin, f32);
+    bench_func!(c, -1.0..1.0, asin, f64);
+    bench_func!(c, -1.0..1.0, acos, f32);
+    bench_func!(c, -1.0..1.0, acos, f64);
+    bench_func!(c, -1e3..1e3, atan, f32);
+    bench_func!(c, -1e3..1e3, atan, f64);
+
+    bench_func2!(c, -1e3..1e3, atan2, f32, |x, y| y.atan2(x));
+    bench_func2!(c, -1e3..1e3, atan2, f64, |x, y| y.atan2(x));
+}
+
+fn output_directory() -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map_or_else(
+            || Path::new(env!("CARGO_MANIFEST_DIR")).join("target"),
+            PathBuf::from,
+        )
+        .join("criterion")
+}
+
+/// Collects the latest results of all the benchmarks in `dir` into `summary.json`.
+fn write_summary(dir: &Path) -> std::io::Result<()> {
+    fn read_json(path: &Path) -> Option<serde_json::Value> {
+        serde_json::from_slice(&fs::read(path).ok()?).ok()
+    }
+
+    fn collect(dir: &Path, entries: &mut Vec<serde_json::Value>) -> std::io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            if path.ends_with("new") {
+                let (Some(benchmark), Some(estimates)) = (
+                    read_json(&path.join("benchmark.json")),
+                    read_json(&path.join("estimates.json")),
+                ) else {
+                    continue;
+                };
+                let Some(group_id) = benchmark["group_id"].as_str() else {
+                    continue;
+                };
+                let mut group_id = group_id.split('/');
+                let (Some(func), Some(ftype)) = (group_id.next(), group_id.next()) else {
+                    continue;
+                };
+                let distribution = group_id.next().unwrap_or("linspace");
+                let mean_ns = estimates["mean"]["point_estimate"]
+                    .as_f64()
+                    .unwrap_or(f64::NAN);
+                let elements = benchmark["throughput"]["Elements"]
+                    .as_f64()
+                    .unwrap_or(f64::NAN);
+                entries.push(serde_json::json!({
+                    "function": func,
+                    "ftype": ftype,
+                    "distribution": distribution,
+                    "variant": benchmark["function_id"],
+                    "lanes": benchmark["value_str"].as_str().and_then(|lanes| lanes.parse::<u32>().ok()),
+                    "mean_ns": mean_ns,
+                    "elements_per_second": elements / mean_ns * 1e9,
+                }));
+            } else {
+                collect(&path, entries)?;
+            }
+        }
+        Ok(())
+    }
+
+    let mut entries = Vec::new();
+    collect(dir, &mut entries)?;
+    entries.sort_by_key(|entry| {
+        let key = |field: &str| entry[field].as_str().unwrap_or_default().to_owned();
+        (
+            key("function"),
+            key("ftype"),
+            key("distribution"),
+            key("variant"),
+            entry["lanes"].as_u64(),
+        )
+    });
+    fs::write(
+        dir.join("summary.json"),
+        serde_json::to_string_pretty(&entries)?,
+    )
+}
+
+fn main() {
+    let dir = output_directory();
+    let mut criterion = Criterion::default()
+        .output_directory(&dir)
+        .configure_from_args();
+    bench_exp(&mut criterion);
+    bench_trig(&mut criterion);
+    criterion.final_summary();
+
+    if let Err(err) = write_summary(&dir) {
+        eprintln!("failed to write benchmarks summary: {err}");
+    }
+}
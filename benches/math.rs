@@ -2,15 +2,19 @@
 #![allow(internal_features)]
 extern crate test;
 
+#[allow(dead_code)]
 #[path = "../tests/common/mod.rs"]
 mod common;
 use common::Linspace;
 
+#[allow(dead_code, unused_macros)]
+#[macro_use]
+mod sample;
+use sample::{generate_atan2_bench_sample, BENCH_POINTS};
+
 use simd_addons::math::{Exponent, Trigonometry};
 use std::simd::prelude::*;
 
-const BENCH_POINTS: usize = 200_000;
-
 #[cfg(feature = "vectorclass_bench")]
 #[path = "cpp/vclbench.rs"]
 mod vclbench;
@@ -19,6 +23,10 @@ mod vclbench;
 #[path = "cpp/libmvecbench.rs"]
 mod libmvecbench;
 
+#[cfg(feature = "sleef_bench")]
+#[path = "cpp/sleefbench.rs"]
+mod sleefbench;
+
 macro_rules! bench_func {
     ($range: expr, $func: tt, $ftype: ty) => {
         bench_func!($range, $func, $ftype, 64);
@@ -62,6 +70,7 @@ macro_rules! bench_func {
         #[cfg(feature = "vectorclass_bench")]
         #[bench]
         fn [<bench_ $func _ $ftype _vcl >](b: &mut test::Bencher) {
+            #[allow(clippy::all)]
             let data: Vec<$ftype> = ($range as $ftype).linspace(BENCH_POINTS).collect();
             let mut result = vec![0.0; BENCH_POINTS];
             b.iter(|| {
@@ -72,12 +81,137 @@ macro_rules! bench_func {
         #[cfg(feature = "libmvec_bench")]
         #[bench]
         fn [<bench_ $func _ $ftype _libmvec >](b: &mut test::Bencher) {
+            #[allow(clippy::all)]
             let data: Vec<$ftype> = ($range as $ftype).linspace(BENCH_POINTS).collect();
             let mut result = vec![0.0; BENCH_POINTS];
             b.iter(|| {
                 unsafe { libmvecbench::[<$func _ $ftype _libmvec>](data.as_ptr(), result.as_mut_ptr()) }
             });
         }
+
+        #[cfg(feature = "sleef_bench")]
+        #[bench]
+        fn [<bench_ $func _ $ftype _sleefu10 >](b: &mut test::Bencher) {
+            #[allow(clippy::all)]
+            let data: Vec<$ftype> = ($range as $ftype).linspace(BENCH_POINTS).collect();
+            let mut result = vec![0.0; BENCH_POINTS];
+            b.iter(|| {
+                unsafe { sleefbench::[<$func _ $ftype _sleefu10>](data.as_ptr(), result.as_mut_ptr()) }
+            });
+        }
+
+        #[cfg(feature = "sleef_bench")]
+        #[bench]
+        fn [<bench_ $func _ $ftype _sleefu35 >](b: &mut test::Bencher) {
+            #[allow(clippy::all)]
+            let data: Vec<$ftype> = ($range as $ftype).linspace(BENCH_POINTS).collect();
+            let mut result = vec![0.0; BENCH_POINTS];
+            b.iter(|| {
+                unsafe { sleefbench::[<$func _ $ftype _sleefu35>](data.as_ptr(), result.as_mut_ptr()) }
+            });
+        }
+        }
+    };
+}
+
+/// Benchmarks a function of two arguments on a 2D sample. `$sample` returns `x` and `y`
+/// coordinates of the points as `Vec<f64>`, `$call` computes the function of a single point.
+macro_rules! bench_func2 {
+    ($sample: expr, $func: tt, $ftype: ty, |$x: ident, $y: ident| $call: expr) => {
+        bench_func2!($sample, $func, $ftype, 64, |$x, $y| $call);
+    };
+
+    ($sample: expr, $func: tt, $ftype: ty, $vecsize: literal, |$x: ident, $y: ident| $call: expr) => {
+        paste::paste! {
+        #[bench]
+        fn [< bench_ $func _ $ftype _vec >](b: &mut test::Bencher) {
+            let (x_data, y_data) = sample_as!($sample, $ftype);
+            let (x, y) = (x_data.as_slice(), y_data.as_slice());
+            let mut result_vec: Vec<$ftype> = vec![0.0; BENCH_POINTS];
+            let result = result_vec.as_mut_slice();
+            b.iter(|| {
+                assert_eq!(x.len(), BENCH_POINTS);
+                assert_eq!(y.len(), BENCH_POINTS);
+                assert_eq!(result.len(), BENCH_POINTS);
+                for i in (0..BENCH_POINTS).step_by($vecsize) {
+                    let $x = Simd::<_, $vecsize>::from_slice(&x[i..]);
+                    let $y = Simd::<_, $vecsize>::from_slice(&y[i..]);
+                    $call.copy_to_slice(&mut result[i..]);
+                }
+            })
+        }
+
+        #[bench]
+        fn [< bench_ $func _ $ftype _scalar >](b: &mut test::Bencher) {
+            let (x_data, y_data) = sample_as!($sample, $ftype);
+            let mut result: Vec<$ftype> = vec![0.0; BENCH_POINTS];
+            b.iter(|| {
+                assert_eq!(result.len(), BENCH_POINTS);
+                for ((&$x, &$y), res) in std::iter::zip(std::iter::zip(&x_data, &y_data), &mut result) {
+                    *res = $call;
+                }
+            });
+        }
+
+        #[cfg(feature = "vectorclass_bench")]
+        #[bench]
+        fn [< bench_ $func _ $ftype _vcl >](b: &mut test::Bencher) {
+            let (x_data, y_data) = sample_as!($sample, $ftype);
+            let mut result: Vec<$ftype> = vec![0.0; BENCH_POINTS];
+            b.iter(|| {
+                unsafe {
+                    vclbench::[< $func _ $ftype _vcl >](x_data.as_ptr(), y_data.as_ptr(), result.as_mut_ptr())
+                }
+            });
+        }
+
+        #[cfg(feature = "libmvec_bench")]
+        #[bench]
+        fn [< bench_ $func _ $ftype _libmvec >](b: &mut test::Bencher) {
+            let (x_data, y_data) = sample_as!($sample, $ftype);
+            let mut result: Vec<$ftype> = vec![0.0; BENCH_POINTS];
+            b.iter(|| {
+                unsafe {
+                    libmvecbench::[< $func _ $ftype _libmvec >](
+                        x_data.as_ptr(),
+                        y_data.as_ptr(),
+                        result.as_mut_ptr(),
+                    )
+                }
+            });
+        }
+
+        #[cfg(feature = "sleef_bench")]
+        #[bench]
+        fn [< bench_ $func _ $ftype _sleefu10 >](b: &mut test::Bencher) {
+            let (x_data, y_data) = sample_as!($sample, $ftype);
+            let mut result: Vec<$ftype> = vec![0.0; BENCH_POINTS];
+            b.iter(|| {
+                unsafe {
+                    sleefbench::[< $func _ $ftype _sleefu10 >](
+                        x_data.as_ptr(),
+                        y_data.as_ptr(),
+                        result.as_mut_ptr(),
+                    )
+                }
+            });
+        }
+
+        #[cfg(feature = "sleef_bench")]
+        #[bench]
+        fn [< bench_ $func _ $ftype _sleefu35 >](b: &mut test::Bencher) {
+            let (x_data, y_data) = sample_as!($sample, $ftype);
+            let mut result: Vec<$ftype> = vec![0.0; BENCH_POINTS];
+            b.iter(|| {
+                unsafe {
+                    sleefbench::[< $func _ $ftype _sleefu35 >](
+                        x_data.as_ptr(),
+                        y_data.as_ptr(),
+                        result.as_mut_ptr(),
+                    )
+                }
+            });
+        }
         }
     };
 }
@@ -103,7 +237,5 @@ bench_func!(-1.0..1.0, acos, f64);
 bench_func!(-1e3..1e3, atan, f32);
 bench_func!(-1e3..1e3, atan, f64);
 
-#[allow(unused)]
-fn generate_atan2_bench_sample() -> (Vec<f32>, Vec<f32>) {
-    todo!()
-}
+bench_func2!(generate_atan2_bench_sample, atan2, f32, |x, y| y.atan2(x));
+bench_func2!(generate_atan2_bench_sample, atan2, f64, |x, y| y.atan2(x));
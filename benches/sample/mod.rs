@@ -0,0 +1,176 @@
+//! Input samples shared by the benchmarks.
+
+use crate::common::Linspace;
+use std::{f64::consts::TAU, fmt, ops::Range};
+
+/// Number of points in every sample. The C++ benchmarks assume the same value.
+pub const BENCH_POINTS: usize = 200_000;
+
+/// Converts the 2D sample generated by `$sample` to vectors of `$ftype`.
+macro_rules! sample_as {
+    ($sample: expr, $ftype: ty) => {{
+        #[allow(clippy::unnecessary_cast)]
+        let convert = |data: Vec<f64>| data.into_iter().map(|v| v as $ftype).collect::<Vec<_>>();
+        let (x, y) = $sample();
+        (convert(x), convert(y))
+    }};
+}
+
+/// Generates a sample of `$ftype` from `$distribution` over `$range`.
+macro_rules! sample_of {
+    ($distribution: expr, $range: expr, $ftype: ident) => {{
+        #[allow(clippy::unnecessary_cast)]
+        $distribution
+            .sample($range, $ftype::MIN_EXP..$ftype::MAX_EXP, 0)
+            .into_iter()
+            .map(|v| v as $ftype)
+            .collect::<Vec<$ftype>>()
+    }};
+}
+
+/// Generates a 2D sample of `$ftype` from `$distribution` over `$range²`.
+macro_rules! sample2_of {
+    ($distribution: expr, $range: expr, $ftype: ident) => {
+        sample_as!(
+            || $distribution.sample2($range, $ftype::MIN_EXP..$ftype::MAX_EXP),
+            $ftype
+        )
+    };
+}
+
+/// Uniform grid of `BENCH_POINTS` points covering `[-1e3, 1e3]²`, row by row.
+pub fn generate_atan2_bench_sample() -> (Vec<f64>, Vec<f64>) {
+    generate_grid(-1e3..1e3)
+}
+
+/// Uniform grid of `BENCH_POINTS` points covering `range²`, row by row.
+fn generate_grid(range: Range<f64>) -> (Vec<f64>, Vec<f64>) {
+    const COLUMNS: usize = 500;
+    const ROWS: usize = BENCH_POINTS / COLUMNS;
+    let xs: Vec<_> = range.linspace(COLUMNS).collect();
+    let ys: Vec<_> = range.linspace(ROWS).collect();
+    ys.iter()
+        .flat_map(|&y| xs.iter().map(move |&x| (x, y)))
+        .unzip()
+}
+
+/// SplitMix64 generator. It is fast, good enough for benchmarks and, unlike generators of external
+/// crates, is guaranteed to produce the same samples forever.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let z = (self.0 ^ (self.0 >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        let z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * f64::EPSILON / 2.0
+    }
+
+    /// Standard normal, by Box-Muller transform.
+    fn next_normal(&mut self) -> f64 {
+        let r = (-2.0 * (1.0 - self.next_f64()).ln()).sqrt();
+        r * (TAU * self.next_f64()).cos()
+    }
+}
+
+/// Distribution of the inputs of a benchmark.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Distribution {
+    /// Sorted evenly spaced points of the range.
+    Linspace,
+    /// Uniformly distributed points of the range.
+    Uniform,
+    /// Normally distributed around the middle of the range, which is 6 standard deviations wide.
+    Normal,
+    /// Magnitude is log-uniform over the whole exponent range of the type, sign is random.
+    LogUniform,
+    /// Uniform over the range, but 1% of the values are infinite or NaN.
+    Specials,
+    /// NaNs only.
+    Nan,
+}
+
+impl Distribution {
+    pub const ALL: [Distribution; 6] = [
+        Distribution::Linspace,
+        Distribution::Uniform,
+        Distribution::Normal,
+        Distribution::LogUniform,
+        Distribution::Specials,
+        Distribution::Nan,
+    ];
+
+    /// Name used in the benchmark IDs.
+    pub fn name(self) -> &'static str {
+        match self {
+            Distribution::Linspace => "linspace",
+            Distribution::Uniform => "uniform",
+            Distribution::Normal => "normal",
+            Distribution::LogUniform => "loguniform",
+            Distribution::Specials => "specials",
+            Distribution::Nan => "nan",
+        }
+    }
+
+    /// Generates `BENCH_POINTS` values. `exponents` are the exponents of finite values of the
+    /// target type, the same `seed` always gives the same sample.
+    pub fn sample(self, range: Range<f64>, exponents: Range<i32>, seed: u64) -> Vec<f64> {
+        const SPECIALS: [f64; 3] = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY];
+
+        let mut rng = SplitMix64(seed);
+        let width = range.end - range.start;
+        let uniform = |rng: &mut SplitMix64| range.start + width * rng.next_f64();
+        (0..BENCH_POINTS)
+            .map(|i| match self {
+                Distribution::Linspace => {
+                    range.start + width * (i as f64) / ((BENCH_POINTS - 1) as f64)
+                }
+                Distribution::Uniform => uniform(&mut rng),
+                Distribution::Normal => {
+                    (range.start + range.end) / 2.0 + width / 6.0 * rng.next_normal()
+                }
+                Distribution::LogUniform => {
+                    let exp = (exponents.start - 1) as f64
+                        + rng.next_f64() * (exponents.end - exponents.start) as f64;
+                    let magnitude = exp.exp2();
+                    if rng.next_u64() & 1 == 0 {
+                        magnitude
+                    } else {
+                        -magnitude
+                    }
+                }
+                Distribution::Specials => {
+                    if rng.next_u64() % 100 == 0 {
+                        SPECIALS[(rng.next_u64() % 3) as usize]
+                    } else {
+                        uniform(&mut rng)
+                    }
+                }
+                Distribution::Nan => f64::NAN,
+            })
+            .collect()
+    }
+
+    /// Generates `BENCH_POINTS` points of `range²`, coordinates are sampled independently. The
+    /// points of `Linspace` form a grid.
+    pub fn sample2(self, range: Range<f64>, exponents: Range<i32>) -> (Vec<f64>, Vec<f64>) {
+        match self {
+            Distribution::Linspace => generate_grid(range),
+            _ => (
+                self.sample(range.clone(), exponents.clone(), 0),
+                self.sample(range, exponents, 1),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
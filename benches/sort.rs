@@ -0,0 +1,107 @@
+//! Criterion benchmarks of `algo::sort` against sorting by `std`.
+//!
+//! The ID of a benchmark is `function/etype/distribution/length/variant`, where the variant is
+//! `simd` or `std`.
+#![feature(portable_simd)]
+
+#[allow(dead_code)]
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+#[allow(dead_code, unused_macros)]
+#[macro_use]
+mod sample;
+use sample::Distribution;
+
+use criterion::{black_box, BatchSize, BenchmarkId, Criterion, Throughput};
+use simd_addons::algo::sort;
+
+const LENGTHS: [usize; 4] = [100, 1_000, 10_000, 100_000];
+
+/// Distributions which make sense for sorting: ordered, random and with few distinct values.
+const DISTRIBUTIONS: [Distribution; 4] = [
+    Distribution::Linspace,
+    Distribution::Uniform,
+    Distribution::Normal,
+    Distribution::LogUniform,
+];
+
+macro_rules! bench_sort {
+    ($c: expr, $etype: ident, $convert: expr, |$data: ident| $std_sort: expr) => {
+        for distribution in DISTRIBUTIONS {
+            let sample: Vec<$etype> = distribution
+                .sample(-1e3..1e3, f32::MIN_EXP..f32::MAX_EXP, 0)
+                .into_iter()
+                .map($convert)
+                .collect();
+            for len in LENGTHS {
+                let input = &sample[..len];
+
+                let mut group =
+                    $c.benchmark_group(format!("sort/{}/{distribution}/{len}", stringify!($etype)));
+                group.throughput(Throughput::Elements(len as u64));
+                group.bench_function(BenchmarkId::from_parameter("simd"), |b| {
+                    b.iter_batched_ref(
+                        || input.to_vec(),
+                        |data| sort::sort(black_box(data)),
+                        BatchSize::SmallInput,
+                    )
+                });
+                group.bench_function(BenchmarkId::from_parameter("std"), |b| {
+                    b.iter_batched_ref(|| input.to_vec(), |$data| $std_sort, BatchSize::SmallInput)
+                });
+                group.finish();
+            }
+        }
+    };
+}
+
+macro_rules! bench_argsort {
+    ($c: expr, $etype: ident, $index: ident, $convert: expr) => {
+        let sample: Vec<$etype> = Distribution::Uniform
+            .sample(-1e3..1e3, f32::MIN_EXP..f32::MAX_EXP, 0)
+            .into_iter()
+            .map($convert)
+            .collect();
+        for len in LENGTHS {
+            let input = &sample[..len];
+
+            let mut group =
+                $c.benchmark_group(format!("argsort/{}/uniform/{len}", stringify!($etype)));
+            group.throughput(Throughput::Elements(len as u64));
+            group.bench_function(BenchmarkId::from_parameter("simd"), |b| {
+                b.iter_batched_ref(
+                    || (input.to_vec(), vec![0; len]),
+                    |(keys, indices)| sort::argsort(black_box(keys), indices),
+                    BatchSize::SmallInput,
+                )
+            });
+            group.bench_function(BenchmarkId::from_parameter("std"), |b| {
+                b.iter_batched_ref(
+                    || (0..len as $index).collect::<Vec<_>>(),
+                    |indices| {
+                        indices.sort_unstable_by(|&a, &b| {
+                            input[a as usize].total_cmp(&input[b as usize])
+                        })
+                    },
+                    BatchSize::SmallInput,
+                )
+            });
+            group.finish();
+        }
+    };
+}
+
+fn main() {
+    let mut criterion = Criterion::default().configure_from_args();
+    bench_sort!(criterion, f32, |x| x as f32, |data| data
+        .sort_unstable_by(f32::total_cmp));
+    bench_sort!(criterion, f64, |x| x, |data| data
+        .sort_unstable_by(f64::total_cmp));
+    bench_sort!(criterion, u32, |x| ((x + 1e3) * 1e6) as u32, |data| data
+        .sort_unstable());
+    bench_sort!(criterion, i64, |x| (x * 1e12) as i64, |data| data
+        .sort_unstable());
+    bench_argsort!(criterion, f32, u32, |x| x as f32);
+    criterion.final_summary();
+}
@@ -1,17 +1,39 @@
 """
 Reads output of `cargo bench` from stdin
 Prints markdown-formatter tables on stdout
+
+With `--json` reads `criterion/summary.json` written by `cargo bench --bench criterion` instead.
+Vector variants are then named by lane count, e.g. `vec16`. A separate table is printed for
+every input distribution, `--distribution NAME` prints only the tables of the given one.
 """
 
+import json
+import sys
 from collections import defaultdict
 from tabulate import tabulate
 from sys import argv
 
-BENCHMARKED_LIBS = argv[1:]
+ARGS = argv[1:]
+JSON_INPUT = "--json" in ARGS
+DISTRIBUTION = None
+if "--distribution" in ARGS:
+    index = ARGS.index("--distribution")
+    DISTRIBUTION = ARGS[index + 1]
+    del ARGS[index : index + 2]
+BENCHMARKED_LIBS = [arg for arg in ARGS if arg != "--json"]
 
 all_bench_data: defaultdict = defaultdict(lambda: defaultdict(defaultdict))
 
-while True:
+if JSON_INPUT:
+    for bench in json.load(sys.stdin):
+        lib = bench["variant"] + str(bench["lanes"] or "")
+        distribution = bench.get("distribution", "linspace")
+        if lib not in BENCHMARKED_LIBS or DISTRIBUTION not in (None, distribution):
+            continue
+        table = f"{bench['ftype']} ({distribution})"
+        all_bench_data[table][bench["function"]][lib] = round(bench["mean_ns"])
+
+while not JSON_INPUT:
     try:
         bench_res_line = input()
     except EOFError:
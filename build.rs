@@ -1,4 +1,47 @@
+use std::{env, path::PathBuf, process::Command};
+
+/// Whether the compiler has `core::simd`, i.e. whether `portable_simd` can be enabled.
+fn has_portable_simd() -> bool {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    let probe = out_dir.join("probe.rs");
+    std::fs::write(
+        &probe,
+        "#![no_std]\n#![feature(portable_simd)]\npub use core::simd::Simd;\n",
+    )
+    .expect("failed to write the probe");
+    // the probe is compiled the way cargo compiles the crate: through the wrappers, outermost
+    // first, and with the flags given to cargo
+    let program: Vec<_> = ["RUSTC_WRAPPER", "RUSTC_WORKSPACE_WRAPPER"]
+        .into_iter()
+        .filter_map(env::var_os)
+        .filter(|wrapper| !wrapper.is_empty())
+        .chain([rustc])
+        .collect();
+    let mut command = Command::new(&program[0]);
+    command.args(&program[1..]);
+    if let Ok(flags) = env::var("CARGO_ENCODED_RUSTFLAGS") {
+        command.args(flags.split('\x1f').filter(|flag| !flag.is_empty()));
+    }
+    command
+        .args(["--crate-type=lib", "--emit=metadata", "--out-dir"])
+        .arg(&out_dir)
+        .arg("--target")
+        .arg(env::var_os("TARGET").expect("TARGET is set by cargo"))
+        .arg(&probe)
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
 fn main() {
+    // `stable` only lets stable compilers build the crate, so the vectors of `core::simd` are
+    // used whenever the compiler has them, and enabling the feature doesn't change the API
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(simd_shim)");
+    if env::var_os("CARGO_FEATURE_STABLE").is_some() && !has_portable_simd() {
+        println!("cargo:rustc-cfg=simd_shim");
+    }
+
     #[cfg(feature = "vectorclass_bench")]
     {
         println!("cargo:rerun-if-changed=benches/cpp/vclbench.cpp");
@@ -22,4 +65,62 @@ fn main() {
             .std("c++17")
             .compile("libmvecbench");
     }
+
+    #[cfg(feature = "sleef_bench")]
+    {
+        // the sources of the pinned release are fetched into `OUT_DIR` once, unless a checkout
+        // of them is given, e.g. for offline builds
+        const SLEEF_TAG: &str = "3.6.1";
+        println!("cargo:rerun-if-env-changed=SLEEF_SOURCE_DIR");
+        let sleef_dir = env::var_os("SLEEF_SOURCE_DIR").map_or_else(
+            || {
+                let dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo"))
+                    .join(format!("sleef-{SLEEF_TAG}"));
+                if !dir.join("CMakeLists.txt").exists() {
+                    let status = Command::new("git")
+                        .args(["clone", "--depth=1", "--branch", SLEEF_TAG])
+                        .arg("https://github.com/shibatch/sleef")
+                        .arg(&dir)
+                        .status()
+                        .expect("failed to run git");
+                    assert!(
+                        status.success(),
+                        "failed to fetch SLEEF {SLEEF_TAG}, set SLEEF_SOURCE_DIR to a checkout \
+                         of it to build offline"
+                    );
+                }
+                dir
+            },
+            PathBuf::from,
+        );
+        assert!(
+            sleef_dir.join("CMakeLists.txt").exists(),
+            "{} isn't a checkout of the SLEEF sources",
+            sleef_dir.display()
+        );
+        let sleef = cmake::Config::new(&sleef_dir)
+            .define("CMAKE_INSTALL_LIBDIR", "lib")
+            .define("BUILD_SHARED_LIBS", "OFF")
+            .define("SLEEF_BUILD_SHARED_LIBS", "OFF")
+            .define("SLEEF_BUILD_TESTS", "OFF")
+            .define("SLEEF_BUILD_DFT", "OFF")
+            .define("SLEEF_BUILD_QUAD", "OFF")
+            .define("SLEEF_BUILD_GNUABI_LIBS", "OFF")
+            .build();
+        println!(
+            "cargo:rustc-link-search=native={}",
+            sleef.join("lib").display()
+        );
+        println!("cargo:rustc-link-lib=static=sleef");
+
+        println!("cargo:rerun-if-changed=benches/cpp/sleefbench.cpp");
+        cxx_build::bridge("benches/cpp/sleefbench.rs")
+            .compiler("g++")
+            .file("benches/cpp/sleefbench.cpp")
+            .include(sleef.join("include"))
+            .flag("-march=native")
+            .flag("-Ofast")
+            .std("c++17")
+            .compile("sleefbench");
+    }
 }
@@ -0,0 +1,27 @@
+#![no_main]
+#![feature(portable_simd)]
+
+use libfuzzer_sys::fuzz_target;
+use simd_addons::math::Trigonometry;
+use simd_addons_fuzz::{check_binary, vectors, with_float, with_lanes, Tolerance};
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, data)) = data.split_first() else {
+        return;
+    };
+    with_float!(selector, |F| {
+        with_lanes!(selector, |N| {
+            // consecutive vectors are `y` and `x`
+            for yx in vectors::<F, N>(selector, data).chunks_exact(2) {
+                check_binary(
+                    "atan2",
+                    (yx[0], yx[1]),
+                    Trigonometry::atan2,
+                    Trigonometry::atan2,
+                    |y, x| Some(y.atan2(x)),
+                    Tolerance::DEFAULT,
+                );
+            }
+        });
+    });
+});
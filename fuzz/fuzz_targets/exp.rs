@@ -0,0 +1,58 @@
+#![no_main]
+#![feature(portable_simd)]
+
+use libfuzzer_sys::fuzz_target;
+use simd_addons::math::Exponent;
+use simd_addons_fuzz::{check_unary, vectors, with_float, with_lanes, Tolerance};
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, data)) = data.split_first() else {
+        return;
+    };
+    with_float!(selector, |F| {
+        // errors are checked on the ranges of `tests/math.rs`, results are close to overflow or
+        // underflow outside of them
+        let (exp_limit, exp2_limit): (F, F) = if F::MAX_EXP == 128 {
+            (88.0, 127.0)
+        } else {
+            (709.0, 1023.0)
+        };
+        // `exp_m1(-0.0)` is `0.0`
+        let unsigned_zeros = Tolerance {
+            signed_zeros: false,
+            ..Tolerance::DEFAULT
+        };
+
+        with_lanes!(selector, |N| {
+            for x in vectors::<F, N>(selector, data) {
+                check_unary(
+                    "exp",
+                    x,
+                    Exponent::exp,
+                    Exponent::exp,
+                    |x| Some(x.exp()),
+                    |x| x.abs() <= exp_limit,
+                    Tolerance::DEFAULT,
+                );
+                check_unary(
+                    "exp2",
+                    x,
+                    Exponent::exp2,
+                    Exponent::exp2,
+                    |x| Some(x.exp2()),
+                    |x| x.abs() <= exp2_limit,
+                    Tolerance::DEFAULT,
+                );
+                check_unary(
+                    "exp_m1",
+                    x,
+                    Exponent::exp_m1,
+                    Exponent::exp_m1,
+                    |x| Some(x.exp_m1()),
+                    |x| x.abs() <= exp_limit,
+                    unsigned_zeros,
+                );
+            }
+        });
+    });
+});
@@ -0,0 +1,112 @@
+#![no_main]
+#![feature(portable_simd)]
+
+use libfuzzer_sys::fuzz_target;
+use simd_addons::{
+    math::{Exponent, Trigonometry},
+    polynomial_simd,
+};
+use simd_addons_fuzz::{check_lanes, vectors, with_float, with_lanes, FuzzFloat};
+use std::simd::{Mask, Simd, StdFloat};
+
+/// Checks that the `_masked` variant of `$func` equals the function in the selected lanes and
+/// `$fallback` in the others, and that the `_checked` variant has the same result and raises the
+/// same flags in every lane as in a single lane.
+macro_rules! check_variants {
+    ($trait: ident, $func: ident, $x: expr, $mask: expr, $fallback: expr) => {
+        paste::paste! {{
+            let (x, mask, fallback) = ($x, $mask, $fallback);
+            let result = $trait::$func(x);
+
+            let masked = $trait::[< $func _masked >](x, mask, fallback);
+            for i in 0..x.len() {
+                let expected = if mask.test(i) { result[i] } else { fallback[i] };
+                assert_eq!(
+                    masked[i].bits(),
+                    expected.bits(),
+                    "{}_masked({:?}) = {:?} in lane {i}, mask is {}",
+                    stringify!($func), x[i], masked[i], mask.test(i)
+                );
+            }
+
+            let (checked, flags) = $trait::[< $func _checked >](x);
+            check_lanes(concat!(stringify!($func), "_checked"), &[x], checked, |args| {
+                $trait::$func(args[0])
+            });
+            for i in 0..x.len() {
+                let (_, lane_flags) = $trait::[< $func _checked >](x[i]);
+                assert_eq!(
+                    flags.map(|mask| mask.test(i)),
+                    lane_flags,
+                    "{}_checked({:?}) flags in lane {i}",
+                    stringify!($func), x[i]
+                );
+            }
+        }}
+    };
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, data)) = data.split_first() else {
+        return;
+    };
+    with_float!(selector, |F| {
+        with_lanes!(selector, |N| {
+            // consecutive vectors are the argument and the fallback, bits of the latter also
+            // select the masked lanes
+            for pair in vectors::<F, N>(selector, data).chunks_exact(2) {
+                let (x, fallback) = (pair[0], pair[1]);
+                let mask = Mask::from_array(fallback.to_array().map(|v| v.bits() & 2 != 0));
+
+                check_variants!(Exponent, exp, x, mask, fallback);
+                check_variants!(Exponent, exp2, x, mask, fallback);
+                check_variants!(Exponent, exp_m1, x, mask, fallback);
+                check_variants!(Trigonometry, sin, x, mask, fallback);
+                check_variants!(Trigonometry, cos, x, mask, fallback);
+                check_variants!(Trigonometry, tan, x, mask, fallback);
+                check_variants!(Trigonometry, asin, x, mask, fallback);
+                check_variants!(Trigonometry, acos, x, mask, fallback);
+                check_variants!(Trigonometry, atan, x, mask, fallback);
+
+                let (sin, cos) = Trigonometry::sin_cos(x);
+                let (sin_masked, cos_masked) =
+                    Trigonometry::sin_cos_masked(x, mask, (fallback, -fallback));
+                let atan2 = Trigonometry::atan2(x, fallback);
+                let atan2_masked = Trigonometry::atan2_masked(x, fallback, mask, -x);
+                for i in 0..N {
+                    let (sin, cos, atan2) = if mask.test(i) {
+                        (sin[i], cos[i], atan2[i])
+                    } else {
+                        (fallback[i], -fallback[i], -x[i])
+                    };
+                    assert_eq!(
+                        sin_masked[i].bits(),
+                        sin.bits(),
+                        "sin_cos_masked({:?}).0 in lane {i}",
+                        x[i]
+                    );
+                    assert_eq!(
+                        cos_masked[i].bits(),
+                        cos.bits(),
+                        "sin_cos_masked({:?}).1 in lane {i}",
+                        x[i]
+                    );
+                    assert_eq!(
+                        atan2_masked[i].bits(),
+                        atan2.bits(),
+                        "atan2_masked({:?}, {:?}) in lane {i}",
+                        x[i],
+                        fallback[i]
+                    );
+                }
+
+                // Estrin's scheme mixes lanes only through splats
+                let poly = polynomial_simd!(x; 1.0, -0.5, 0.25, 3.0, -7.0, 0.125);
+                check_lanes("polynomial_simd", &[x], poly, |args| {
+                    let x = Simd::<F, 1>::splat(args[0]);
+                    polynomial_simd!(x; 1.0, -0.5, 0.25, 3.0, -7.0, 0.125)[0]
+                });
+            }
+        });
+    });
+});
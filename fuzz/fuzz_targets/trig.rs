@@ -0,0 +1,96 @@
+#![no_main]
+#![feature(portable_simd)]
+
+use libfuzzer_sys::fuzz_target;
+use simd_addons::math::Trigonometry;
+use simd_addons_fuzz::{check_unary, vectors, with_float, with_lanes, Tolerance};
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, data)) = data.split_first() else {
+        return;
+    };
+    with_float!(selector, |F| {
+        // results of `sin`, `cos` and `tan` are unspecified for NaNs and the inputs which are too
+        // large for argument reduction, errors are checked on the ranges of `tests/math.rs`
+        let specified = |x: F| !x.is_nan() && !Trigonometry::sin_checked(x).1.invalid;
+        let trig_limit: F = if F::MAX_EXP == 128 { 1e3 } else { 1e5 };
+        let reduced = |x: F| x.abs() <= trig_limit;
+
+        with_lanes!(selector, |N| {
+            for x in vectors::<F, N>(selector, data) {
+                check_unary(
+                    "sin",
+                    x,
+                    Trigonometry::sin,
+                    Trigonometry::sin,
+                    |x| specified(x).then(|| x.sin()),
+                    reduced,
+                    Tolerance::DEFAULT,
+                );
+                check_unary(
+                    "cos",
+                    x,
+                    Trigonometry::cos,
+                    Trigonometry::cos,
+                    |x| specified(x).then(|| x.cos()),
+                    reduced,
+                    Tolerance::DEFAULT,
+                );
+                check_unary(
+                    "tan",
+                    x,
+                    Trigonometry::tan,
+                    Trigonometry::tan,
+                    |x| specified(x).then(|| x.tan()),
+                    reduced,
+                    Tolerance::DEFAULT,
+                );
+                check_unary(
+                    "sin_cos.0",
+                    x,
+                    |x| x.sin_cos().0,
+                    |x| Trigonometry::sin_cos(x).0,
+                    |x| Some(Trigonometry::sin(x)),
+                    |_| true,
+                    Tolerance::DEFAULT,
+                );
+                check_unary(
+                    "sin_cos.1",
+                    x,
+                    |x| x.sin_cos().1,
+                    |x| Trigonometry::sin_cos(x).1,
+                    |x| Some(Trigonometry::cos(x)),
+                    |_| true,
+                    Tolerance::DEFAULT,
+                );
+                check_unary(
+                    "asin",
+                    x,
+                    Trigonometry::asin,
+                    Trigonometry::asin,
+                    |x| Some(x.asin()),
+                    |_| true,
+                    Tolerance::DEFAULT,
+                );
+                check_unary(
+                    "acos",
+                    x,
+                    Trigonometry::acos,
+                    Trigonometry::acos,
+                    |x| Some(x.acos()),
+                    |_| true,
+                    Tolerance::DEFAULT,
+                );
+                check_unary(
+                    "atan",
+                    x,
+                    Trigonometry::atan,
+                    Trigonometry::atan,
+                    |x| Some(x.atan()),
+                    |_| true,
+                    Tolerance::DEFAULT,
+                );
+            }
+        });
+    });
+});
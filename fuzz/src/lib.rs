@@ -0,0 +1,281 @@
+//! Decoding of fuzzer inputs and checks shared by the fuzz targets.
+//!
+//! The first byte of an input selects the float type, the lane count and the encoding of the lanes,
+//! the rest is split into the lanes. A lane is either an arbitrary bit pattern or, to make mixes of
+//! special values in adjacent lanes likely, a single byte selecting one of a few special values.
+#![feature(portable_simd)]
+
+use std::{
+    fmt::Debug,
+    simd::{LaneCount, Simd, SimdElement, SupportedLaneCount},
+};
+
+/// Float types the functions are implemented for.
+pub trait FuzzFloat: SimdElement + Default + Debug {
+    const EPSILON: f64;
+
+    fn from_le_slice(bytes: &[u8]) -> Self;
+
+    /// Exact conversion.
+    fn to_f64(self) -> f64;
+
+    fn bits(self) -> u64;
+
+    /// One of the special values: zeros, infinities, NaN, extreme and unit values.
+    fn special(index: u8) -> Self;
+
+    /// Number of representable values between `self` and `other`.
+    fn ulps(self, other: Self) -> u64;
+}
+
+macro_rules! impl_fuzz_float {
+    ($ftype: ident, $itype: ident) => {
+        impl FuzzFloat for $ftype {
+            const EPSILON: f64 = $ftype::EPSILON as f64;
+
+            fn from_le_slice(bytes: &[u8]) -> Self {
+                $ftype::from_le_bytes(bytes.try_into().unwrap())
+            }
+
+            #[allow(clippy::unnecessary_cast)]
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn bits(self) -> u64 {
+                self.to_bits() as u64
+            }
+
+            fn special(index: u8) -> Self {
+                const SPECIALS: [$ftype; 11] = [
+                    0.0,
+                    -0.0,
+                    $ftype::INFINITY,
+                    $ftype::NEG_INFINITY,
+                    $ftype::NAN,
+                    1.0,
+                    -1.0,
+                    $ftype::MIN_POSITIVE,
+                    -$ftype::MIN_POSITIVE,
+                    $ftype::MAX,
+                    $ftype::MIN,
+                ];
+                SPECIALS[index as usize % SPECIALS.len()]
+            }
+
+            fn ulps(self, other: Self) -> u64 {
+                // maps floats to integers of the same order
+                let key = |x: $ftype| {
+                    let bits = x.to_bits() as $itype;
+                    if bits < 0 {
+                        $itype::MIN - bits
+                    } else {
+                        bits
+                    }
+                };
+                key(self).abs_diff(key(other)) as u64
+            }
+        }
+    };
+}
+
+impl_fuzz_float!(f32, i32);
+impl_fuzz_float!(f64, i64);
+
+/// Runs `$body` with `$ftype` bound to the float type selected by the lowest bit of `$selector`.
+#[macro_export]
+macro_rules! with_float {
+    ($selector: expr, |$ftype: ident| $body: block) => {
+        if $selector & 1 == 0 {
+            type $ftype = f32;
+            $body
+        } else {
+            type $ftype = f64;
+            $body
+        }
+    };
+}
+
+/// Runs `$body` with the constant `$lanes` bound to the lane count selected by the bits of
+/// `$selector` above the lowest one.
+#[macro_export]
+macro_rules! with_lanes {
+    ($selector: expr, |$lanes: ident| $body: block) => {
+        $crate::with_lanes!(@counts ($selector >> 1) % 7, $lanes, $body, 0 => 1, 1 => 2, 2 => 4, 3 => 8, 4 => 16, 5 => 32, 6 => 64)
+    };
+
+    (@counts $index: expr, $lanes: ident, $body: block, $( $i: literal => $count: literal ),+) => {
+        match $index {
+            $(
+            $i => {
+                const $lanes: usize = $count;
+                $body
+            }
+            )+
+            _ => unreachable!(),
+        }
+    };
+}
+
+/// Splits `data` into vectors of `N` lanes of `T` encoded as selected by the highest bit of
+/// `selector`, ignoring the incomplete tail.
+pub fn vectors<T: FuzzFloat, const N: usize>(selector: u8, data: &[u8]) -> Vec<Simd<T, N>>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let lane_size = if selector & 0x80 == 0 {
+        std::mem::size_of::<T>()
+    } else {
+        1
+    };
+    data.chunks_exact(N * lane_size)
+        .map(|chunk| {
+            let mut lanes = [T::default(); N];
+            for (lane, bytes) in lanes.iter_mut().zip(chunk.chunks_exact(lane_size)) {
+                *lane = match bytes {
+                    [index] => T::special(*index),
+                    _ => T::from_le_slice(bytes),
+                };
+            }
+            Simd::from_array(lanes)
+        })
+        .collect()
+}
+
+/// Accuracy required from a function where it is compared with the reference.
+#[derive(Clone, Copy, Debug)]
+pub struct Tolerance {
+    /// Maximum error in ULPs.
+    pub max_ulps: u64,
+    /// Errors below `max_abs_epsilons` machine epsilons are accepted regardless of ULPs, since
+    /// relative errors near zeros of functions are large. The sign of such results isn't checked.
+    pub max_abs_epsilons: u32,
+    /// Whether zero results must have the sign of the reference.
+    pub signed_zeros: bool,
+}
+
+impl Tolerance {
+    /// Tolerance of the tests in `tests/math.rs`.
+    pub const DEFAULT: Tolerance = Tolerance {
+        max_ulps: 5,
+        max_abs_epsilons: 1,
+        signed_zeros: true,
+    };
+}
+
+/// Checks `result` of `name` at `args` against the `reference`: NaN-ness must be the same, zeros
+/// and infinities must be exact and other values must have the same sign and, if `accurate`, be
+/// close enough.
+pub fn check_reference<T: FuzzFloat>(
+    name: &str,
+    args: &[T],
+    result: T,
+    reference: T,
+    accurate: bool,
+    tolerance: Tolerance,
+) {
+    let message = || format!("{name}{args:?} = {result:?}, expected {reference:?}");
+    let (actual, expected) = (result.to_f64(), reference.to_f64());
+    assert_eq!(actual.is_nan(), expected.is_nan(), "{}", message());
+    if expected.is_nan() {
+        return;
+    }
+    if (expected.is_infinite() && accurate) || (expected == 0.0 && tolerance.signed_zeros) {
+        assert_eq!(result.bits(), reference.bits(), "{}", message());
+        return;
+    }
+    if expected == 0.0 {
+        assert_eq!(actual, 0.0, "{}", message());
+        return;
+    }
+    if !accurate {
+        assert_eq!(
+            actual.is_sign_negative(),
+            expected.is_sign_negative(),
+            "{}",
+            message()
+        );
+        return;
+    }
+    if (actual - expected).abs() <= tolerance.max_abs_epsilons as f64 * T::EPSILON {
+        return;
+    }
+    assert_eq!(
+        actual.is_sign_negative(),
+        expected.is_sign_negative(),
+        "{}",
+        message()
+    );
+    let ulps = result.ulps(reference);
+    assert!(ulps <= tolerance.max_ulps, "{}: {ulps} ULPs", message());
+}
+
+/// Checks that every lane of `result` is bit-identical to `single_lane` evaluated on the lane.
+pub fn check_lanes<T: FuzzFloat, const N: usize>(
+    name: &str,
+    args: &[Simd<T, N>],
+    result: Simd<T, N>,
+    single_lane: impl Fn(&[T]) -> T,
+) where
+    LaneCount<N>: SupportedLaneCount,
+{
+    for i in 0..N {
+        let lane_args: Vec<T> = args.iter().map(|arg| arg[i]).collect();
+        let expected = single_lane(&lane_args);
+        assert_eq!(
+            result[i].bits(),
+            expected.bits(),
+            "{name}{lane_args:?} = {:?} in lane {i} of {N}, {expected:?} in a single lane",
+            result[i]
+        );
+    }
+}
+
+/// Checks that lanes of `ours` at `x` are independent of each other, and every lane against
+/// `reference` where it is specified. Errors are checked only where the inputs are `accurate`.
+pub fn check_unary<T: FuzzFloat, const N: usize>(
+    name: &str,
+    x: Simd<T, N>,
+    ours: impl Fn(Simd<T, N>) -> Simd<T, N>,
+    single_lane: impl Fn(T) -> T,
+    reference: impl Fn(T) -> Option<T>,
+    accurate: impl Fn(T) -> bool,
+    tolerance: Tolerance,
+) where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let result = ours(x);
+    check_lanes(name, &[x], result, |args| single_lane(args[0]));
+    for i in 0..N {
+        if let Some(expected) = reference(x[i]) {
+            check_reference(
+                name,
+                &[x[i]],
+                result[i],
+                expected,
+                accurate(x[i]),
+                tolerance,
+            );
+        }
+    }
+}
+
+/// Same as [`check_unary`], but for a function of two arguments.
+pub fn check_binary<T: FuzzFloat, const N: usize>(
+    name: &str,
+    (x, y): (Simd<T, N>, Simd<T, N>),
+    ours: impl Fn(Simd<T, N>, Simd<T, N>) -> Simd<T, N>,
+    single_lane: impl Fn(T, T) -> T,
+    reference: impl Fn(T, T) -> Option<T>,
+    tolerance: Tolerance,
+) where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let result = ours(x, y);
+    check_lanes(name, &[x, y], result, |args| single_lane(args[0], args[1]));
+    for i in 0..N {
+        if let Some(expected) = reference(x[i], y[i]) {
+            check_reference(name, &[x[i], y[i]], result[i], expected, true, tolerance);
+        }
+    }
+}
@@ -0,0 +1,545 @@
+//! Stream compaction: packing the selected lanes of vectors together (compress) and spreading
+//! contiguous elements over the selected lanes (expand), and filtering and partitioning of slices
+//! built upon them.
+//!
+//! Vectors of 16 or 32 bytes with up to 8 lanes are permuted by byte shuffles with indices from
+//! lookup tables, other vectors lane by lane. The shuffles are fast only if the target has them,
+//! e.g. x86-64 since SSSE3, so enable the target features of the hardware, e.g. with
+//! `-C target-cpu=native`.
+//!
+//! # Examples
+//! ```
+//! # #![feature(portable_simd)]
+//! # use std::simd::prelude::*;
+//! use simd_addons::algo::compress;
+//!
+//! let lanes = f32x4::from_array([1.0, -2.0, 3.0, -4.0]);
+//! let mut out = [0.0; 4];
+//! let count = compress::compress_store(lanes, lanes.simd_lt(f32x4::splat(0.0)), &mut out);
+//! assert_eq!(out[..count], [-2.0, -4.0]);
+//!
+//! let mask = mask32x4::from_array([false, true, true, false]);
+//! let lanes = compress::expand_load(&[5.0, 6.0], mask, f32x4::splat(0.0));
+//! assert_eq!(lanes, f32x4::from_array([0.0, 5.0, 6.0, 0.0]));
+//!
+//! let data: Vec<i64> = (0..20).collect();
+//! let mut even = [0; 20];
+//! let count = compress::filter(&data, &mut even, |v: i64x8| (v % i64x8::splat(2)).simd_eq(i64x8::splat(0)));
+//! assert_eq!(even[..count], [0, 2, 4, 6, 8, 10, 12, 14, 16, 18]);
+//! ```
+
+use crate::{
+    algo::load_padded,
+    simd::{LaneCount, Mask, Simd, SimdElement, SupportedLaneCount},
+};
+
+/// Byte indices of shuffles of vectors of `BYTES / SIZE` lanes of `SIZE` bytes, for every
+/// selection of lanes given by the bits of the index of the entry.
+///
+/// For compression the selected lanes are moved to the beginning in order, followed by the other
+/// ones. For expansion the `k`-th selected lane takes the `k`-th lane, the other ones keep their
+/// lanes.
+#[cfg(not(simd_shim))]
+const fn shuffle_table<const SIZE: usize, const BYTES: usize, const ENTRIES: usize>(
+    expand: bool,
+) -> [[u8; BYTES]; ENTRIES] {
+    let lanes = BYTES / SIZE;
+    let mut table = [[0; BYTES]; ENTRIES];
+    let mut bits = 0;
+    while bits < ENTRIES {
+        // (destination, source) of the selected lanes first, then of the other ones
+        let mut count = 0;
+        let mut pass = 0;
+        while pass < 2 {
+            let mut lane = 0;
+            while lane < lanes {
+                if ((bits >> lane) & 1 == 1) == (pass == 0) {
+                    let (destination, source) = match (expand, pass) {
+                        (false, _) => (count, lane),
+                        (true, 0) => (lane, count),
+                        (true, _) => (lane, lane),
+                    };
+                    let mut byte = 0;
+                    while byte < SIZE {
+                        table[bits][destination * SIZE + byte] = (source * SIZE + byte) as u8;
+                        byte += 1;
+                    }
+                    count += 1;
+                }
+                lane += 1;
+            }
+            pass += 1;
+        }
+        bits += 1;
+    }
+    table
+}
+
+/// Shuffle tables for 4 and 8 lanes of 32 bits, and 2 and 4 lanes of 64 bits.
+#[cfg(not(simd_shim))]
+struct ShuffleTables {
+    lanes_32x4: [[u8; 16]; 16],
+    lanes_32x8: [[u8; 32]; 256],
+    lanes_64x2: [[u8; 16]; 4],
+    lanes_64x4: [[u8; 32]; 16],
+}
+
+#[cfg(not(simd_shim))]
+impl ShuffleTables {
+    const fn new(expand: bool) -> Self {
+        Self {
+            lanes_32x4: shuffle_table::<4, 16, 16>(expand),
+            lanes_32x8: shuffle_table::<4, 32, 256>(expand),
+            lanes_64x2: shuffle_table::<8, 16, 4>(expand),
+            lanes_64x4: shuffle_table::<8, 32, 16>(expand),
+        }
+    }
+}
+
+#[cfg(not(simd_shim))]
+static COMPRESS_TABLES: ShuffleTables = ShuffleTables::new(false);
+
+#[cfg(not(simd_shim))]
+static EXPAND_TABLES: ShuffleTables = ShuffleTables::new(true);
+
+/// Whether shuffles of 16 and 32 bytes are single instructions. On x86-64 without them,
+/// `swizzle_dyn` is emulated lane by lane, and the scalar loops are faster.
+const FAST_SHUFFLE_16: bool =
+    !cfg!(simd_shim) && (!cfg!(target_arch = "x86_64") || cfg!(target_feature = "ssse3"));
+const FAST_SHUFFLE_32: bool =
+    !cfg!(simd_shim) && (!cfg!(target_arch = "x86_64") || cfg!(target_feature = "avx2"));
+
+/// Shuffles the bytes of `lanes` by `indices`, which must move whole lanes.
+#[cfg(not(simd_shim))]
+#[inline(always)]
+fn shuffle_bytes<T, const N: usize, const BYTES: usize>(
+    lanes: Simd<T, N>,
+    indices: &[u8; BYTES],
+) -> Simd<T, N>
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+    LaneCount<BYTES>: SupportedLaneCount,
+{
+    assert_eq!(core::mem::size_of::<Simd<T, N>>(), BYTES);
+    // SAFETY: the sizes are equal, and whole lanes are moved, so every lane of the result is a
+    // valid element
+    unsafe {
+        let bytes = core::mem::transmute_copy::<Simd<T, N>, Simd<u8, BYTES>>(&lanes);
+        let shuffled = swizzle_bytes(bytes, Simd::from_array(*indices));
+        core::mem::transmute_copy::<Simd<u8, BYTES>, Simd<T, N>>(&shuffled)
+    }
+}
+
+/// Shuffles `bytes` by `indices`, which must move whole lanes of 4 or 8 bytes.
+///
+/// `swizzle_dyn` picks the shuffle instructions by the target features the standard library is
+/// built with, i.e. none on x86-64, so they are used directly.
+#[cfg(not(simd_shim))]
+#[inline(always)]
+fn swizzle_bytes<const BYTES: usize>(
+    bytes: Simd<u8, BYTES>,
+    indices: Simd<u8, BYTES>,
+) -> Simd<u8, BYTES>
+where
+    LaneCount<BYTES>: SupportedLaneCount,
+{
+    #[cfg(all(target_arch = "x86_64", target_feature = "ssse3"))]
+    if BYTES == 16 {
+        use core::arch::x86_64::{__m128i, _mm_shuffle_epi8};
+        // SAFETY: the target has the instruction, and the vectors are 16 bytes
+        return unsafe {
+            let shuffled = _mm_shuffle_epi8(
+                core::mem::transmute_copy::<_, __m128i>(&bytes),
+                core::mem::transmute_copy::<_, __m128i>(&indices),
+            );
+            core::mem::transmute_copy(&shuffled)
+        };
+    }
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+    if BYTES == 32 {
+        use core::arch::x86_64::{__m256i, _mm256_permutevar8x32_epi32};
+        // whole lanes of 4 bytes are moved, so the lane of 4 bytes is given by its first byte
+        // SAFETY: the target has the instruction, and the vectors are 32 bytes
+        return unsafe {
+            let first = core::mem::transmute_copy::<_, Simd<u32, 8>>(&indices)
+                & Simd::<u32, 8>::splat(0xff);
+            let shuffled = _mm256_permutevar8x32_epi32(
+                core::mem::transmute_copy::<_, __m256i>(&bytes),
+                core::mem::transmute_copy::<_, __m256i>(&(first >> Simd::<u32, 8>::splat(2))),
+            );
+            core::mem::transmute_copy(&shuffled)
+        };
+    }
+    bytes.swizzle_dyn(indices)
+}
+
+/// Compresses vectors of 64 bytes by the AVX-512 instructions.
+#[cfg(all(not(simd_shim), target_arch = "x86_64", target_feature = "avx512f"))]
+#[inline(always)]
+fn compress_wide<T, const N: usize>(lanes: Simd<T, N>, bits: u64) -> Option<Simd<T, N>>
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    use core::arch::x86_64::{__m512i, _mm512_maskz_compress_epi32, _mm512_maskz_compress_epi64};
+    // SAFETY: the target has the instructions, and the vectors are 64 bytes of whole lanes
+    unsafe {
+        let packed = match (core::mem::size_of::<T>(), N) {
+            (4, 16) => _mm512_maskz_compress_epi32(bits as u16, core::mem::transmute_copy(&lanes)),
+            (8, 8) => _mm512_maskz_compress_epi64(bits as u8, core::mem::transmute_copy(&lanes)),
+            _ => return None,
+        };
+        Some(core::mem::transmute_copy::<__m512i, Simd<T, N>>(&packed))
+    }
+}
+
+/// Compresses vectors of 64 bytes as two halves by the tables, the packed high half is written
+/// after the selected lanes of the low one.
+#[cfg(all(
+    not(simd_shim),
+    not(all(target_arch = "x86_64", target_feature = "avx512f"))
+))]
+#[inline(always)]
+fn compress_wide<T, const N: usize>(lanes: Simd<T, N>, bits: u64) -> Option<Simd<T, N>>
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    if !FAST_SHUFFLE_32 {
+        return None;
+    }
+    let (table, half) = match (core::mem::size_of::<T>(), N) {
+        (4, 16) => (&COMPRESS_TABLES.lanes_32x8[..], 8),
+        (8, 8) => (&COMPRESS_TABLES.lanes_64x4[..], 4),
+        _ => return None,
+    };
+    let low_bits = bits & ((1 << half) - 1);
+    // SAFETY: both are 64 bytes
+    let [low, high] = unsafe { core::mem::transmute_copy::<_, [Simd<u8, 32>; 2]>(&lanes) };
+    let low = swizzle_bytes::<32>(low, Simd::<u8, 32>::from_array(table[low_bits as usize]));
+    let high = swizzle_bytes::<32>(
+        high,
+        Simd::<u8, 32>::from_array(table[(bits >> half) as usize]),
+    );
+    let low_bytes = low_bits.count_ones() as usize * core::mem::size_of::<T>();
+    let mut bytes = [0; 64];
+    bytes[..32].copy_from_slice(low.as_array());
+    bytes[low_bytes..low_bytes + 32].copy_from_slice(high.as_array());
+    // SAFETY: the bytes are whole lanes of `lanes`, followed by zeros if some of the low half
+    // are left out, which are valid elements as well
+    Some(unsafe { core::mem::transmute_copy::<[u8; 64], Simd<T, N>>(&bytes) })
+}
+
+/// Permutes `lanes` by the table for the selection `bits`, if there is one for such vectors.
+#[cfg(not(simd_shim))]
+#[inline(always)]
+fn shuffle_by_table<T, const N: usize>(
+    lanes: Simd<T, N>,
+    bits: u64,
+    tables: &ShuffleTables,
+) -> Option<Simd<T, N>>
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let bits = bits as usize;
+    match (core::mem::size_of::<T>(), N) {
+        (4, 4) if FAST_SHUFFLE_16 => {
+            Some(shuffle_bytes::<T, N, 16>(lanes, &tables.lanes_32x4[bits]))
+        }
+        (4, 8) if FAST_SHUFFLE_32 => {
+            Some(shuffle_bytes::<T, N, 32>(lanes, &tables.lanes_32x8[bits]))
+        }
+        (8, 2) if FAST_SHUFFLE_16 => {
+            Some(shuffle_bytes::<T, N, 16>(lanes, &tables.lanes_64x2[bits]))
+        }
+        (8, 4) if FAST_SHUFFLE_32 => {
+            Some(shuffle_bytes::<T, N, 32>(lanes, &tables.lanes_64x4[bits]))
+        }
+        _ => None,
+    }
+}
+
+/// Compresses `lanes` by the selection `bits` with vector shuffles, if the target has fast ones for
+/// such vectors.
+#[cfg(not(simd_shim))]
+#[inline(always)]
+fn compress_by_shuffle<T, const N: usize>(lanes: Simd<T, N>, bits: u64) -> Option<Simd<T, N>>
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    shuffle_by_table(lanes, bits, &COMPRESS_TABLES).or_else(|| compress_wide(lanes, bits))
+}
+
+#[cfg(simd_shim)]
+#[inline(always)]
+fn compress_by_shuffle<T, const N: usize>(_: Simd<T, N>, _: u64) -> Option<Simd<T, N>>
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    None
+}
+
+/// Whether [`compress`] is done by vector shuffles for such vectors, rather than lane by lane.
+#[inline(always)]
+pub(crate) fn is_vectorized<T, const N: usize>() -> bool {
+    match (core::mem::size_of::<T>(), N) {
+        (4, 4) | (8, 2) => FAST_SHUFFLE_16,
+        (4, 8) | (8, 4) | (4, 16) | (8, 8) => FAST_SHUFFLE_32,
+        _ => false,
+    }
+}
+
+/// Moves the lanes selected by `mask` to the beginning of the vector, keeping their order, and
+/// returns the vector and the number of the selected lanes. The other lanes are unspecified.
+///
+/// # Examples
+/// ```
+/// # #![feature(portable_simd)]
+/// # use std::simd::prelude::*;
+/// use simd_addons::algo::compress;
+///
+/// let mask = mask64x4::from_array([false, true, false, true]);
+/// let (packed, count) = compress::compress(u64x4::from_array([1, 2, 3, 4]), mask);
+/// assert_eq!(packed[..count], [2, 4]);
+/// ```
+#[inline]
+pub fn compress<T, const N: usize>(lanes: Simd<T, N>, mask: Mask<T::Mask, N>) -> (Simd<T, N>, usize)
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let bits = mask.to_bitmask();
+    let count = bits.count_ones() as usize;
+    if let Some(packed) = compress_by_shuffle(lanes, bits) {
+        return (packed, count);
+    }
+    // every lane is written after the selected ones, and the position moves past it if it is
+    // selected, which is branchless
+    let array = lanes.to_array();
+    let mut packed = array;
+    let mut position = 0;
+    for (i, &lane) in array.iter().enumerate() {
+        packed[position] = lane;
+        position += ((bits >> i) & 1) as usize;
+    }
+    (Simd::from_array(packed), count)
+}
+
+/// Inverse of [`compress`]: the `k`-th lane selected by `mask` takes the `k`-th lane of `lanes`,
+/// the other lanes are taken from `fallback`.
+#[inline]
+pub fn expand<T, const N: usize>(
+    lanes: Simd<T, N>,
+    mask: Mask<T::Mask, N>,
+    fallback: Simd<T, N>,
+) -> Simd<T, N>
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let bits = mask.to_bitmask();
+    #[cfg(not(simd_shim))]
+    if let Some(spread) = shuffle_by_table(lanes, bits, &EXPAND_TABLES) {
+        return mask.select(spread, fallback);
+    }
+    // the `k`-th selected lane takes lane `k`, the other lanes are replaced by the fallback
+    let array = lanes.to_array();
+    let mut position = 0;
+    let spread = Simd::from_array(core::array::from_fn(|i| {
+        let lane = array[position];
+        position = (position + ((bits >> i) & 1) as usize).min(N - 1);
+        lane
+    }));
+    mask.select(spread, fallback)
+}
+
+/// Writes the lanes selected by `mask` to the beginning of `out`, keeping their order, and
+/// returns their number. The rest of `out` is left intact.
+///
+/// # Panics
+/// Panics if `out` is shorter than the number of the selected lanes.
+#[inline]
+pub fn compress_store<T, const N: usize>(
+    lanes: Simd<T, N>,
+    mask: Mask<T::Mask, N>,
+    out: &mut [T],
+) -> usize
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let bits = mask.to_bitmask();
+    let count = bits.count_ones() as usize;
+    let Some(packed) = compress_by_shuffle(lanes, bits) else {
+        assert!(
+            out.len() >= count,
+            "output is shorter than the number of selected lanes"
+        );
+        let array = lanes.to_array();
+        let Some(&kept) = out.get(count) else {
+            // `out` fits the selected lanes exactly
+            let selected = array
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| (bits >> i) & 1 != 0);
+            for (slot, (_, &lane)) in out.iter_mut().zip(selected) {
+                *slot = lane;
+            }
+            return count;
+        };
+        // as in `compress`, but written to `out` directly, which only overwrites the element
+        // after the selected ones, so it's restored
+        let mut position = 0;
+        for (i, &lane) in array.iter().enumerate() {
+            out[position] = lane;
+            position += ((bits >> i) & 1) as usize;
+        }
+        out[count] = kept;
+        return count;
+    };
+    if let Some(out) = out.get_mut(..N) {
+        // writing whole vectors is faster, the lanes past the selected ones are restored
+        let kept = Simd::from_slice(out);
+        out.copy_from_slice(within(0, count).select(packed, kept).as_array());
+    } else {
+        assert!(
+            out.len() >= count,
+            "output is shorter than the number of selected lanes"
+        );
+        out[..count].copy_from_slice(&packed.as_array()[..count]);
+    }
+    count
+}
+
+/// Loads the first elements of `slice` to the lanes selected by `mask`, in order, and takes the
+/// other lanes from `fallback`. Reads as many elements as there are selected lanes.
+///
+/// # Panics
+/// Panics if `slice` is shorter than the number of the selected lanes.
+#[inline]
+pub fn expand_load<T, const N: usize>(
+    slice: &[T],
+    mask: Mask<T::Mask, N>,
+    fallback: Simd<T, N>,
+) -> Simd<T, N>
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let count = mask.to_bitmask().count_ones() as usize;
+    assert!(
+        slice.len() >= count,
+        "input is shorter than the number of selected lanes"
+    );
+    expand(load_padded(slice, 0, fallback[0]), mask, fallback)
+}
+
+/// Mask of the lanes of a vector starting at `start` which are within `len` elements.
+#[inline(always)]
+fn within<M, const N: usize>(start: usize, len: usize) -> Mask<M, N>
+where
+    M: crate::simd::MaskElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    Mask::from_array(core::array::from_fn(|i| start + i < len))
+}
+
+/// Copies the elements of `input` which satisfy `predicate` to the beginning of `out`, keeping
+/// their order, and returns their number. The predicate is evaluated on vectors of `N` elements,
+/// the lanes of the last one past the end of `input` are ignored. The elements of `out` past the
+/// copied ones are unspecified.
+///
+/// # Panics
+/// Panics if `out` is shorter than the number of the copied elements.
+#[inline]
+pub fn filter<T, const N: usize>(
+    input: &[T],
+    out: &mut [T],
+    predicate: impl Fn(Simd<T, N>) -> Mask<T::Mask, N>,
+) -> usize
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let mut count = 0;
+    for start in (0..input.len()).step_by(N) {
+        let lanes = load_padded(input, start, input[start]);
+        let mask = predicate(lanes) & within(start, input.len());
+        count += store_selected(lanes, mask, &mut out[count..]);
+    }
+    count
+}
+
+/// Copies the elements of `input` which satisfy `predicate` to the beginning of `accepted` and
+/// the others to the beginning of `rejected`, keeping their order, and returns the number of the
+/// accepted ones. The predicate is evaluated like by [`filter`]. The elements of the outputs past
+/// the copied ones are unspecified.
+///
+/// # Panics
+/// Panics if either output is shorter than the number of the elements copied to it.
+///
+/// # Examples
+/// ```
+/// # #![feature(portable_simd)]
+/// # use std::simd::prelude::*;
+/// use simd_addons::algo::compress;
+///
+/// let data = [3, -1, 4, -1, -5, 9];
+/// let (mut positive, mut negative) = ([0; 6], [0; 6]);
+/// let count = compress::partition(&data, &mut positive, &mut negative, |v: i32x4| {
+///     v.simd_gt(i32x4::splat(0))
+/// });
+/// assert_eq!(positive[..count], [3, 4, 9]);
+/// assert_eq!(negative[..data.len() - count], [-1, -1, -5]);
+/// ```
+#[inline]
+pub fn partition<T, const N: usize>(
+    input: &[T],
+    accepted: &mut [T],
+    rejected: &mut [T],
+    predicate: impl Fn(Simd<T, N>) -> Mask<T::Mask, N>,
+) -> usize
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let mut accepted_count = 0;
+    let mut rejected_count = 0;
+    for start in (0..input.len()).step_by(N) {
+        let lanes = load_padded(input, start, input[start]);
+        let valid = within(start, input.len());
+        let mask = predicate(lanes);
+        accepted_count += store_selected(lanes, mask & valid, &mut accepted[accepted_count..]);
+        rejected_count += store_selected(lanes, !mask & valid, &mut rejected[rejected_count..]);
+    }
+    accepted_count
+}
+
+/// Like [`compress_store`], but overwrites the beginning of `out` with a whole vector if it is
+/// long enough.
+#[inline(always)]
+fn store_selected<T, const N: usize>(
+    lanes: Simd<T, N>,
+    mask: Mask<T::Mask, N>,
+    out: &mut [T],
+) -> usize
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let (packed, count) = compress(lanes, mask);
+    if let Some(out) = out.get_mut(..N) {
+        out.copy_from_slice(packed.as_array());
+    } else {
+        assert!(
+            out.len() >= count,
+            "output is shorter than the number of selected elements"
+        );
+        out[..count].copy_from_slice(&packed.as_array()[..count]);
+    }
+    count
+}
@@ -0,0 +1,42 @@
+//! Algorithms on slices and vectors built upon SIMD.
+
+pub mod compress;
+pub mod reduce;
+pub mod scan;
+pub mod search;
+pub mod sort;
+pub mod utf8;
+
+use crate::simd::{LaneCount, Simd, SimdElement, SupportedLaneCount};
+
+/// Copies `slice[start..start + N]` to a vector, filling the lanes past the end with `padding`.
+#[inline(always)]
+pub(crate) fn load_padded<T: SimdElement, const N: usize>(
+    slice: &[T],
+    start: usize,
+    padding: T,
+) -> Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    if let Some(lanes) = slice.get(start..start + N) {
+        return Simd::from_slice(lanes);
+    }
+    let mut lanes = [padding; N];
+    let tail = slice.get(start..).unwrap_or_default();
+    lanes[..tail.len()].copy_from_slice(tail);
+    Simd::from_array(lanes)
+}
+
+/// Writes the lanes to `slice[start..start + N]`, ignoring the ones past the end.
+#[inline(always)]
+pub(crate) fn store<T: SimdElement, const N: usize>(
+    slice: &mut [T],
+    start: usize,
+    lanes: Simd<T, N>,
+) where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let count = N.min(slice.len() - start);
+    slice[start..start + count].copy_from_slice(&lanes.as_array()[..count]);
+}
@@ -0,0 +1,435 @@
+//! Reductions of slices of floats: sums, dot products, Euclidean norms, mean and variance, and
+//! minimums and maximums with their indices.
+//!
+//! Slices are processed by several vectors at once with an independent accumulator for each of
+//! them, so consecutive additions don't wait for each other. Thus the order of the additions
+//! differs from the sequential one, and so do the rounding errors. The accuracy of the sums is
+//! chosen by [`Summation`].
+//!
+//! # Examples
+//! ```
+//! use simd_addons::algo::reduce::{self, Summation};
+//!
+//! let data = [1e8f32, 1.0, -1e8, 1.0];
+//! assert_eq!(reduce::sum(&data, Summation::Compensated), 2.0);
+//! assert_eq!(reduce::dot(&data, &[1.0, 2.0, 1.0, 2.0], Summation::Compensated), 4.0);
+//! assert_eq!(reduce::norm2(&[3e30f32, 4e30], Summation::default()), 5e30);
+//! assert_eq!(reduce::mean_variance(&[1.0, 2.0, 3.0, 4.0]), (2.5, 1.25));
+//! assert_eq!(reduce::max_index(&[1.0, f32::NAN, 3.0, 3.0]), Some((2, 3.0)));
+//! ```
+
+use core::ops::{Add, Range};
+
+use crate::{
+    algo::load_padded,
+    math::{ops::CoreFloat, util::ErrorFreeTransform},
+    simd::{cmp::SimdPartialOrd, num::SimdFloat, LaneCount, Simd, SimdElement, SupportedLaneCount},
+};
+
+/// Number of independent vector accumulators.
+const ACCUMULATORS: usize = 4;
+
+/// Number of vectors summed naively by the leaves of pairwise summation.
+const PAIRWISE_BLOCK: usize = 32;
+
+/// Algorithm of summation used by [`sum`], [`dot`] and [`norm2`].
+///
+/// The error bounds below are relative to the sum of absolute values of the terms, `n` is the
+/// number of terms and `ε` is the machine epsilon.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Summation {
+    /// Accumulates the terms one after another. The error is bounded by about `n·ε`.
+    Naive,
+    /// Sums blocks of a few vectors naively and adds the sums of the blocks up pairwise. The error
+    /// is bounded by about `log₂(n)·ε`, and it is almost as fast as naive summation.
+    #[default]
+    Pairwise,
+    /// Keeps the rounding errors of the additions (and of the products, for dot products) and
+    /// adds them to the result in the end, like Kahan–Neumaier summation and the Sum2 and Dot2
+    /// algorithms of Ogita, Rump and Oishi. The result is as accurate as if it was computed with
+    /// twice the precision and then rounded, but it takes about twice as long.
+    Compensated,
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Types of elements of the slices which can be reduced: `f32` and `f64`.
+pub trait ReduceElement: SimdElement + sealed::Sealed {
+    #[doc(hidden)]
+    fn sum_slice(slice: &[Self], summation: Summation) -> Self;
+
+    #[doc(hidden)]
+    fn dot_slices(a: &[Self], b: &[Self], summation: Summation) -> Self;
+
+    #[doc(hidden)]
+    fn norm2_slice(slice: &[Self], summation: Summation) -> Self;
+
+    #[doc(hidden)]
+    fn mean_variance_slice(slice: &[Self]) -> (Self, Self);
+
+    #[doc(hidden)]
+    fn min_index_slice(slice: &[Self]) -> Option<(usize, Self)>;
+
+    #[doc(hidden)]
+    fn max_index_slice(slice: &[Self]) -> Option<(usize, Self)>;
+}
+
+/// Accumulates `step(accumulator, i)` over vectors `range`, every `ACCUMULATORS`-th vector into
+/// the same accumulator.
+#[inline(always)]
+fn naive<V>(range: Range<usize>, step: impl Fn(V, usize) -> V) -> V
+where
+    V: Copy + Default + Add<Output = V>,
+{
+    let mut accumulators = [V::default(); ACCUMULATORS];
+    let mut i = range.start;
+    while i + ACCUMULATORS <= range.end {
+        for (j, accumulator) in accumulators.iter_mut().enumerate() {
+            *accumulator = step(*accumulator, i + j);
+        }
+        i += ACCUMULATORS;
+    }
+    for (accumulator, i) in accumulators.iter_mut().zip(i..range.end) {
+        *accumulator = step(*accumulator, i);
+    }
+    (accumulators[0] + accumulators[1]) + (accumulators[2] + accumulators[3])
+}
+
+/// Accumulates `step(accumulator, i)` over blocks of vectors of `range` naively and sums the
+/// blocks pairwise.
+fn pairwise<V>(range: Range<usize>, step: &impl Fn(V, usize) -> V) -> V
+where
+    V: Copy + Default + Add<Output = V>,
+{
+    if range.len() <= PAIRWISE_BLOCK {
+        return naive(range, step);
+    }
+    let mid = range.start + range.len() / 2;
+    pairwise(range.start..mid, step) + pairwise(mid..range.end, step)
+}
+
+/// Sums `term(i)` over vectors `range`, where a term is a value and the rounding error of
+/// computing it. Returns the sums of the accumulators and their total error.
+#[inline(always)]
+fn compensated<V>(range: Range<usize>, term: impl Fn(usize) -> (V, V)) -> ([V; ACCUMULATORS], V)
+where
+    V: Copy + Default + Add<Output = V> + ErrorFreeTransform,
+{
+    let mut sums = [V::default(); ACCUMULATORS];
+    let mut errors = [V::default(); ACCUMULATORS];
+    let mut add = |j: usize, i: usize| {
+        let (value, value_error) = term(i);
+        let (sum, sum_error) = sums[j].two_sum(value);
+        sums[j] = sum;
+        errors[j] = errors[j] + (sum_error + value_error);
+    };
+    let mut i = range.start;
+    while i + ACCUMULATORS <= range.end {
+        for j in 0..ACCUMULATORS {
+            add(j, i + j);
+        }
+        i += ACCUMULATORS;
+    }
+    for (j, i) in (i..range.end).enumerate() {
+        add(j, i);
+    }
+    (sums, (errors[0] + errors[1]) + (errors[2] + errors[3]))
+}
+
+/// Sum of `count` vectors: the steps add terms to accumulators for [`Summation::Naive`] and
+/// [`Summation::Pairwise`], the terms with their errors are used by [`Summation::Compensated`].
+#[inline(always)]
+fn accumulate<T, const N: usize>(
+    count: usize,
+    summation: Summation,
+    step: impl Fn(Simd<T, N>, usize) -> Simd<T, N>,
+    term: impl Fn(usize) -> (Simd<T, N>, Simd<T, N>),
+) -> T
+where
+    T: SimdElement + Default + Add<Output = T> + ErrorFreeTransform,
+    LaneCount<N>: SupportedLaneCount,
+    Simd<T, N>: SimdFloat<Scalar = T> + Add<Output = Simd<T, N>> + ErrorFreeTransform,
+{
+    match summation {
+        Summation::Naive => naive(0..count, step).reduce_sum(),
+        Summation::Pairwise => pairwise(0..count, &step).reduce_sum(),
+        Summation::Compensated => {
+            let (sums, error) = compensated(0..count, term);
+            // the lanes are summed up the same way, with their errors
+            let mut sum = T::default();
+            let mut sum_error = error.reduce_sum();
+            for lane in sums.iter().flat_map(|sums| sums.to_array()) {
+                let (new_sum, error) = sum.two_sum(lane);
+                sum = new_sum;
+                sum_error = sum_error + error;
+            }
+            sum + sum_error
+        }
+    }
+}
+
+/// Index and value of the least (or the greatest) of the non-NaN elements of `$slice`.
+///
+/// Every lane keeps the best element among the ones it has seen and the number of its vector,
+/// which has type `$index` to match the mask of the elements. Longer slices are processed in
+/// segments of `$index::MAX + 1` vectors.
+macro_rules! extreme_index {
+    ($slice: expr, $ftype: ident, $index: ident, $lanes: literal, $better: ident, $op: tt) => {{
+        let slice = $slice;
+        let segment_len = ($index::MAX as usize).saturating_add(1).saturating_mul($lanes);
+        let mut result: Option<(usize, $ftype)> = None;
+        for (segment_number, segment) in slice.chunks(segment_len).enumerate() {
+            let mut best = Simd::<$ftype, $lanes>::splat($ftype::NAN);
+            let mut best_vector = Simd::<$index, $lanes>::splat(0);
+            for (i, start) in (0..segment.len()).step_by($lanes).enumerate() {
+                let x = load_padded(segment, start, $ftype::NAN);
+                // comparisons with NaN are false, so NaN elements are never taken
+                let take = x.$better(best) | (best.is_nan() & !x.is_nan());
+                best = take.select(x, best);
+                best_vector = take.select(Simd::splat(i as $index), best_vector);
+            }
+            for lane in 0..$lanes {
+                let value = best[lane];
+                if value.is_nan() {
+                    continue;
+                }
+                let index = segment_number * segment_len + best_vector[lane] as usize * $lanes + lane;
+                result = match result {
+                    Some((best_index, best_value))
+                        if !(value $op best_value || (value == best_value && index < best_index)) =>
+                    {
+                        result
+                    }
+                    _ => Some((index, value)),
+                };
+            }
+        }
+        result
+    }};
+}
+
+macro_rules! impl_reduce {
+    ($( $ftype: ident, $index: ident, $lanes: literal );+ $(;)?) => {
+        $(
+        impl sealed::Sealed for $ftype {}
+
+        impl ReduceElement for $ftype {
+            #[inline]
+            fn sum_slice(slice: &[Self], summation: Summation) -> Self {
+                let load = |i: usize| load_padded::<_, $lanes>(slice, i * $lanes, 0.0);
+                accumulate(
+                    slice.len().div_ceil($lanes),
+                    summation,
+                    |sum, i| sum + load(i),
+                    |i| (load(i), Simd::default()),
+                )
+            }
+
+            #[inline]
+            fn dot_slices(a: &[Self], b: &[Self], summation: Summation) -> Self {
+                let load = |i: usize| {
+                    (
+                        load_padded::<_, $lanes>(a, i * $lanes, 0.0),
+                        load_padded::<_, $lanes>(b, i * $lanes, 0.0),
+                    )
+                };
+                accumulate(
+                    a.len().div_ceil($lanes),
+                    summation,
+                    |sum, i| {
+                        let (a, b) = load(i);
+                        a.mul_add(b, sum)
+                    },
+                    |i| {
+                        let (a, b) = load(i);
+                        a.two_prod(b)
+                    },
+                )
+            }
+
+            #[inline]
+            fn norm2_slice(slice: &[Self], summation: Summation) -> Self {
+                let squares = Self::dot_slices(slice, slice, summation);
+                if squares.is_finite() && squares >= $ftype::MIN_POSITIVE {
+                    return Simd::<$ftype, 1>::splat(squares).sqrt()[0];
+                }
+                // the squares may have overflowed or lost precision to underflow, so the elements
+                // are scaled down (or up) by the greatest absolute value, which doesn't overflow
+                let count = slice.len().div_ceil($lanes);
+                let load = |i: usize| load_padded::<_, $lanes>(slice, i * $lanes, 0.0);
+                let max = naive(0..count, |max: Simd<$ftype, $lanes>, i| max.simd_max(load(i).abs()))
+                    .reduce_max();
+                if max == $ftype::INFINITY {
+                    // infinities win over NaNs, like in `hypot`
+                    return max;
+                }
+                if max == 0.0 {
+                    // NaN if there are NaNs, zero otherwise
+                    return squares;
+                }
+                let scale = Simd::splat(max);
+                let scaled_squares = accumulate(
+                    count,
+                    summation,
+                    |sum, i| {
+                        let x = load(i) / scale;
+                        x.mul_add(x, sum)
+                    },
+                    |i| {
+                        let x = load(i) / scale;
+                        x.two_prod(x)
+                    },
+                );
+                max * Simd::<$ftype, 1>::splat(scaled_squares).sqrt()[0]
+            }
+
+            #[inline]
+            fn mean_variance_slice(slice: &[Self]) -> (Self, Self) {
+                const GROUP: usize = ACCUMULATORS * $lanes;
+                // Welford's update of every lane of the accumulators, which have seen the same
+                // number of elements
+                let (groups, tail) = slice.split_at(slice.len() / GROUP * GROUP);
+                let mut means = [Simd::<$ftype, $lanes>::default(); ACCUMULATORS];
+                let mut m2s = means;
+                for (k, group) in groups.chunks_exact(GROUP).enumerate() {
+                    let reciprocal = Simd::splat((1.0 / (k + 1) as f64) as $ftype);
+                    for (j, lanes) in group.chunks_exact($lanes).enumerate() {
+                        let x = Simd::from_slice(lanes);
+                        let delta = x - means[j];
+                        means[j] = delta.mul_add(reciprocal, means[j]);
+                        m2s[j] = delta.mul_add(x - means[j], m2s[j]);
+                    }
+                }
+
+                // Chan's combination of the counts, means and sums of squared deviations of two
+                // parts of the data
+                let merge = |(count_a, mean_a, m2_a): (usize, $ftype, $ftype),
+                             (count_b, mean_b, m2_b): (usize, $ftype, $ftype)| {
+                    if count_b == 0 {
+                        return (count_a, mean_a, m2_a);
+                    }
+                    let count = count_a + count_b;
+                    let fraction_b = (count_b as f64 / count as f64) as $ftype;
+                    let delta = mean_b - mean_a;
+                    let mean = mean_a + delta * fraction_b;
+                    let m2 = m2_a + m2_b + delta * delta * count_a as $ftype * fraction_b;
+                    (count, mean, m2)
+                };
+                let lane_count = groups.len() / GROUP;
+                let mut state = (0, 0.0, 0.0);
+                for (means, m2s) in means.iter().zip(m2s.iter()) {
+                    for lane in 0..$lanes {
+                        state = merge(state, (lane_count, means[lane], m2s[lane]));
+                    }
+                }
+                for &x in tail {
+                    state = merge(state, (1, x, 0.0));
+                }
+
+                let (count, mean, m2) = state;
+                if count == 0 {
+                    return ($ftype::NAN, $ftype::NAN);
+                }
+                (mean, m2 / count as $ftype)
+            }
+
+            #[inline]
+            fn min_index_slice(slice: &[Self]) -> Option<(usize, Self)> {
+                extreme_index!(slice, $ftype, $index, $lanes, simd_lt, <)
+            }
+
+            #[inline]
+            fn max_index_slice(slice: &[Self]) -> Option<(usize, Self)> {
+                extreme_index!(slice, $ftype, $index, $lanes, simd_gt, >)
+            }
+        }
+        )+
+    };
+}
+
+impl_reduce!(
+    f32, u32, 16;
+    f64, u64, 8;
+);
+
+/// Sum of the elements of `slice`, `0.0` if it is empty.
+///
+/// # Examples
+/// ```
+/// use simd_addons::algo::reduce::{self, Summation};
+///
+/// let data: Vec<f32> = (1..=100).map(|i| i as f32).collect();
+/// assert_eq!(reduce::sum(&data, Summation::Naive), 5050.0);
+/// ```
+#[inline]
+pub fn sum<T: ReduceElement>(slice: &[T], summation: Summation) -> T {
+    T::sum_slice(slice, summation)
+}
+
+/// Dot product of `a` and `b`, i.e. the sum of products of their elements.
+///
+/// # Panics
+/// Panics if the slices have different lengths.
+#[inline]
+pub fn dot<T: ReduceElement>(a: &[T], b: &[T], summation: Summation) -> T {
+    assert_eq!(a.len(), b.len(), "slices must have the same length");
+    T::dot_slices(a, b, summation)
+}
+
+/// Euclidean norm of `slice`, i.e. the square root of the sum of squares of its elements.
+///
+/// The result doesn't overflow or underflow unless the norm itself does. It is infinite if any
+/// element is infinite, even if others are NaN, and NaN if any other element is NaN.
+#[inline]
+pub fn norm2<T: ReduceElement>(slice: &[T], summation: Summation) -> T {
+    T::norm2_slice(slice, summation)
+}
+
+/// Arithmetic mean of the elements of `slice`, NaN if it is empty. Computed by Welford's
+/// algorithm along with the variance, see [`mean_variance`].
+#[inline]
+pub fn mean<T: ReduceElement>(slice: &[T]) -> T {
+    T::mean_variance_slice(slice).0
+}
+
+/// Population variance of the elements of `slice`, i.e. the mean of squared deviations from the
+/// mean, NaN if it is empty. Multiply it by `n / (n - 1)` to get the sample variance.
+#[inline]
+pub fn variance<T: ReduceElement>(slice: &[T]) -> T {
+    T::mean_variance_slice(slice).1
+}
+
+/// Mean and population variance of the elements of `slice`, both NaN if it is empty.
+///
+/// Computed in a single pass by Welford's algorithm, which updates the mean and the sum of
+/// squared deviations with every element and unlike the textbook formula `E[x²] - E[x]²` doesn't
+/// suffer from cancellation. Every lane of several vectors runs its own update over a part of the
+/// elements, and the parts are combined by the formulas of Chan et al.
+#[inline]
+pub fn mean_variance<T: ReduceElement>(slice: &[T]) -> (T, T) {
+    T::mean_variance_slice(slice)
+}
+
+/// Index and value of the least element of `slice`, ignoring NaNs. Returns the first such index
+/// if there are several ones, and `None` if there are no elements other than NaN.
+///
+/// # Examples
+/// ```
+/// use simd_addons::algo::reduce;
+///
+/// assert_eq!(reduce::min_index(&[2.0, -1.0, f64::NAN, -1.0]), Some((1, -1.0)));
+/// assert_eq!(reduce::min_index::<f64>(&[f64::NAN]), None);
+/// ```
+#[inline]
+pub fn min_index<T: ReduceElement>(slice: &[T]) -> Option<(usize, T)> {
+    T::min_index_slice(slice)
+}
+
+/// Index and value of the greatest element of `slice`, ignoring NaNs. Returns the first such
+/// index if there are several ones, and `None` if there are no elements other than NaN.
+#[inline]
+pub fn max_index<T: ReduceElement>(slice: &[T]) -> Option<(usize, T)> {
+    T::max_index_slice(slice)
+}
@@ -0,0 +1,357 @@
+//! Prefix sums (scans) of vector lanes and slices, over addition or any other associative
+//! operation.
+//!
+//! An inclusive scan replaces the `i`-th element with the combination of the elements `0..=i`,
+//! an exclusive one with the combination of the elements `0..i`, i.e. the first element becomes
+//! the identity of the operation. Lanes are scanned in `log₂(N)` steps, each of them combines the
+//! lanes with the ones shifted by a power of two. Slices are scanned by vectors, with the total of
+//! the previous ones carried over to the next one.
+//!
+//! Integers wrap around on overflow. Floats are combined in a different order than by a
+//! sequential loop, so the rounding errors of sums and products may differ from it.
+//!
+//! # Examples
+//! ```
+//! # #![feature(portable_simd)]
+//! # use std::simd::prelude::*;
+//! use simd_addons::algo::scan::{self, Max, SimdScan};
+//!
+//! let lanes = u32x4::from_array([1, 2, 3, 4]);
+//! assert_eq!(lanes.prefix_sum(), u32x4::from_array([1, 3, 6, 10]));
+//! assert_eq!(lanes.exclusive_prefix_sum(), u32x4::from_array([0, 1, 3, 6]));
+//!
+//! // offsets of the rows of a CSR matrix from their lengths
+//! let mut offsets = [2, 0, 3, 1];
+//! assert_eq!(scan::exclusive_prefix_sum(&mut offsets), 6);
+//! assert_eq!(offsets, [0, 2, 2, 5]);
+//!
+//! let mut data = [1.0, 3.0, -2.0, 5.0, 4.0];
+//! scan::scan::<Max, _>(&mut data);
+//! assert_eq!(data, [1.0, 3.0, 3.0, 5.0, 5.0]);
+//! ```
+
+use crate::{
+    algo::{load_padded, store},
+    simd::{
+        cmp::SimdOrd, num::SimdFloat, LaneCount, Mask, Simd, SimdElement, SupportedLaneCount,
+        Swizzle,
+    },
+};
+
+/// Associative operation of a scan. It doesn't have to be commutative: earlier elements are
+/// always the first operand.
+///
+/// # Examples
+/// ```
+/// # #![feature(portable_simd)]
+/// # use std::simd::prelude::*;
+/// use simd_addons::algo::scan::{self, ScanOp};
+/// use std::simd::{LaneCount, SupportedLaneCount};
+///
+/// /// Takes the last non-zero element.
+/// struct LastNonZero;
+///
+/// impl ScanOp<u32> for LastNonZero {
+///     const IDENTITY: u32 = 0;
+///
+///     fn apply<const N: usize>(a: Simd<u32, N>, b: Simd<u32, N>) -> Simd<u32, N>
+///     where
+///         LaneCount<N>: SupportedLaneCount,
+///     {
+///         b.simd_eq(Simd::splat(0)).select(a, b)
+///     }
+/// }
+///
+/// let mut data = [0, 7, 0, 0, 3, 0];
+/// scan::scan::<LastNonZero, _>(&mut data);
+/// assert_eq!(data, [0, 7, 7, 7, 3, 3]);
+/// ```
+pub trait ScanOp<T: SimdElement> {
+    /// Identity element of the operation, i.e. `apply(IDENTITY, x) == apply(x, IDENTITY) == x`.
+    const IDENTITY: T;
+
+    /// Combines the lanes of `a` with the corresponding lanes of `b`.
+    fn apply<const N: usize>(a: Simd<T, N>, b: Simd<T, N>) -> Simd<T, N>
+    where
+        LaneCount<N>: SupportedLaneCount;
+}
+
+/// Addition. Its identity for floats is `-0.0`, so the sign of zeros is kept.
+pub struct Sum;
+
+/// Multiplication.
+pub struct Product;
+
+/// Maximum, for floats NaNs are ignored like by [`SimdFloat::simd_max`].
+pub struct Max;
+
+/// Minimum, for floats NaNs are ignored like by [`SimdFloat::simd_min`].
+pub struct Min;
+
+macro_rules! impl_scan_ops {
+    ($( $type: ident, $zero: literal, $one: literal, $min: expr, $max: expr, $ord: ident );+ $(;)?) => {
+        $(
+        impl ScanOp<$type> for Sum {
+            const IDENTITY: $type = $zero;
+
+            #[inline(always)]
+            fn apply<const N: usize>(a: Simd<$type, N>, b: Simd<$type, N>) -> Simd<$type, N>
+            where
+                LaneCount<N>: SupportedLaneCount,
+            {
+                a + b
+            }
+        }
+
+        impl ScanOp<$type> for Product {
+            const IDENTITY: $type = $one;
+
+            #[inline(always)]
+            fn apply<const N: usize>(a: Simd<$type, N>, b: Simd<$type, N>) -> Simd<$type, N>
+            where
+                LaneCount<N>: SupportedLaneCount,
+            {
+                a * b
+            }
+        }
+
+        impl ScanOp<$type> for Max {
+            const IDENTITY: $type = $min;
+
+            #[inline(always)]
+            fn apply<const N: usize>(a: Simd<$type, N>, b: Simd<$type, N>) -> Simd<$type, N>
+            where
+                LaneCount<N>: SupportedLaneCount,
+            {
+                $ord::simd_max(a, b)
+            }
+        }
+
+        impl ScanOp<$type> for Min {
+            const IDENTITY: $type = $max;
+
+            #[inline(always)]
+            fn apply<const N: usize>(a: Simd<$type, N>, b: Simd<$type, N>) -> Simd<$type, N>
+            where
+                LaneCount<N>: SupportedLaneCount,
+            {
+                $ord::simd_min(a, b)
+            }
+        }
+        )+
+    };
+}
+
+impl_scan_ops!(
+    i32, 0, 1, i32::MIN, i32::MAX, SimdOrd;
+    u32, 0, 1, u32::MIN, u32::MAX, SimdOrd;
+    i64, 0, 1, i64::MIN, i64::MAX, SimdOrd;
+    u64, 0, 1, u64::MIN, u64::MAX, SimdOrd;
+    f32, -0.0, 1.0, f32::NEG_INFINITY, f32::INFINITY, SimdFloat;
+    f64, -0.0, 1.0, f64::NEG_INFINITY, f64::INFINITY, SimdFloat;
+);
+
+/// Moves lane `i` to lane `i + D`. The first `D` lanes are garbage.
+struct ShiftRight<const D: usize>;
+
+const fn shift_right_index<const N: usize>(distance: usize) -> [usize; N] {
+    let mut index = [0; N];
+    let mut i = distance;
+    while i < N {
+        index[i] = i - distance;
+        i += 1;
+    }
+    index
+}
+
+impl<const D: usize, const N: usize> Swizzle<N> for ShiftRight<D> {
+    const INDEX: [usize; N] = shift_right_index::<N>(D);
+}
+
+/// Lanes shifted to the right by `D`, with the identity of `O` shifted in.
+#[inline(always)]
+fn shift_right<T, O, const D: usize, const N: usize>(lanes: Simd<T, N>) -> Simd<T, N>
+where
+    T: SimdElement,
+    O: ScanOp<T>,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let shifted_in = Mask::<T::Mask, N>::from_array(core::array::from_fn(|i| i < D));
+    shifted_in.select(Simd::splat(O::IDENTITY), ShiftRight::<D>::swizzle(lanes))
+}
+
+/// Combines every lane with the one at distance `D` to the left of it, if it is a step of scanning
+/// `N` lanes.
+#[inline(always)]
+fn step<T, O, const D: usize, const N: usize>(lanes: Simd<T, N>) -> Simd<T, N>
+where
+    T: SimdElement,
+    O: ScanOp<T>,
+    LaneCount<N>: SupportedLaneCount,
+{
+    if D >= N {
+        return lanes;
+    }
+    O::apply(shift_right::<T, O, D, N>(lanes), lanes)
+}
+
+/// Inclusive scan of the lanes.
+#[inline(always)]
+fn scan_lanes<T, O, const N: usize>(lanes: Simd<T, N>) -> Simd<T, N>
+where
+    T: SimdElement,
+    O: ScanOp<T>,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let lanes = step::<T, O, 1, N>(lanes);
+    let lanes = step::<T, O, 2, N>(lanes);
+    let lanes = step::<T, O, 4, N>(lanes);
+    let lanes = step::<T, O, 8, N>(lanes);
+    let lanes = step::<T, O, 16, N>(lanes);
+    step::<T, O, 32, N>(lanes)
+}
+
+/// Scans `slice` in place by vectors of `N` lanes and returns the total of all its elements.
+#[inline(always)]
+fn scan_slice<T, O, const N: usize>(slice: &mut [T], exclusive: bool) -> T
+where
+    T: SimdElement,
+    O: ScanOp<T>,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let mut carry = Simd::<T, N>::splat(O::IDENTITY);
+    for start in (0..slice.len()).step_by(N) {
+        let lanes = scan_lanes::<T, O, N>(load_padded(slice, start, O::IDENTITY));
+        let result = if exclusive {
+            O::apply(carry, shift_right::<T, O, 1, N>(lanes))
+        } else {
+            O::apply(carry, lanes)
+        };
+        store(slice, start, result);
+        // the padding is the identity, so the last lane is the total of the vector
+        carry = O::apply(carry, Simd::splat(lanes[N - 1]));
+    }
+    carry[0]
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Types of elements which can be scanned: 32- and 64-bit integers and floats.
+pub trait ScanElement: SimdElement + sealed::Sealed {
+    #[doc(hidden)]
+    fn scan_slice<O: ScanOp<Self>>(slice: &mut [Self], exclusive: bool) -> Self;
+}
+
+macro_rules! impl_scan_element {
+    ($( $type: ident, $lanes: literal );+ $(;)?) => {
+        $(
+        impl sealed::Sealed for $type {}
+
+        impl ScanElement for $type {
+            #[inline]
+            fn scan_slice<O: ScanOp<Self>>(slice: &mut [Self], exclusive: bool) -> Self {
+                scan_slice::<_, O, $lanes>(slice, exclusive)
+            }
+        }
+        )+
+    };
+}
+
+impl_scan_element!(
+    i32, 16;
+    u32, 16;
+    f32, 16;
+    i64, 8;
+    u64, 8;
+    f64, 8;
+);
+
+/// Scans of vector lanes.
+pub trait SimdScan: Sized {
+    /// Type of the elements.
+    type Element: SimdElement;
+
+    /// Inclusive prefix sum: lane `i` becomes the sum of lanes `0..=i`.
+    fn prefix_sum(self) -> Self;
+
+    /// Exclusive prefix sum: lane `i` becomes the sum of lanes `0..i`.
+    fn exclusive_prefix_sum(self) -> Self;
+
+    /// Inclusive scan over `O`: lane `i` becomes the combination of lanes `0..=i`.
+    fn scan<O: ScanOp<Self::Element>>(self) -> Self;
+
+    /// Exclusive scan over `O`: lane `i` becomes the combination of lanes `0..i`.
+    fn exclusive_scan<O: ScanOp<Self::Element>>(self) -> Self;
+}
+
+impl<T: ScanElement, const N: usize> SimdScan for Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    Sum: ScanOp<T>,
+{
+    type Element = T;
+
+    #[inline]
+    fn prefix_sum(self) -> Self {
+        self.scan::<Sum>()
+    }
+
+    #[inline]
+    fn exclusive_prefix_sum(self) -> Self {
+        self.exclusive_scan::<Sum>()
+    }
+
+    #[inline]
+    fn scan<O: ScanOp<T>>(self) -> Self {
+        scan_lanes::<T, O, N>(self)
+    }
+
+    #[inline]
+    fn exclusive_scan<O: ScanOp<T>>(self) -> Self {
+        shift_right::<T, O, 1, N>(scan_lanes::<T, O, N>(self))
+    }
+}
+
+/// Replaces every element of `slice` with the sum of the elements up to and including it.
+#[inline]
+pub fn prefix_sum<T: ScanElement>(slice: &mut [T])
+where
+    Sum: ScanOp<T>,
+{
+    T::scan_slice::<Sum>(slice, false);
+}
+
+/// Replaces every element of `slice` with the sum of the elements before it, and returns the sum
+/// of all the elements.
+#[inline]
+pub fn exclusive_prefix_sum<T: ScanElement>(slice: &mut [T]) -> T
+where
+    Sum: ScanOp<T>,
+{
+    T::scan_slice::<Sum>(slice, true)
+}
+
+/// Replaces every element of `slice` with the combination by `O` of the elements up to and
+/// including it.
+#[inline]
+pub fn scan<O: ScanOp<T>, T: ScanElement>(slice: &mut [T]) {
+    T::scan_slice::<O>(slice, false);
+}
+
+/// Replaces every element of `slice` with the combination by `O` of the elements before it, and
+/// returns the combination of all the elements.
+///
+/// # Examples
+/// ```
+/// use simd_addons::algo::scan::{self, Product};
+///
+/// let mut data = [2, 3, 4];
+/// assert_eq!(scan::exclusive_scan::<Product, _>(&mut data), 24);
+/// assert_eq!(data, [1, 2, 6]);
+/// ```
+#[inline]
+pub fn exclusive_scan<O: ScanOp<T>, T: ScanElement>(slice: &mut [T]) -> T {
+    T::scan_slice::<O>(slice, true)
+}
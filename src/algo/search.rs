@@ -0,0 +1,258 @@
+//! Search of bytes and substrings in byte slices, like `memchr` and `memmem`.
+//!
+//! Bytes are searched for by comparing blocks of the haystack with the needles and turning the
+//! results into bitmasks, whose trailing (or leading, for reverse search) zeros give the position
+//! of the first match. Substrings are searched for by the packed pair algorithm: the first and
+//! the last bytes of the needle are compared with the haystack at every position of a vector at
+//! once, and only the positions where both of them match are compared with the whole needle.
+//!
+//! # Examples
+//! ```
+//! use simd_addons::algo::search;
+//!
+//! let log = b"GET /index.html 200\nPOST /login 302\nGET /favicon.ico 404\n";
+//! assert_eq!(search::find_byte(log, b'\n'), Some(19));
+//! assert_eq!(search::rfind_byte(log, b'\n'), Some(56));
+//! assert_eq!(search::find_any_of(log, [b'3', b'4']), Some(32));
+//! assert_eq!(search::find_byte_iter(log, b'\n').count(), 3);
+//! assert_eq!(search::find(log, b"GET"), Some(0));
+//! assert_eq!(search::find_iter(log, b"GET").collect::<Vec<_>>(), [0, 36]);
+//! ```
+
+use core::iter::FusedIterator;
+
+use crate::{
+    algo::load_padded,
+    simd::{cmp::SimdPartialEq, Mask, Simd},
+};
+
+/// Number of bytes compared at once.
+const LANES: usize = 32;
+
+/// Number of bytes of a block of the haystack, whose matches are checked together.
+const BLOCK: usize = 2 * LANES;
+
+/// Lanes of `chunk` equal to any of `needles`.
+#[inline(always)]
+fn matches<const K: usize>(
+    chunk: Simd<u8, LANES>,
+    needles: &[Simd<u8, LANES>; K],
+) -> Mask<i8, LANES> {
+    needles.iter().fold(Mask::splat(false), |mask, &needle| {
+        mask | chunk.simd_eq(needle)
+    })
+}
+
+/// Bitmask of the bytes of `haystack[start..start + BLOCK]` equal to any of `needles`, the bits
+/// of the bytes past the end are zero.
+#[inline(always)]
+fn block_bits<const K: usize>(
+    haystack: &[u8],
+    start: usize,
+    needles: &[Simd<u8, LANES>; K],
+) -> u64 {
+    if let Some(block) = haystack.get(start..start + BLOCK) {
+        let low = matches(Simd::from_slice(&block[..LANES]), needles);
+        let high = matches(Simd::from_slice(&block[LANES..]), needles);
+        // most of the blocks don't match, which is checked faster than building the bitmask
+        if !(low | high).any() {
+            return 0;
+        }
+        return low.to_bitmask() | (high.to_bitmask() << LANES);
+    }
+    let tail = &haystack[start..];
+    let low = matches(load_padded(tail, 0, 0), needles);
+    let high = matches(load_padded(tail, LANES, 0), needles);
+    (low.to_bitmask() | (high.to_bitmask() << LANES)) & (u64::MAX >> (BLOCK - tail.len()))
+}
+
+/// Returns the index of the first byte of `haystack` equal to `needle`.
+#[inline]
+pub fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    find_any_of(haystack, [needle])
+}
+
+/// Returns the index of the last byte of `haystack` equal to `needle`.
+#[inline]
+pub fn rfind_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    rfind_any_of(haystack, [needle])
+}
+
+/// Returns the index of the first byte of `haystack` equal to any of `needles`. Every needle
+/// costs a comparison per byte, so a few of them (like two or three) are searched for almost as
+/// fast as a single one.
+#[inline]
+pub fn find_any_of<const K: usize>(haystack: &[u8], needles: [u8; K]) -> Option<usize> {
+    let needles = needles.map(Simd::splat);
+    (0..haystack.len()).step_by(BLOCK).find_map(|start| {
+        let bits = block_bits(haystack, start, &needles);
+        (bits != 0).then(|| start + bits.trailing_zeros() as usize)
+    })
+}
+
+/// Returns the index of the last byte of `haystack` equal to any of `needles`.
+#[inline]
+pub fn rfind_any_of<const K: usize>(haystack: &[u8], needles: [u8; K]) -> Option<usize> {
+    let needles = needles.map(Simd::splat);
+    let mut end = haystack.len();
+    while end > 0 {
+        // blocks are aligned to the end, the first one may be incomplete
+        let start = end.saturating_sub(BLOCK);
+        let bits = block_bits(&haystack[..end], start, &needles);
+        if bits != 0 {
+            return Some(start + 63 - bits.leading_zeros() as usize);
+        }
+        end = start;
+    }
+    None
+}
+
+/// Returns an iterator over the indices of the bytes of `haystack` equal to `needle`, in
+/// ascending order, or descending if it is reversed.
+#[inline]
+pub fn find_byte_iter(haystack: &[u8], needle: u8) -> Matches<'_, 1> {
+    find_any_of_iter(haystack, [needle])
+}
+
+/// Returns an iterator over the indices of the bytes of `haystack` equal to any of `needles`, in
+/// ascending order, or descending if it is reversed.
+///
+/// # Examples
+/// ```
+/// use simd_addons::algo::search;
+///
+/// let matches = search::find_any_of_iter(b"a,b;c,d", [b',', b';']);
+/// assert_eq!(matches.rev().collect::<Vec<_>>(), [5, 3, 1]);
+/// ```
+#[inline]
+pub fn find_any_of_iter<const K: usize>(haystack: &[u8], needles: [u8; K]) -> Matches<'_, K> {
+    Matches {
+        haystack,
+        needles,
+        start: 0,
+        end: haystack.len(),
+    }
+}
+
+/// Iterator over the indices of the bytes equal to any of `K` needles, created by
+/// [`find_byte_iter`] and [`find_any_of_iter`].
+#[derive(Clone, Debug)]
+pub struct Matches<'a, const K: usize> {
+    haystack: &'a [u8],
+    needles: [u8; K],
+    /// Bytes `start..end` haven't been searched yet.
+    start: usize,
+    end: usize,
+}
+
+impl<const K: usize> Iterator for Matches<'_, K> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let index = self.start + find_any_of(&self.haystack[self.start..self.end], self.needles)?;
+        self.start = index + 1;
+        Some(index)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.start))
+    }
+}
+
+impl<const K: usize> DoubleEndedIterator for Matches<'_, K> {
+    #[inline]
+    fn next_back(&mut self) -> Option<usize> {
+        let index = self.start + rfind_any_of(&self.haystack[self.start..self.end], self.needles)?;
+        self.end = index;
+        Some(index)
+    }
+}
+
+impl<const K: usize> FusedIterator for Matches<'_, K> {}
+
+/// Bitmask of the positions `start..start + LANES` where `first` and `last` bytes of the needle
+/// match, with the last one at distance `distance`. The positions starting at `candidates` and
+/// further are not reported.
+#[inline(always)]
+fn pair_bits(
+    haystack: &[u8],
+    start: usize,
+    distance: usize,
+    candidates: usize,
+    first: Simd<u8, LANES>,
+    last: Simd<u8, LANES>,
+) -> u64 {
+    if start + LANES <= candidates {
+        let first_matches = Simd::from_slice(&haystack[start..]).simd_eq(first);
+        let last_matches = Simd::from_slice(&haystack[start + distance..]).simd_eq(last);
+        return (first_matches & last_matches).to_bitmask();
+    }
+    let first_matches = load_padded(haystack, start, 0).simd_eq(first);
+    let last_matches = load_padded(haystack, start + distance, 0).simd_eq(last);
+    (first_matches & last_matches).to_bitmask() & (u64::MAX >> (64 - (candidates - start)))
+}
+
+/// Returns the index of the first occurrence of `needle` in `haystack`. An empty needle occurs at
+/// every index, so it is found at `0`.
+#[inline]
+pub fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    let (&first, &last) = match needle {
+        [] => return Some(0),
+        [byte] => return find_byte(haystack, *byte),
+        [first, .., last] => (first, last),
+    };
+    if needle.len() > haystack.len() {
+        return None;
+    }
+    let distance = needle.len() - 1;
+    let candidates = haystack.len() - distance;
+    let (first, last) = (Simd::splat(first), Simd::splat(last));
+    for start in (0..candidates).step_by(LANES) {
+        let mut bits = pair_bits(haystack, start, distance, candidates, first, last);
+        while bits != 0 {
+            let index = start + bits.trailing_zeros() as usize;
+            if haystack[index + 1..index + distance] == needle[1..distance] {
+                return Some(index);
+            }
+            bits &= bits - 1;
+        }
+    }
+    None
+}
+
+/// Returns an iterator over the indices of the non-overlapping occurrences of `needle` in
+/// `haystack`, like [`str::match_indices`].
+#[inline]
+pub fn find_iter<'a>(haystack: &'a [u8], needle: &'a [u8]) -> SubstringMatches<'a> {
+    SubstringMatches {
+        haystack,
+        needle,
+        start: 0,
+    }
+}
+
+/// Iterator over the indices of the non-overlapping occurrences of a needle, created by
+/// [`find_iter`].
+#[derive(Clone, Debug)]
+pub struct SubstringMatches<'a> {
+    haystack: &'a [u8],
+    needle: &'a [u8],
+    /// Index to search from, past the end of the haystack if the search is over.
+    start: usize,
+}
+
+impl Iterator for SubstringMatches<'_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let index = self.start + find(self.haystack.get(self.start..)?, self.needle)?;
+        // an empty needle is found at every index
+        self.start = index + self.needle.len().max(1);
+        Some(index)
+    }
+}
+
+impl FusedIterator for SubstringMatches<'_> {}
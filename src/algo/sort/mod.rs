@@ -0,0 +1,278 @@
+//! Sorting of vector lanes and slices.
+//!
+//! Lanes are sorted by bitonic networks. Slices are sorted by quicksort which partitions them by
+//! compressing the lanes of each vector to both ends (see [`compress`](crate::algo::compress)) and
+//! sorts partitions of up to a few vectors by the networks. Sorting is unstable, i.e. the order of equal elements isn't preserved.
+//!
+//! The networks are built of lanewise minimums and maximums, which the baseline targets lack for
+//! some element types (e.g. x86-64 has no such instructions for unsigned and 64-bit integers
+//! before SSE4.1 and AVX-512 respectively), so enable the target features of the hardware, e.g.
+//! with `-C target-cpu=native`.
+//!
+//! Floats are sorted in the order of [`f32::total_cmp`]: negative NaNs first, then negative
+//! numbers, `-0.0`, `+0.0`, positive numbers and positive NaNs last.
+//!
+//! # Examples
+//! ```
+//! # #![feature(portable_simd)]
+//! # use std::simd::prelude::*;
+//! use simd_addons::algo::sort::{self, SimdSort};
+//!
+//! let mut keys = [3.0, f32::NAN, -1.0, 2.0, -0.0, 0.0];
+//! let mut indices = [0; 6];
+//! sort::argsort(&mut keys, &mut indices);
+//! assert_eq!(indices, [2, 4, 5, 3, 0, 1]);
+//!
+//! let (lanes, values) = u32x4::from_array([7, 1, 5, 1]).sort_lanes_with(u32x4::from_array([0, 1, 2, 3]));
+//! assert_eq!(lanes, u32x4::from_array([1, 1, 5, 7]));
+//! assert_eq!(values, u32x4::from_array([1, 3, 2, 0]));
+//! ```
+
+mod network;
+mod quicksort;
+
+use crate::simd::{
+    num::{SimdFloat, SimdInt, SimdUint},
+    LaneCount, Simd, SimdElement, SupportedLaneCount,
+};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Types of elements which can be sorted: 32- and 64-bit integers and floats.
+pub trait SortElement: SimdElement + sealed::Sealed {
+    /// Unsigned integer of the same width as the element. Values sorted along with the elements
+    /// and indices of [`argsort`] have this type.
+    type Index: SimdElement + TryFrom<usize>;
+
+    #[doc(hidden)]
+    fn sort_slice(slice: &mut [Self]);
+
+    #[doc(hidden)]
+    fn sort_pairs_slice(keys: &mut [Self], values: &mut [Self::Index]);
+
+    #[doc(hidden)]
+    fn sort_lanes<const N: usize>(lanes: Simd<Self, N>) -> Simd<Self, N>
+    where
+        LaneCount<N>: SupportedLaneCount;
+
+    #[doc(hidden)]
+    fn sort_lanes_with<const N: usize>(
+        lanes: Simd<Self, N>,
+        values: Simd<Self::Index, N>,
+    ) -> (Simd<Self, N>, Simd<Self::Index, N>)
+    where
+        LaneCount<N>: SupportedLaneCount;
+}
+
+macro_rules! impl_sort_int {
+    ($( $itype: ident, $index: ident, $lanes: literal );+ $(;)?) => {
+        $(
+        impl sealed::Sealed for $itype {}
+
+        impl SortElement for $itype {
+            type Index = $index;
+
+            #[inline]
+            fn sort_slice(slice: &mut [Self]) {
+                quicksort::sort::<_, _, $lanes>(slice);
+            }
+
+            #[inline]
+            fn sort_pairs_slice(keys: &mut [Self], values: &mut [$index]) {
+                quicksort::sort::<_, _, $lanes>((keys, values));
+            }
+
+            #[inline]
+            fn sort_lanes<const N: usize>(lanes: Simd<Self, N>) -> Simd<Self, N>
+            where
+                LaneCount<N>: SupportedLaneCount,
+            {
+                network::sort_lanes(lanes)
+            }
+
+            #[inline]
+            fn sort_lanes_with<const N: usize>(
+                lanes: Simd<Self, N>,
+                values: Simd<$index, N>,
+            ) -> (Simd<Self, N>, Simd<$index, N>)
+            where
+                LaneCount<N>: SupportedLaneCount,
+            {
+                network::sort_lanes((lanes, values))
+            }
+        }
+        )+
+    };
+}
+
+impl_sort_int!(
+    i32, u32, 16;
+    u32, u32, 16;
+    i64, u64, 8;
+    u64, u64, 8;
+);
+
+/// Floats are sorted as signed integers of the same order. The mapping between them flips the
+/// bits of negative numbers except for the sign, it is an involution.
+macro_rules! impl_sort_float {
+    ($( $ftype: ident, $itype: ident, $index: ident );+ $(;)?) => {
+        $(
+        impl sealed::Sealed for $ftype {}
+
+        impl SortElement for $ftype {
+            type Index = $index;
+
+            #[inline]
+            fn sort_slice(slice: &mut [Self]) {
+                let keys = float_keys!(slice, $itype);
+                $itype::sort_slice(keys);
+                float_keys!(keys, $itype);
+            }
+
+            #[inline]
+            fn sort_pairs_slice(keys: &mut [Self], values: &mut [$index]) {
+                let keys = float_keys!(keys, $itype);
+                $itype::sort_pairs_slice(keys, values);
+                float_keys!(keys, $itype);
+            }
+
+            #[inline]
+            fn sort_lanes<const N: usize>(lanes: Simd<Self, N>) -> Simd<Self, N>
+            where
+                LaneCount<N>: SupportedLaneCount,
+            {
+                let keys = float_keys!(@simd lanes.to_bits().cast::<$itype>(), $itype);
+                let sorted = $itype::sort_lanes(keys);
+                Simd::from_bits(float_keys!(@simd sorted, $itype).cast())
+            }
+
+            #[inline]
+            fn sort_lanes_with<const N: usize>(
+                lanes: Simd<Self, N>,
+                values: Simd<$index, N>,
+            ) -> (Simd<Self, N>, Simd<$index, N>)
+            where
+                LaneCount<N>: SupportedLaneCount,
+            {
+                let keys = float_keys!(@simd lanes.to_bits().cast::<$itype>(), $itype);
+                let (sorted, values) = $itype::sort_lanes_with(keys, values);
+                (Simd::from_bits(float_keys!(@simd sorted, $itype).cast()), values)
+            }
+        }
+        )+
+    };
+}
+
+/// Maps floats to integer keys and back, in place for slices.
+macro_rules! float_keys {
+    (@simd $bits: expr, $itype: ident) => {{
+        let bits = $bits;
+        bits ^ ((bits >> Simd::splat($itype::BITS as $itype - 1)) & Simd::splat($itype::MAX))
+    }};
+
+    ($slice: expr, $itype: ident) => {{
+        // SAFETY: floats and integers of the same width have the same size and alignment, and
+        // every bit pattern is valid for both of them
+        let keys = unsafe {
+            core::slice::from_raw_parts_mut($slice.as_mut_ptr().cast::<$itype>(), $slice.len())
+        };
+        for key in keys.iter_mut() {
+            *key ^= (*key >> ($itype::BITS - 1)) & $itype::MAX;
+        }
+        keys
+    }};
+}
+
+impl_sort_float!(
+    f32, i32, u32;
+    f64, i64, u64;
+);
+
+/// Sorting of vector lanes.
+pub trait SimdSort: Sized {
+    /// Vector of values which can be sorted along with the lanes.
+    type Values;
+
+    /// Sorts the lanes ascending.
+    fn sort_lanes(self) -> Self;
+
+    /// Sorts the lanes ascending and permutes `values` the same way. Lanes with equal elements
+    /// are ordered by the values.
+    fn sort_lanes_with(self, values: Self::Values) -> (Self, Self::Values);
+}
+
+impl<T: SortElement, const N: usize> SimdSort for Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    type Values = Simd<T::Index, N>;
+
+    #[inline]
+    fn sort_lanes(self) -> Self {
+        T::sort_lanes(self)
+    }
+
+    #[inline]
+    fn sort_lanes_with(self, values: Self::Values) -> (Self, Self::Values) {
+        T::sort_lanes_with(self, values)
+    }
+}
+
+/// Sorts `slice` ascending.
+///
+/// # Examples
+/// ```
+/// use simd_addons::algo::sort;
+///
+/// let mut data = [5, -3, 7, 0, -3];
+/// sort::sort(&mut data);
+/// assert_eq!(data, [-3, -3, 0, 5, 7]);
+/// ```
+#[inline]
+pub fn sort<T: SortElement>(slice: &mut [T]) {
+    T::sort_slice(slice);
+}
+
+/// Sorts `keys` ascending and permutes `values` the same way.
+///
+/// # Panics
+/// Panics if the slices have different lengths.
+///
+/// # Examples
+/// ```
+/// use simd_addons::algo::sort;
+///
+/// let mut keys = [2.5f32, -1.0, 0.5];
+/// let mut values = [20, 10, 5];
+/// sort::sort_pairs(&mut keys, &mut values);
+/// assert_eq!(keys, [-1.0, 0.5, 2.5]);
+/// assert_eq!(values, [10, 5, 20]);
+/// ```
+#[inline]
+pub fn sort_pairs<T: SortElement>(keys: &mut [T], values: &mut [T::Index]) {
+    assert_eq!(
+        keys.len(),
+        values.len(),
+        "keys and values must have the same length"
+    );
+    T::sort_pairs_slice(keys, values);
+}
+
+/// Sorts `keys` ascending and writes their original indices to `indices`, i.e. `indices[i]` is
+/// the index of the `i`-th smallest key before sorting. Sort a copy of the keys to keep the
+/// original order.
+///
+/// # Panics
+/// Panics if the slices have different lengths or the indices don't fit into
+/// [`T::Index`](SortElement::Index).
+#[inline]
+pub fn argsort<T: SortElement>(keys: &mut [T], indices: &mut [T::Index]) {
+    for (i, index) in indices.iter_mut().enumerate() {
+        *index = T::Index::try_from(i)
+            .ok()
+            .expect("indices must fit into the index type");
+    }
+    sort_pairs(keys, indices);
+}
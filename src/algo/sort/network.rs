@@ -0,0 +1,245 @@
+//! Bitonic sorting networks over lanes of vectors and over short sequences of vectors.
+
+use crate::simd::{
+    cmp::{SimdOrd, SimdPartialEq, SimdPartialOrd},
+    LaneCount, Mask, MaskElement, Simd, SimdElement, SupportedLaneCount, Swizzle,
+};
+
+/// Swaps lanes `i` and `i ^ D`. Lanes without a partner (only possible when `D >= N`) are kept.
+struct Xor<const D: usize>;
+
+const fn xor_index<const N: usize>(distance: usize) -> [usize; N] {
+    let mut index = [0; N];
+    let mut i = 0;
+    while i < N {
+        index[i] = if i ^ distance < N { i ^ distance } else { i };
+        i += 1;
+    }
+    index
+}
+
+impl<const D: usize, const N: usize> Swizzle<N> for Xor<D> {
+    const INDEX: [usize; N] = xor_index::<N>(D);
+}
+
+#[inline(always)]
+fn lane_mask<M: MaskElement, const N: usize>(f: impl Fn(usize) -> bool) -> Mask<M, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    Mask::from_array(core::array::from_fn(f))
+}
+
+/// Vector of items being sorted: keys, or keys with values attached.
+pub(crate) trait Lanes<const N: usize>: Copy
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    type Mask: Copy;
+
+    /// Mask with the lanes `i` for which `f(i)` is true set.
+    fn mask(f: impl Fn(usize) -> bool) -> Self::Mask;
+
+    /// Lanewise smaller and greater items of `self` and `other`.
+    fn min_max(self, other: Self) -> (Self, Self);
+
+    /// Compares lanes `i` and `i ^ D`, and puts the greater item to the lanes of `take_max` and
+    /// the smaller one to the others.
+    fn exchange<const D: usize>(self, take_max: Self::Mask) -> Self;
+
+    fn reverse(self) -> Self;
+}
+
+impl<K, const N: usize> Lanes<N> for Simd<K, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    K: SimdElement,
+    Simd<K, N>: SimdOrd,
+{
+    type Mask = Mask<K::Mask, N>;
+
+    #[inline(always)]
+    fn mask(f: impl Fn(usize) -> bool) -> Self::Mask {
+        lane_mask(f)
+    }
+
+    #[inline(always)]
+    fn min_max(self, other: Self) -> (Self, Self) {
+        (self.simd_min(other), self.simd_max(other))
+    }
+
+    #[inline(always)]
+    fn exchange<const D: usize>(self, take_max: Self::Mask) -> Self {
+        let (min, max) = self.min_max(Xor::<D>::swizzle(self));
+        take_max.select(max, min)
+    }
+
+    #[inline(always)]
+    fn reverse(self) -> Self {
+        Simd::reverse(self)
+    }
+}
+
+/// Keys with values are ordered lexicographically, so the order of the lanes which are equal
+/// as pairs doesn't matter.
+impl<K, V, const N: usize> Lanes<N> for (Simd<K, N>, Simd<V, N>)
+where
+    LaneCount<N>: SupportedLaneCount,
+    K: SimdElement,
+    V: SimdElement<Mask = K::Mask>,
+    Simd<K, N>: SimdPartialOrd + SimdPartialEq<Mask = Mask<K::Mask, N>>,
+    Simd<V, N>: SimdPartialOrd + SimdPartialEq<Mask = Mask<K::Mask, N>>,
+{
+    type Mask = Mask<K::Mask, N>;
+
+    #[inline(always)]
+    fn mask(f: impl Fn(usize) -> bool) -> Self::Mask {
+        lane_mask(f)
+    }
+
+    #[inline(always)]
+    fn min_max(self, other: Self) -> (Self, Self) {
+        let other_less = pairs_lt(other, self);
+        (
+            (
+                other_less.select(other.0, self.0),
+                other_less.select(other.1, self.1),
+            ),
+            (
+                other_less.select(self.0, other.0),
+                other_less.select(self.1, other.1),
+            ),
+        )
+    }
+
+    #[inline(always)]
+    fn exchange<const D: usize>(self, take_max: Self::Mask) -> Self {
+        let partner = (Xor::<D>::swizzle(self.0), Xor::<D>::swizzle(self.1));
+        let take_partner =
+            (take_max & pairs_lt(self, partner)) | (!take_max & pairs_lt(partner, self));
+        (
+            take_partner.select(partner.0, self.0),
+            take_partner.select(partner.1, self.1),
+        )
+    }
+
+    #[inline(always)]
+    fn reverse(self) -> Self {
+        (self.0.reverse(), self.1.reverse())
+    }
+}
+
+#[inline(always)]
+fn pairs_lt<K, V, const N: usize>(
+    a: (Simd<K, N>, Simd<V, N>),
+    b: (Simd<K, N>, Simd<V, N>),
+) -> Mask<K::Mask, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    K: SimdElement,
+    V: SimdElement<Mask = K::Mask>,
+    Simd<K, N>: SimdPartialOrd + SimdPartialEq<Mask = Mask<K::Mask, N>>,
+    Simd<V, N>: SimdPartialOrd + SimdPartialEq<Mask = Mask<K::Mask, N>>,
+{
+    a.0.simd_lt(b.0) | (a.0.simd_eq(b.0) & a.1.simd_lt(b.1))
+}
+
+/// Compares lanes at distance `D` if it is a stage of merging bitonic blocks of `block` lanes.
+#[inline(always)]
+fn stage<L: Lanes<N>, const D: usize, const N: usize>(lanes: L, block: usize) -> L
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    if D >= N || D >= block {
+        return lanes;
+    }
+    // blocks are sorted ascending and descending in turn, `block >= N` sorts all lanes ascending
+    lanes.exchange::<D>(L::mask(|i| ((i & D) != 0) != ((i & block) != 0)))
+}
+
+/// Sorts bitonic blocks of `block` lanes, the even ones ascending and the odd ones descending.
+#[inline(always)]
+fn merge_lanes<L: Lanes<N>, const N: usize>(lanes: L, block: usize) -> L
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let lanes = stage::<L, 32, N>(lanes, block);
+    let lanes = stage::<L, 16, N>(lanes, block);
+    let lanes = stage::<L, 8, N>(lanes, block);
+    let lanes = stage::<L, 4, N>(lanes, block);
+    let lanes = stage::<L, 2, N>(lanes, block);
+    stage::<L, 1, N>(lanes, block)
+}
+
+/// Sorts the lanes ascending.
+#[inline]
+pub(crate) fn sort_lanes<L: Lanes<N>, const N: usize>(mut lanes: L) -> L
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let mut block = 2;
+    while block <= N {
+        lanes = merge_lanes(lanes, block);
+        block *= 2;
+    }
+    lanes
+}
+
+/// Sorts the items of `vectors` ascending. The number of vectors must be a power of two.
+#[inline]
+pub(crate) fn sort_vectors<L: Lanes<N>, const N: usize>(vectors: &mut [L])
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    debug_assert!(vectors.len().is_power_of_two());
+    for lanes in vectors.iter_mut() {
+        *lanes = sort_lanes(*lanes);
+    }
+    let mut run = 1;
+    while run < vectors.len() {
+        for runs in vectors.chunks_exact_mut(2 * run) {
+            merge_runs(runs);
+        }
+        run *= 2;
+    }
+}
+
+/// Merges the sorted halves of `runs`.
+#[inline]
+fn merge_runs<L: Lanes<N>, const N: usize>(runs: &mut [L])
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // the first half followed by the reversed second one is bitonic
+    let (low, high) = runs.split_at_mut(runs.len() / 2);
+    high.reverse();
+    for (low, high) in low.iter_mut().zip(high) {
+        (*low, *high) = low.min_max(high.reverse());
+    }
+    // now every item of the first half is less than every item of the second one, and both are
+    // bitonic
+    for half in runs.chunks_exact_mut(runs.len() / 2) {
+        merge_vectors(half);
+    }
+}
+
+/// Sorts a bitonic sequence of vectors.
+#[inline]
+fn merge_vectors<L: Lanes<N>, const N: usize>(vectors: &mut [L])
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let mut distance = vectors.len() / 2;
+    while distance > 0 {
+        for block in vectors.chunks_exact_mut(2 * distance) {
+            let (low, high) = block.split_at_mut(distance);
+            for (low, high) in low.iter_mut().zip(high) {
+                (*low, *high) = low.min_max(*high);
+            }
+        }
+        distance /= 2;
+    }
+    for lanes in vectors.iter_mut() {
+        *lanes = merge_lanes(*lanes, N);
+    }
+}
@@ -0,0 +1,381 @@
+//! Quicksort partitioning slices by compressing vectors to both ends, with small partitions sorted
+//! by the networks.
+
+use super::network::{sort_lanes, sort_vectors, Lanes};
+use crate::algo::{compress, load_padded, store};
+use crate::simd::{cmp::SimdPartialOrd, LaneCount, Mask, Simd, SimdElement, SupportedLaneCount};
+
+/// Maximum number of vectors sorted by a network at once.
+const MAX_BLOCK_VECTORS: usize = 8;
+
+/// Slice of items being sorted: keys, or keys with values in a separate slice of the same length.
+pub(crate) trait Items<K, const N: usize>: Sized
+where
+    K: SimdElement + Ord,
+    LaneCount<N>: SupportedLaneCount,
+{
+    type Lanes: Lanes<N>;
+
+    fn len(&self) -> usize;
+
+    fn key(&self, i: usize) -> K;
+
+    fn swap(&mut self, i: usize, j: usize);
+
+    fn split_at(self, mid: usize) -> (Self, Self);
+
+    /// Items `start..start + N`, lanes past the end are filled with the greatest items.
+    fn load_padded(&self, start: usize) -> Self::Lanes;
+
+    /// Writes the lanes to items `start..start + N`, ignoring the ones past the end.
+    fn store(&mut self, start: usize, lanes: Self::Lanes);
+
+    /// Keys of the lanes.
+    fn keys(lanes: Self::Lanes) -> Simd<K, N>;
+
+    /// Writes the first `count` lanes to the ends of items `ends.0..ends.1`, the ones selected by
+    /// the bits of `selected` to the left end and the others to the right end, and moves the ends.
+    fn store_split(
+        &mut self,
+        ends: &mut (usize, usize),
+        lanes: Self::Lanes,
+        selected: u64,
+        count: usize,
+    );
+}
+
+/// Greatest values of the key types, used to pad incomplete vectors.
+pub(crate) trait Max {
+    const MAX: Self;
+}
+
+macro_rules! impl_max {
+    ($( $type: ty ),*) => {
+        $(
+        impl Max for $type {
+            const MAX: Self = <$type>::MAX;
+        }
+        )*
+    };
+}
+
+impl_max!(i32, u32, i64, u64);
+
+impl<K, const N: usize> Items<K, N> for &mut [K]
+where
+    K: SimdElement + Ord + Max,
+    LaneCount<N>: SupportedLaneCount,
+    Simd<K, N>: Lanes<N>,
+{
+    type Lanes = Simd<K, N>;
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        <[K]>::len(self)
+    }
+
+    #[inline(always)]
+    fn key(&self, i: usize) -> K {
+        self[i]
+    }
+
+    #[inline(always)]
+    fn swap(&mut self, i: usize, j: usize) {
+        <[K]>::swap(self, i, j);
+    }
+
+    #[inline(always)]
+    fn split_at(self, mid: usize) -> (Self, Self) {
+        self.split_at_mut(mid)
+    }
+
+    #[inline(always)]
+    fn load_padded(&self, start: usize) -> Simd<K, N> {
+        load_padded(self, start, K::MAX)
+    }
+
+    #[inline(always)]
+    fn store(&mut self, start: usize, lanes: Simd<K, N>) {
+        store(self, start, lanes);
+    }
+
+    #[inline(always)]
+    fn keys(lanes: Simd<K, N>) -> Simd<K, N> {
+        lanes
+    }
+
+    #[inline(always)]
+    fn store_split(
+        &mut self,
+        ends: &mut (usize, usize),
+        lanes: Simd<K, N>,
+        selected: u64,
+        count: usize,
+    ) {
+        store_split(self, ends, lanes, selected, count);
+    }
+}
+
+impl<'a, K, V, const N: usize> Items<K, N> for (&'a mut [K], &'a mut [V])
+where
+    K: SimdElement + Ord + Max,
+    V: SimdElement + Max,
+    LaneCount<N>: SupportedLaneCount,
+    (Simd<K, N>, Simd<V, N>): Lanes<N>,
+{
+    type Lanes = (Simd<K, N>, Simd<V, N>);
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline(always)]
+    fn key(&self, i: usize) -> K {
+        self.0[i]
+    }
+
+    #[inline(always)]
+    fn swap(&mut self, i: usize, j: usize) {
+        self.0.swap(i, j);
+        self.1.swap(i, j);
+    }
+
+    #[inline(always)]
+    fn split_at(self, mid: usize) -> (Self, Self) {
+        let (keys_low, keys_high) = self.0.split_at_mut(mid);
+        let (values_low, values_high) = self.1.split_at_mut(mid);
+        ((keys_low, values_low), (keys_high, values_high))
+    }
+
+    #[inline(always)]
+    fn load_padded(&self, start: usize) -> Self::Lanes {
+        // pairs are compared lexicographically, so padding is greater than or equal to any item
+        (
+            load_padded(self.0, start, K::MAX),
+            load_padded(self.1, start, V::MAX),
+        )
+    }
+
+    #[inline(always)]
+    fn store(&mut self, start: usize, lanes: Self::Lanes) {
+        store(self.0, start, lanes.0);
+        store(self.1, start, lanes.1);
+    }
+
+    #[inline(always)]
+    fn keys(lanes: Self::Lanes) -> Simd<K, N> {
+        lanes.0
+    }
+
+    #[inline(always)]
+    fn store_split(
+        &mut self,
+        ends: &mut (usize, usize),
+        lanes: Self::Lanes,
+        selected: u64,
+        count: usize,
+    ) {
+        store_split(self.1, &mut ends.clone(), lanes.1, selected, count);
+        store_split(self.0, ends, lanes.0, selected, count);
+    }
+}
+
+/// [`Items::store_split`] for either keys or values.
+#[inline(always)]
+fn store_split<T, const N: usize>(
+    items: &mut [T],
+    (left, right): &mut (usize, usize),
+    lanes: Simd<T, N>,
+    selected: u64,
+    count: usize,
+) where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let valid = u64::MAX >> (64 - N) >> (N - count);
+    if compress::is_vectorized::<T, N>() {
+        *left += compress::compress_store(lanes, Mask::from_bitmask(selected), &mut items[*left..]);
+        *right -= (!selected & valid).count_ones() as usize;
+        compress::compress_store(
+            lanes,
+            Mask::from_bitmask(!selected & valid),
+            &mut items[*right..],
+        );
+    } else {
+        // every lane is written to one of the ends, which is branchless
+        for (i, &lane) in lanes.as_array()[..count].iter().enumerate() {
+            let is_selected = (selected >> i) & 1 != 0;
+            items[if is_selected { *left } else { *right - 1 }] = lane;
+            *left += is_selected as usize;
+            *right -= !is_selected as usize;
+        }
+    }
+}
+
+/// Sorts `items` ascending by keys.
+pub(crate) fn sort<K, I, const N: usize>(items: I)
+where
+    K: SimdElement + Ord,
+    I: Items<K, N>,
+    LaneCount<N>: SupportedLaneCount,
+    Simd<K, N>: Lanes<N> + SimdPartialOrd<Mask = Mask<K::Mask, N>>,
+{
+    // fall back to heapsort after too many unbalanced partitions, like introsort
+    let depth_limit = 2 * (usize::BITS - items.len().leading_zeros());
+    quicksort(items, depth_limit);
+}
+
+fn quicksort<K, I, const N: usize>(mut items: I, mut depth_limit: u32)
+where
+    K: SimdElement + Ord,
+    I: Items<K, N>,
+    LaneCount<N>: SupportedLaneCount,
+    Simd<K, N>: Lanes<N> + SimdPartialOrd<Mask = Mask<K::Mask, N>>,
+{
+    loop {
+        let len = items.len();
+        if len <= MAX_BLOCK_VECTORS * N {
+            sort_block(items);
+            return;
+        }
+        if depth_limit == 0 {
+            heapsort(items);
+            return;
+        }
+        depth_limit -= 1;
+
+        let pivot = choose_pivot(&items);
+        let mut mid = partition(&mut items, |keys| keys.simd_lt(Simd::splat(pivot)));
+        if mid == 0 {
+            // the pivot is the least key, so the items equal to it are already in place
+            mid = partition(&mut items, |keys| keys.simd_le(Simd::splat(pivot)));
+            items = items.split_at(mid).1;
+            continue;
+        }
+        // the pivot itself is in the second part, so both parts are shorter than `items`
+        let (low, high) = items.split_at(mid);
+        if low.len() < high.len() {
+            quicksort(low, depth_limit);
+            items = high;
+        } else {
+            quicksort(high, depth_limit);
+            items = low;
+        }
+    }
+}
+
+/// Sorts up to `MAX_BLOCK_VECTORS` vectors of items by a network.
+fn sort_block<K, I, const N: usize>(mut items: I)
+where
+    K: SimdElement + Ord,
+    I: Items<K, N>,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let len = items.len();
+    if len <= 1 {
+        return;
+    }
+    let count = len.div_ceil(N).next_power_of_two();
+    let mut vectors = [items.load_padded(0); MAX_BLOCK_VECTORS];
+    for (i, lanes) in vectors[..count].iter_mut().enumerate().skip(1) {
+        *lanes = items.load_padded(i * N);
+    }
+    sort_vectors(&mut vectors[..count]);
+    for (i, lanes) in vectors[..count].iter().enumerate() {
+        if i * N < len {
+            items.store(i * N, *lanes);
+        }
+    }
+}
+
+/// Median of `N` keys sampled evenly from `items`, which must have at least `N` items.
+fn choose_pivot<K, I, const N: usize>(items: &I) -> K
+where
+    K: SimdElement + Ord,
+    I: Items<K, N>,
+    LaneCount<N>: SupportedLaneCount,
+    Simd<K, N>: Lanes<N>,
+{
+    let step = items.len() / N;
+    let sample = Simd::from_array(core::array::from_fn(|i| items.key(i * step + step / 2)));
+    sort_lanes(sample)[N / 2]
+}
+
+/// Moves the items whose keys satisfy `predicate` to the beginning of `items` and returns their
+/// number. `items` must have at least two vectors of items.
+#[inline]
+fn partition<K, I, const N: usize>(
+    items: &mut I,
+    predicate: impl Fn(Simd<K, N>) -> Mask<K::Mask, N>,
+) -> usize
+where
+    K: SimdElement + Ord,
+    I: Items<K, N>,
+    LaneCount<N>: SupportedLaneCount,
+{
+    // The items satisfying the predicate are compressed to the left end and the others to the
+    // right end. The first and the last vectors are set aside, which leaves room for a vector at
+    // both ends, and the other vectors are read from the end with less room, so that both ends
+    // have room for the vector being written.
+    let len = items.len();
+    debug_assert!(len >= 2 * N);
+    let (first, last) = (items.load_padded(0), items.load_padded(len - N));
+    let (mut read_left, mut read_right) = (N, len - N);
+    // the ends written so far, the first `count` lanes of `lanes` are written to them
+    let mut ends = (0, len);
+    let store = |items: &mut I, ends: &mut (usize, usize), lanes, count: usize| {
+        let valid = u64::MAX >> (64 - N) >> (N - count);
+        let selected = predicate(I::keys(lanes)).to_bitmask() & valid;
+        items.store_split(ends, lanes, selected, count);
+    };
+    while read_right - read_left >= N {
+        let lanes = if read_left - ends.0 <= ends.1 - read_right {
+            read_left += N;
+            items.load_padded(read_left - N)
+        } else {
+            read_right -= N;
+            items.load_padded(read_right)
+        };
+        store(items, &mut ends, lanes, N);
+    }
+    // the rest of the items are fewer than a vector, once they are read, all the room between the
+    // ends is free
+    let rest = items.load_padded(read_left);
+    store(items, &mut ends, rest, read_right - read_left);
+    store(items, &mut ends, first, N);
+    store(items, &mut ends, last, N);
+    ends.0
+}
+
+/// Scalar heapsort by keys, which is `O(n log n)` in the worst case.
+fn heapsort<K, I, const N: usize>(mut items: I)
+where
+    K: SimdElement + Ord,
+    I: Items<K, N>,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let sift_down = |items: &mut I, mut node: usize, end: usize| loop {
+        let mut child = 2 * node + 1;
+        if child >= end {
+            return;
+        }
+        if child + 1 < end && items.key(child) < items.key(child + 1) {
+            child += 1;
+        }
+        if items.key(node) >= items.key(child) {
+            return;
+        }
+        items.swap(node, child);
+        node = child;
+    };
+
+    let len = items.len();
+    for node in (0..len / 2).rev() {
+        sift_down(&mut items, node, len);
+    }
+    for end in (1..len).rev() {
+        items.swap(0, end);
+        sift_down(&mut items, 0, end);
+    }
+}
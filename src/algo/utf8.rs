@@ -0,0 +1,288 @@
+//! Validation of UTF-8 and operations on ASCII text.
+//!
+//! UTF-8 is validated by the lookup algorithm of Keiser and Lemire ("Validating UTF-8 In Less
+//! Than One Instruction Per Byte", 2021). Every byte is checked together with the three bytes
+//! preceding it: the high and the low nibbles of the previous byte and the high nibble of the
+//! byte itself are looked up in tables of 16 entries, whose bits flag the kinds of errors which
+//! are possible for the nibble. A pair of bytes is invalid if some kind of error is flagged by all
+//! three lookups, the third and the fourth bytes of sequences are checked by comparing the bytes
+//! two and three positions back with the leading bytes of longer sequences.
+//!
+//! # Examples
+//! ```
+//! use simd_addons::algo::utf8;
+//!
+//! assert_eq!(utf8::from_utf8("héllo wörld".as_bytes()), Ok("héllo wörld"));
+//! assert_eq!(utf8::from_utf8(b"caf\xe9"), core::str::from_utf8(b"caf\xe9"));
+//! assert_eq!(utf8::count_chars("héllo wörld"), 11);
+//!
+//! let mut text = *b"Hello, World!";
+//! utf8::make_ascii_uppercase(&mut text);
+//! assert_eq!(&text, b"HELLO, WORLD!");
+//! ```
+
+use core::str::Utf8Error;
+
+use crate::{
+    algo::{load_padded, store},
+    simd::{
+        cmp::{SimdPartialEq, SimdPartialOrd},
+        LaneCount, Simd, SupportedLaneCount, Swizzle,
+    },
+};
+
+/// Number of bytes processed at once.
+const LANES: usize = 32;
+
+type Bytes = Simd<u8, LANES>;
+
+// Kinds of errors flagged by the lookup tables.
+/// A leading byte isn't followed by a continuation byte.
+const TOO_SHORT: u8 = 1 << 0;
+/// A continuation byte follows an ASCII byte.
+const TOO_LONG: u8 = 1 << 1;
+/// A three-byte sequence encodes a code point below U+0800.
+const OVERLONG_3: u8 = 1 << 2;
+/// A four-byte sequence encodes a code point above U+10FFFF, with the second byte 0x90 or
+/// greater.
+const TOO_LARGE: u8 = 1 << 3;
+/// A three-byte sequence encodes a surrogate, U+D800 to U+DFFF.
+const SURROGATE: u8 = 1 << 4;
+/// A two-byte sequence encodes a code point below U+0080.
+const OVERLONG_2: u8 = 1 << 5;
+/// A four-byte sequence encodes a code point above U+10FFFF, with the leading byte 0xF5 or
+/// greater and the second byte below 0x90. Shares the bit with `OVERLONG_4`, since their leading
+/// bytes differ.
+const TOO_LARGE_1000: u8 = 1 << 6;
+/// A four-byte sequence encodes a code point below U+10000.
+const OVERLONG_4: u8 = 1 << 6;
+/// A continuation byte follows a continuation byte, which is an error unless the byte is the
+/// third or the fourth byte of a sequence.
+const TWO_CONTS: u8 = 1 << 7;
+/// Errors which depend on the high nibble of the previous byte only.
+const CARRY: u8 = TOO_SHORT | TOO_LONG | TWO_CONTS;
+
+/// Repeats a table of 16 entries to fill a vector.
+const fn table(entries: [u8; 16]) -> [u8; LANES] {
+    let mut table = [0; LANES];
+    let mut i = 0;
+    while i < LANES {
+        table[i] = entries[i % 16];
+        i += 1;
+    }
+    table
+}
+
+/// Errors possible after a byte with the high nibble given by the index.
+const BYTE_1_HIGH: [u8; LANES] = table([
+    // 0_______: ASCII
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    // 10______: continuation
+    TWO_CONTS,
+    TWO_CONTS,
+    TWO_CONTS,
+    TWO_CONTS,
+    // 1100____: leading byte of two
+    TOO_SHORT | OVERLONG_2,
+    // 1101____: leading byte of two
+    TOO_SHORT,
+    // 1110____: leading byte of three
+    TOO_SHORT | OVERLONG_3 | SURROGATE,
+    // 1111____: leading byte of four
+    TOO_SHORT | TOO_LARGE | TOO_LARGE_1000 | OVERLONG_4,
+]);
+
+/// Errors possible after a byte with the low nibble given by the index.
+const BYTE_1_LOW: [u8; LANES] = table([
+    // ____0000
+    CARRY | OVERLONG_3 | OVERLONG_2 | OVERLONG_4,
+    // ____0001
+    CARRY | OVERLONG_2,
+    // ____001_
+    CARRY,
+    CARRY,
+    // ____0100
+    CARRY | TOO_LARGE,
+    // ____0101 to ____1100
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    // ____1101
+    CARRY | TOO_LARGE | TOO_LARGE_1000 | SURROGATE,
+    // ____111_
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+]);
+
+/// Errors possible for a byte with the high nibble given by the index.
+const BYTE_2_HIGH: [u8; LANES] = table([
+    // 0_______: ASCII
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    // 1000____
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | OVERLONG_3 | TOO_LARGE_1000 | OVERLONG_4,
+    // 1001____
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | OVERLONG_3 | TOO_LARGE,
+    // 101_____
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | SURROGATE | TOO_LARGE,
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | SURROGATE | TOO_LARGE,
+    // 11______: leading byte
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+]);
+
+/// Selects the lanes of the concatenation of two vectors shifted to the right by `D`.
+struct Previous<const D: usize>;
+
+const fn previous_index<const N: usize>(distance: usize) -> [usize; N] {
+    let mut index = [0; N];
+    let mut i = 0;
+    while i < N {
+        index[i] = N - distance + i;
+        i += 1;
+    }
+    index
+}
+
+impl<const D: usize, const N: usize> Swizzle<N> for Previous<D> {
+    const INDEX: [usize; N] = previous_index::<N>(D);
+}
+
+/// Bytes at distance `D` back from the bytes of `input`, which follows `previous`.
+#[inline(always)]
+fn previous<const D: usize, const N: usize>(
+    previous: Simd<u8, N>,
+    input: Simd<u8, N>,
+) -> Simd<u8, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    Previous::<D>::concat_swizzle(previous, input)
+}
+
+#[inline(always)]
+fn lookup(table: [u8; LANES], nibbles: Bytes) -> Bytes {
+    Simd::from_array(table).swizzle_dyn(nibbles)
+}
+
+/// Non-zero lanes mark the bytes of `input` which make the text invalid, given that `input`
+/// follows `previous`.
+#[inline(always)]
+fn errors(previous_input: Bytes, input: Bytes) -> Bytes {
+    let prev1 = previous::<1, LANES>(previous_input, input);
+    let special = lookup(BYTE_1_HIGH, prev1 >> 4)
+        & lookup(BYTE_1_LOW, prev1 & Simd::splat(0x0f))
+        & lookup(BYTE_2_HIGH, input >> 4);
+    // the third and the fourth bytes of sequences must be continuations following continuations,
+    // which are flagged as `TWO_CONTS` everywhere else
+    let prev2 = previous::<2, LANES>(previous_input, input);
+    let prev3 = previous::<3, LANES>(previous_input, input);
+    let must_continue = prev2.simd_ge(Simd::splat(0xe0)) | prev3.simd_ge(Simd::splat(0xf0));
+    special ^ must_continue.select(Simd::splat(TWO_CONTS), Simd::splat(0))
+}
+
+/// Returns `true` if `bytes` are valid UTF-8.
+#[inline]
+pub fn is_utf8(bytes: &[u8]) -> bool {
+    let mut previous_input = Bytes::splat(0);
+    let mut error = Bytes::splat(0);
+    for start in (0..bytes.len()).step_by(LANES) {
+        let input = load_padded(bytes, start, 0);
+        // ASCII following ASCII is always valid
+        if !(input | previous_input).simd_lt(Simd::splat(0x80)).all() {
+            error |= errors(previous_input, input);
+        }
+        previous_input = input;
+    }
+    // the text must not end in the middle of a sequence, i.e. it may be followed by ASCII
+    error |= errors(previous_input, Simd::splat(0));
+    error.simd_eq(Simd::splat(0)).all()
+}
+
+/// Converts bytes to a string slice like [`core::str::from_utf8`], with the same result.
+///
+/// # Errors
+/// Returns the same error as [`core::str::from_utf8`] if `bytes` aren't valid UTF-8. Invalid text
+/// is assumed to be rare, so the error is located by [`core::str::from_utf8`] itself once the
+/// text is found to be invalid.
+#[inline]
+pub fn from_utf8(bytes: &[u8]) -> Result<&str, Utf8Error> {
+    if is_utf8(bytes) {
+        // SAFETY: the bytes are valid UTF-8.
+        Ok(unsafe { core::str::from_utf8_unchecked(bytes) })
+    } else {
+        core::str::from_utf8(bytes)
+    }
+}
+
+/// Returns `true` if all bytes are ASCII.
+#[inline]
+pub fn is_ascii(bytes: &[u8]) -> bool {
+    (0..bytes.len()).step_by(LANES).all(|start| {
+        let input: Bytes = load_padded(bytes, start, 0);
+        input.simd_lt(Simd::splat(0x80)).all()
+    })
+}
+
+/// Flips the case of the ASCII letters starting from `first`, which is `b'a'` or `b'A'`.
+#[inline(always)]
+fn flip_ascii_case(bytes: &mut [u8], first: u8) {
+    for start in (0..bytes.len()).step_by(LANES) {
+        let input: Bytes = load_padded(bytes, start, 0);
+        let letters = (input - Simd::splat(first)).simd_lt(Simd::splat(26));
+        store(
+            bytes,
+            start,
+            letters.select(input ^ Simd::splat(0x20), input),
+        );
+    }
+}
+
+/// Converts ASCII letters to upper case like [`slice::make_ascii_uppercase`], other bytes
+/// (including all bytes of multibyte UTF-8 sequences) are left intact.
+#[inline]
+pub fn make_ascii_uppercase(bytes: &mut [u8]) {
+    flip_ascii_case(bytes, b'a');
+}
+
+/// Converts ASCII letters to lower case like [`slice::make_ascii_lowercase`], other bytes
+/// (including all bytes of multibyte UTF-8 sequences) are left intact.
+#[inline]
+pub fn make_ascii_lowercase(bytes: &mut [u8]) {
+    flip_ascii_case(bytes, b'A');
+}
+
+/// Returns the number of characters of `text`, the same as `text.chars().count()`.
+#[inline]
+pub fn count_chars(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    (0..bytes.len())
+        .step_by(LANES)
+        .map(|start| {
+            // every character has a single byte which isn't a continuation, padding is one
+            let input: Bytes = load_padded(bytes, start, 0x80);
+            let leading = (input & Simd::splat(0xc0)).simd_ne(Simd::splat(0x80));
+            leading.to_bitmask().count_ones() as usize
+        })
+        .sum()
+}
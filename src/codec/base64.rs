@@ -0,0 +1,384 @@
+//! Base64 encoding of RFC 4648 with the standard and the URL-safe alphabets.
+//!
+//! Blocks of 24 bytes are encoded to vectors of 32 symbols: the bytes of every group of three are
+//! spread over the lanes of the four indices they encode by swizzles, the 6-bit indices are cut
+//! out of them by shifts and masks and turned into symbols by adding the offset of the range of
+//! the alphabet they fall into, which is chosen by comparisons. Decoding reverses these steps,
+//! a vector with any byte out of the alphabet is handed over to the scalar code locating the
+//! error.
+//!
+//! # Examples
+//! ```
+//! use simd_addons::codec::{base64::{self, Config}, DecodeError};
+//!
+//! let mut text = [0; 8];
+//! let len = base64::encode(b"hello", &mut text, Config::STANDARD);
+//! assert_eq!(&text[..len], b"aGVsbG8=");
+//!
+//! let mut data = [0; 5];
+//! assert_eq!(base64::decode(b"aGVsbG8=", &mut data, Config::STANDARD), Ok(5));
+//! assert_eq!(&data, b"hello");
+//! assert_eq!(
+//!     base64::decode(b"aGVs*G8", &mut data, Config::STANDARD_NO_PAD),
+//!     Err(DecodeError::InvalidByte { offset: 4, byte: b'*' })
+//! );
+//! ```
+
+use crate::{
+    algo::load_padded,
+    codec::DecodeError,
+    simd::{
+        cmp::{SimdPartialEq, SimdPartialOrd},
+        Simd, Swizzle,
+    },
+};
+
+/// Number of symbols processed at once.
+const LANES: usize = 32;
+
+/// Number of bytes encoded by a vector of symbols.
+const CHUNK: usize = LANES / 4 * 3;
+
+type Bytes = Simd<u8, LANES>;
+
+/// Symbols of the 6-bit indices.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Alphabet {
+    /// `A`–`Z`, `a`–`z`, `0`–`9`, `+` and `/`.
+    #[default]
+    Standard,
+    /// `A`–`Z`, `a`–`z`, `0`–`9`, `-` and `_`, which are safe in URLs and file names.
+    UrlSafe,
+}
+
+impl Alphabet {
+    /// Symbols of the indices 62 and 63, the others are the same in both alphabets.
+    #[inline(always)]
+    const fn last_symbols(self) -> [u8; 2] {
+        match self {
+            Alphabet::Standard => *b"+/",
+            Alphabet::UrlSafe => *b"-_",
+        }
+    }
+
+    #[inline(always)]
+    fn symbol(self, index: u8) -> u8 {
+        match index {
+            0..=25 => b'A' + index,
+            26..=51 => b'a' + (index - 26),
+            52..=61 => b'0' + (index - 52),
+            _ => self.last_symbols()[index as usize - 62],
+        }
+    }
+
+    #[inline(always)]
+    fn index(self, symbol: u8) -> Option<u8> {
+        let [symbol_62, symbol_63] = self.last_symbols();
+        match symbol {
+            b'A'..=b'Z' => Some(symbol - b'A'),
+            b'a'..=b'z' => Some(symbol - b'a' + 26),
+            b'0'..=b'9' => Some(symbol - b'0' + 52),
+            _ if symbol == symbol_62 => Some(62),
+            _ if symbol == symbol_63 => Some(63),
+            _ => None,
+        }
+    }
+}
+
+/// Padding of the encoded text with `=` to a multiple of 4 bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Padding {
+    /// The text is padded, decoding rejects the text without padding.
+    #[default]
+    Required,
+    /// The text is padded, decoding accepts the text with and without padding.
+    Optional,
+    /// The text isn't padded, decoding rejects the text with padding.
+    Omitted,
+}
+
+/// Alphabet and padding of the encoding.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    pub alphabet: Alphabet,
+    pub padding: Padding,
+}
+
+impl Config {
+    /// The standard alphabet with padding, section 4 of RFC 4648.
+    pub const STANDARD: Config = Config {
+        alphabet: Alphabet::Standard,
+        padding: Padding::Required,
+    };
+
+    /// The standard alphabet without padding.
+    pub const STANDARD_NO_PAD: Config = Config {
+        alphabet: Alphabet::Standard,
+        padding: Padding::Omitted,
+    };
+
+    /// The URL-safe alphabet with padding, section 5 of RFC 4648.
+    pub const URL_SAFE: Config = Config {
+        alphabet: Alphabet::UrlSafe,
+        padding: Padding::Required,
+    };
+
+    /// The URL-safe alphabet without padding.
+    pub const URL_SAFE_NO_PAD: Config = Config {
+        alphabet: Alphabet::UrlSafe,
+        padding: Padding::Omitted,
+    };
+}
+
+/// Repeats a pattern of per-lane constants to fill a vector.
+const fn repeat<const P: usize>(pattern: [u8; P]) -> [u8; LANES] {
+    let mut lanes = [0; LANES];
+    let mut i = 0;
+    while i < LANES {
+        lanes[i] = pattern[i % P];
+        i += 1;
+    }
+    lanes
+}
+
+/// Spreads every group of three bytes over four lanes of indices: the lane of an index gets the
+/// byte with its high bits for `HIGH`, and the byte with its low bits otherwise.
+struct SpreadGroups<const HIGH: bool>;
+
+const fn spread_index(high: bool) -> [usize; LANES] {
+    let pattern = if high { [0, 0, 1, 2] } else { [0, 1, 2, 2] };
+    let mut index = [0; LANES];
+    let mut i = 0;
+    while i < LANES {
+        index[i] = i / 4 * 3 + pattern[i % 4];
+        i += 1;
+    }
+    index
+}
+
+impl<const HIGH: bool> Swizzle<LANES> for SpreadGroups<HIGH> {
+    const INDEX: [usize; LANES] = spread_index(HIGH);
+}
+
+/// Gathers every group of four indices to three lanes of bytes: the lane of a byte gets the index
+/// with its high bits for `HIGH`, and the index with its low bits otherwise. The lanes past
+/// [`CHUNK`] are unused.
+struct GatherGroups<const HIGH: bool>;
+
+const fn gather_index(high: bool) -> [usize; LANES] {
+    let mut index = [0; LANES];
+    let mut i = 0;
+    while i < CHUNK {
+        index[i] = i / 3 * 4 + i % 3 + if high { 0 } else { 1 };
+        i += 1;
+    }
+    index
+}
+
+impl<const HIGH: bool> Swizzle<LANES> for GatherGroups<HIGH> {
+    const INDEX: [usize; LANES] = gather_index(HIGH);
+}
+
+/// Symbols of the 6-bit indices.
+#[inline(always)]
+fn encode_symbols(indices: Bytes, alphabet: Alphabet) -> Bytes {
+    let [symbol_62, symbol_63] = alphabet.last_symbols();
+    let offset = indices.simd_lt(Simd::splat(26)).select(
+        Simd::splat(b'A'),
+        indices.simd_lt(Simd::splat(52)).select(
+            Simd::splat(b'a'.wrapping_sub(26)),
+            indices.simd_lt(Simd::splat(62)).select(
+                Simd::splat(b'0'.wrapping_sub(52)),
+                indices.simd_eq(Simd::splat(62)).select(
+                    Simd::splat(symbol_62.wrapping_sub(62)),
+                    Simd::splat(symbol_63.wrapping_sub(63)),
+                ),
+            ),
+        ),
+    );
+    indices + offset
+}
+
+/// Indices of the symbols, `None` if any byte isn't a symbol of the alphabet.
+#[inline(always)]
+fn decode_symbols(symbols: Bytes, alphabet: Alphabet) -> Option<Bytes> {
+    let [symbol_62, symbol_63] = alphabet.last_symbols();
+    let upper = (symbols - Simd::splat(b'A')).simd_lt(Simd::splat(26));
+    let lower = (symbols - Simd::splat(b'a')).simd_lt(Simd::splat(26));
+    let digit = (symbols - Simd::splat(b'0')).simd_lt(Simd::splat(10));
+    let is_62 = symbols.simd_eq(Simd::splat(symbol_62));
+    let is_63 = symbols.simd_eq(Simd::splat(symbol_63));
+    if !(upper | lower | digit | is_62 | is_63).all() {
+        return None;
+    }
+    let offset = upper.select(
+        Simd::splat(0u8.wrapping_sub(b'A')),
+        lower.select(
+            Simd::splat(26u8.wrapping_sub(b'a')),
+            digit.select(
+                Simd::splat(52u8.wrapping_sub(b'0')),
+                is_62.select(
+                    Simd::splat(62u8.wrapping_sub(symbol_62)),
+                    Simd::splat(63u8.wrapping_sub(symbol_63)),
+                ),
+            ),
+        ),
+    );
+    Some(symbols + offset)
+}
+
+/// Returns the length of the text encoding `len` bytes.
+#[inline]
+pub fn encoded_len(len: usize, config: Config) -> usize {
+    match config.padding {
+        Padding::Omitted => len / 3 * 4 + [0, 2, 3][len % 3],
+        Padding::Required | Padding::Optional => len.div_ceil(3) * 4,
+    }
+}
+
+/// Encodes `input` to the beginning of `output` and returns the length of the text, which is
+/// [`encoded_len`].
+///
+/// # Panics
+/// Panics if `output` is shorter than the text.
+#[inline]
+pub fn encode(input: &[u8], output: &mut [u8], config: Config) -> usize {
+    let len = encoded_len(input.len(), config);
+    assert!(
+        output.len() >= len,
+        "output is shorter than the encoded data"
+    );
+    let alphabet = config.alphabet;
+    let high_shift = Simd::from_array(repeat([0, 4, 2, 0]));
+    let high_mask = Simd::from_array(repeat([0, 0x30, 0x3c, 0x3f]));
+    let low_shift = Simd::from_array(repeat([2, 4, 6, 0]));
+    let low_mask = Simd::from_array(repeat([0x3f, 0x0f, 0x03, 0]));
+
+    let mut start = 0;
+    while start + CHUNK <= input.len() {
+        let bytes: Bytes = load_padded(input, start, 0);
+        let high = SpreadGroups::<true>::swizzle(bytes);
+        let low = SpreadGroups::<false>::swizzle(bytes);
+        let indices = ((high << high_shift) & high_mask) | ((low >> low_shift) & low_mask);
+        let out = start / 3 * 4;
+        encode_symbols(indices, alphabet).copy_to_slice(&mut output[out..out + LANES]);
+        start += CHUNK;
+    }
+
+    let mut out = start / 3 * 4;
+    for group in input[start..].chunks(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| group.get(i).copied().unwrap_or(0));
+        let indices = [
+            a >> 2,
+            (a << 4 | b >> 4) & 0x3f,
+            (b << 2 | c >> 6) & 0x3f,
+            c & 0x3f,
+        ];
+        // a group of `n` bytes has `8 * n` bits, which are encoded by `n + 1` symbols
+        for &index in &indices[..group.len() + 1] {
+            output[out] = alphabet.symbol(index);
+            out += 1;
+        }
+    }
+    output[out..len].fill(b'=');
+    len
+}
+
+/// Number of `=` at the end of `input`, the padding of a group takes two of them at most.
+#[inline(always)]
+fn padding_len(input: &[u8]) -> usize {
+    input
+        .iter()
+        .rev()
+        .take(2)
+        .take_while(|&&byte| byte == b'=')
+        .count()
+}
+
+/// Returns the length of the data encoded by `input`, if it is valid.
+#[inline]
+pub fn decoded_len(input: &[u8]) -> usize {
+    let symbols = input.len() - padding_len(input);
+    symbols / 4 * 3 + symbols % 4 * 3 / 4
+}
+
+/// Decodes `input` to the beginning of `output` and returns the length of the data, which is
+/// [`decoded_len`].
+///
+/// # Errors
+/// Returns [`DecodeError::InvalidLength`] or [`DecodeError::InvalidPadding`] if the length or the
+/// padding of `input` isn't valid for the `config`, otherwise [`DecodeError::InvalidByte`] with
+/// the first byte which isn't a symbol of the alphabet, or [`DecodeError::InvalidLastSymbol`] if
+/// the last symbol encodes non-zero bits past the end of the data. The content of `output` is
+/// unspecified in this case.
+///
+/// # Panics
+/// Panics if `output` is shorter than the data.
+#[inline]
+pub fn decode(input: &[u8], output: &mut [u8], config: Config) -> Result<usize, DecodeError> {
+    let symbols = input.len() - padding_len(input);
+    let tail = symbols % 4;
+    if tail == 1 {
+        return Err(DecodeError::InvalidLength {
+            length: input.len(),
+        });
+    }
+    let padded = symbols < input.len();
+    match config.padding {
+        Padding::Omitted if padded => return Err(DecodeError::InvalidPadding { offset: symbols }),
+        _ if padded && tail + (input.len() - symbols) != 4 => {
+            return Err(DecodeError::InvalidPadding { offset: symbols })
+        }
+        Padding::Required if !padded && tail != 0 => {
+            return Err(DecodeError::InvalidPadding {
+                offset: input.len(),
+            })
+        }
+        _ => {}
+    }
+    let len = decoded_len(input);
+    assert!(
+        output.len() >= len,
+        "output is shorter than the decoded data"
+    );
+    let alphabet = config.alphabet;
+    let high_shift = Simd::from_array(repeat([2, 4, 6]));
+    let low_shift = Simd::from_array(repeat([4, 2, 0]));
+
+    let mut start = 0;
+    while start + LANES <= symbols {
+        let Some(indices) = decode_symbols(Simd::from_slice(&input[start..]), alphabet) else {
+            break;
+        };
+        let high = GatherGroups::<true>::swizzle(indices);
+        let low = GatherGroups::<false>::swizzle(indices);
+        let bytes = (high << high_shift) | (low >> low_shift);
+        let out = start / 4 * 3;
+        output[out..out + CHUNK].copy_from_slice(&bytes.as_array()[..CHUNK]);
+        start += LANES;
+    }
+
+    let mut out = start / 4 * 3;
+    for (offset, group) in (start..).step_by(4).zip(input[start..symbols].chunks(4)) {
+        let mut indices = [0; 4];
+        for (i, &symbol) in group.iter().enumerate() {
+            indices[i] = alphabet.index(symbol).ok_or(DecodeError::InvalidByte {
+                offset: offset + i,
+                byte: symbol,
+            })?;
+        }
+        let [a, b, c, d] = indices;
+        let bytes = [a << 2 | b >> 4, b << 4 | c >> 2, c << 6 | d];
+        let count = group.len() * 3 / 4;
+        // the bits of the last symbol past the data would be the next byte
+        if count < 3 && bytes[count] != 0 {
+            return Err(DecodeError::InvalidLastSymbol {
+                offset: offset + group.len() - 1,
+                byte: group[group.len() - 1],
+            });
+        }
+        output[out..out + count].copy_from_slice(&bytes[..count]);
+        out += count;
+    }
+    Ok(len)
+}
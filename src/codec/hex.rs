@@ -0,0 +1,195 @@
+//! Hexadecimal encoding, two digits per byte with the high nibble first.
+//!
+//! Blocks of 32 bytes are encoded by looking the digits of their nibbles up in a table by a
+//! dynamic swizzle and interleaving the digits of the high and the low nibbles. Decoding turns
+//! the digits of both cases into nibbles by comparisons and deinterleaves them, a vector with any
+//! byte which isn't a digit is handed over to the scalar code locating the error.
+//!
+//! # Examples
+//! ```
+//! use simd_addons::codec::{hex::{self, Case}, DecodeError};
+//!
+//! let mut text = [0; 8];
+//! hex::encode(&[0xde, 0xad, 0xbe, 0xef], &mut text, Case::Upper);
+//! assert_eq!(&text, b"DEADBEEF");
+//!
+//! let mut data = [0; 4];
+//! assert_eq!(hex::decode(b"DeadBeef", &mut data), Ok(4));
+//! assert_eq!(data, [0xde, 0xad, 0xbe, 0xef]);
+//! assert_eq!(
+//!     hex::decode(b"c0fXee", &mut data),
+//!     Err(DecodeError::InvalidByte { offset: 3, byte: b'X' })
+//! );
+//! ```
+
+use crate::{
+    codec::DecodeError,
+    simd::{cmp::SimdPartialOrd, Simd, Swizzle},
+};
+
+/// Number of bytes processed at once.
+const LANES: usize = 32;
+
+type Bytes = Simd<u8, LANES>;
+
+/// Case of the digits from `a` to `f`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Case {
+    #[default]
+    Lower,
+    Upper,
+}
+
+impl Case {
+    /// Digits of the nibbles, repeated to fill a vector.
+    #[inline(always)]
+    const fn digits(self) -> [u8; LANES] {
+        let digits = match self {
+            Case::Lower => b"0123456789abcdef",
+            Case::Upper => b"0123456789ABCDEF",
+        };
+        let mut table = [0; LANES];
+        let mut i = 0;
+        while i < LANES {
+            table[i] = digits[i % 16];
+            i += 1;
+        }
+        table
+    }
+}
+
+/// Interleaves the lanes of two vectors, `HALF` of them: lanes `0..LANES / 2` or the others.
+struct Interleave<const HALF: usize>;
+
+const fn interleave_index(half: usize) -> [usize; LANES] {
+    let mut index = [0; LANES];
+    let mut i = 0;
+    while i < LANES {
+        index[i] = half * LANES / 2 + i / 2 + i % 2 * LANES;
+        i += 1;
+    }
+    index
+}
+
+impl<const HALF: usize> Swizzle<LANES> for Interleave<HALF> {
+    const INDEX: [usize; LANES] = interleave_index(HALF);
+}
+
+/// Takes the even (`ODD` is 0) or the odd (`ODD` is 1) lanes of two vectors.
+struct Deinterleave<const ODD: usize>;
+
+const fn deinterleave_index(odd: usize) -> [usize; LANES] {
+    let mut index = [0; LANES];
+    let mut i = 0;
+    while i < LANES {
+        index[i] = 2 * i + odd;
+        i += 1;
+    }
+    index
+}
+
+impl<const ODD: usize> Swizzle<LANES> for Deinterleave<ODD> {
+    const INDEX: [usize; LANES] = deinterleave_index(ODD);
+}
+
+/// Nibbles of the digits, `None` if any byte isn't a digit.
+#[inline(always)]
+fn decode_digits(digits: Bytes) -> Option<Bytes> {
+    let decimal = digits - Simd::splat(b'0');
+    // letters of both cases are lower case with the 0x20 bit set
+    let letter = (digits | Simd::splat(0x20)) - Simd::splat(b'a');
+    let is_decimal = decimal.simd_lt(Simd::splat(10));
+    let is_letter = letter.simd_lt(Simd::splat(6));
+    (is_decimal | is_letter)
+        .all()
+        .then(|| is_decimal.select(decimal, letter + Simd::splat(10)))
+}
+
+#[inline(always)]
+fn decode_digit(input: &[u8], offset: usize) -> Result<u8, DecodeError> {
+    let byte = input[offset];
+    match byte {
+        b'0'..=b'9' => Ok(byte - b'0'),
+        b'a'..=b'f' => Ok(byte - b'a' + 10),
+        b'A'..=b'F' => Ok(byte - b'A' + 10),
+        _ => Err(DecodeError::InvalidByte { offset, byte }),
+    }
+}
+
+/// Encodes `input` to the beginning of `output` and returns the length of the text, which is
+/// twice the length of `input`.
+///
+/// # Panics
+/// Panics if `output` is shorter than the text.
+#[inline]
+pub fn encode(input: &[u8], output: &mut [u8], case: Case) -> usize {
+    let len = 2 * input.len();
+    assert!(
+        output.len() >= len,
+        "output is shorter than the encoded data"
+    );
+    let digits = Simd::from_array(case.digits());
+
+    let mut start = 0;
+    while start + LANES <= input.len() {
+        let bytes: Bytes = Simd::from_slice(&input[start..]);
+        let high = digits.swizzle_dyn(bytes >> 4);
+        let low = digits.swizzle_dyn(bytes & Simd::splat(0x0f));
+        let out = 2 * start;
+        Interleave::<0>::concat_swizzle(high, low).copy_to_slice(&mut output[out..out + LANES]);
+        Interleave::<1>::concat_swizzle(high, low)
+            .copy_to_slice(&mut output[out + LANES..out + 2 * LANES]);
+        start += LANES;
+    }
+
+    let digits = digits.as_array();
+    for (i, &byte) in input.iter().enumerate().skip(start) {
+        output[2 * i] = digits[(byte >> 4) as usize];
+        output[2 * i + 1] = digits[(byte & 0x0f) as usize];
+    }
+    len
+}
+
+/// Decodes `input`, which may have digits of both cases, to the beginning of `output` and returns
+/// the length of the data, which is half the length of `input`.
+///
+/// # Errors
+/// Returns [`DecodeError::InvalidLength`] if the length of `input` is odd, otherwise
+/// [`DecodeError::InvalidByte`] with the first byte which isn't a digit. The content of `output`
+/// is unspecified in this case.
+///
+/// # Panics
+/// Panics if `output` is shorter than the data.
+#[inline]
+pub fn decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+    if input.len() % 2 == 1 {
+        return Err(DecodeError::InvalidLength {
+            length: input.len(),
+        });
+    }
+    let len = input.len() / 2;
+    assert!(
+        output.len() >= len,
+        "output is shorter than the decoded data"
+    );
+
+    let mut start = 0;
+    while start + LANES <= len {
+        let digits = &input[2 * start..];
+        let (Some(first), Some(second)) = (
+            decode_digits(Simd::from_slice(digits)),
+            decode_digits(Simd::from_slice(&digits[LANES..])),
+        ) else {
+            break;
+        };
+        let high = Deinterleave::<0>::concat_swizzle(first, second);
+        let low = Deinterleave::<1>::concat_swizzle(first, second);
+        ((high << 4) | low).copy_to_slice(&mut output[start..start + LANES]);
+        start += LANES;
+    }
+
+    for (i, byte) in output[..len].iter_mut().enumerate().skip(start) {
+        *byte = decode_digit(input, 2 * i)? << 4 | decode_digit(input, 2 * i + 1)?;
+    }
+    Ok(len)
+}
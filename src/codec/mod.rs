@@ -0,0 +1,44 @@
+//! Encodings of binary data as text built upon SIMD.
+//!
+//! Encoders and decoders write to slices provided by the caller, which must be long enough for
+//! the result. Blocks of the input are processed by vectors, the rest of it is processed by
+//! scalar code. Invalid input is rejected with the offset of the error.
+
+use core::fmt;
+
+pub mod base64;
+pub mod hex;
+
+/// Error of decoding invalid input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The byte at `offset` of the input isn't a symbol of the encoding.
+    InvalidByte { offset: usize, byte: u8 },
+    /// No data is encoded by `length` bytes, e.g. by an odd number of hex digits.
+    InvalidLength { length: usize },
+    /// The last symbol at `offset` of the input has bits past the end of the data set, so the
+    /// encoding isn't canonical.
+    InvalidLastSymbol { offset: usize, byte: u8 },
+    /// The padding starting at `offset` of the input is missing, excessive or not allowed.
+    InvalidPadding { offset: usize },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            DecodeError::InvalidByte { offset, byte } => {
+                write!(f, "invalid byte {byte:#04x} at offset {offset}")
+            }
+            DecodeError::InvalidLength { length } => write!(f, "invalid input length {length}"),
+            DecodeError::InvalidLastSymbol { offset, byte } => {
+                write!(f, "invalid last symbol {byte:#04x} at offset {offset}")
+            }
+            DecodeError::InvalidPadding { offset } => {
+                write!(f, "invalid padding at offset {offset}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
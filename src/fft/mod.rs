@@ -0,0 +1,290 @@
+//! Fast Fourier transforms of power-of-two lengths on split complex buffers, i.e. with the real
+//! and the imaginary parts in separate slices.
+//!
+//! The forward transform computes `X[k] = Σ x[j]·exp(-2πi·jk/n)`, the inverse one is normalized,
+//! i.e. it divides by `n`, so it restores the input of the forward one. Transforms are done in
+//! place by decimation in time: the input is permuted into the bit-reversed order, then the
+//! butterflies of two stages at once (radix 4) are computed by vectors, the last stage is
+//! radix 2 if the number of stages is odd. Stages shorter than a vector combine lanes of the same
+//! vector by swizzles, so only the permutation is scalar.
+//!
+//! The twiddle factors are computed once by a plan [`Fft`] with the crate's own
+//! [`sin_cos`](crate::math::Trigonometry::sin_cos), into a table provided by the caller. A plan
+//! does transforms of any power-of-two length up to its own, since the twiddles of a shorter
+//! transform are a part of the table of a longer one.
+//!
+//! The spectrum of real input is Hermitian, so [`Fft::forward_real`] computes only its first half
+//! by a complex transform of half the length, and [`Fft::inverse_real`] restores the input from
+//! it.
+//!
+//! # Examples
+//! ```
+//! # #![feature(portable_simd)]
+//! use simd_addons::fft::Fft;
+//!
+//! let (mut twiddles_re, mut twiddles_im) = ([0.0f32; 8], [0.0; 8]);
+//! let fft = Fft::new(&mut twiddles_re, &mut twiddles_im);
+//! let mut re = [1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+//! let mut im = [0.0; 8];
+//! fft.forward(&mut re, &mut im);
+//! assert!((re[0] - 4.0).abs() < 1e-6 && im[0].abs() < 1e-6);
+//! assert!((re[1] - 1.0).abs() < 1e-6 && (im[1] + 2.414_213_5).abs() < 1e-6);
+//! assert!(re[2].abs() < 1e-6 && im[2].abs() < 1e-6);
+//!
+//! fft.inverse(&mut re, &mut im);
+//! let expected = [1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+//! assert!(re.iter().zip(expected).all(|(x, y)| (x - y).abs() < 1e-6));
+//! assert!(im.iter().all(|x| x.abs() < 1e-6));
+//!
+//! // the first half of the spectrum of real input
+//! let input = [1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+//! let (mut half_re, mut half_im) = ([0.0; 5], [0.0; 5]);
+//! fft.forward_real(&input, &mut half_re, &mut half_im);
+//! assert!((half_re[1] - 1.0).abs() < 1e-6 && (half_im[1] + 2.414_213_5).abs() < 1e-6);
+//!
+//! let mut output = [0.0; 8];
+//! fft.inverse_real(&mut half_re, &mut half_im, &mut output);
+//! assert!(output.iter().zip(input).all(|(x, y)| (x - y).abs() < 1e-6));
+//! ```
+
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::simd::SimdElement;
+
+mod radix;
+mod real;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Types of elements of transforms: `f32` and `f64`.
+pub trait FftFloat:
+    SimdElement
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + From<f32>
+    + sealed::Sealed
+{
+    #[doc(hidden)]
+    fn twiddles(re: &mut [Self], im: &mut [Self]);
+
+    #[doc(hidden)]
+    fn transform(twiddles_re: &[Self], twiddles_im: &[Self], re: &mut [Self], im: &mut [Self]);
+}
+
+macro_rules! impl_fft_float {
+    ($( $type: ident, $lanes: literal );+ $(;)?) => {
+        $(
+        impl sealed::Sealed for $type {}
+
+        impl FftFloat for $type {
+            #[inline]
+            fn twiddles(re: &mut [Self], im: &mut [Self]) {
+                radix::twiddles::<_, $lanes>(re, im)
+            }
+
+            #[inline]
+            fn transform(
+                twiddles_re: &[Self],
+                twiddles_im: &[Self],
+                re: &mut [Self],
+                im: &mut [Self],
+            ) {
+                let twiddles = Twiddles {
+                    re: twiddles_re,
+                    im: twiddles_im,
+                };
+                radix::transform::<_, $lanes>(&twiddles, re, im)
+            }
+        }
+        )+
+    };
+}
+
+impl_fft_float!(
+    f32, 16;
+    f64, 8;
+);
+
+/// The table of twiddle factors of a plan.
+struct Twiddles<'a, T> {
+    re: &'a [T],
+    im: &'a [T],
+}
+
+impl<T: FftFloat> Twiddles<'_, T> {
+    /// `exp(-πi·j/half)`, which is the twiddle factor of element `j` of a butterfly of two halves
+    /// of length `half`.
+    #[inline(always)]
+    fn get(&self, half: usize, j: usize) -> Complex<T> {
+        Complex::new(self.re[half + j], self.im[half + j])
+    }
+}
+
+/// Complex numbers, or vectors of them.
+#[derive(Clone, Copy)]
+struct Complex<V> {
+    re: V,
+    im: V,
+}
+
+impl<V> Complex<V>
+where
+    V: Copy + Add<Output = V> + Sub<Output = V> + Mul<Output = V> + Neg<Output = V>,
+{
+    #[inline(always)]
+    fn new(re: V, im: V) -> Self {
+        Self { re, im }
+    }
+
+    #[inline(always)]
+    fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    /// Multiplication by `-i`, i.e. rotation by a quarter of a turn clockwise.
+    #[inline(always)]
+    fn mul_neg_i(self) -> Self {
+        Self::new(self.im, -self.re)
+    }
+
+    /// Multiplication by `i`, i.e. rotation by a quarter of a turn counterclockwise.
+    #[inline(always)]
+    fn mul_i(self) -> Self {
+        Self::new(-self.im, self.re)
+    }
+
+    #[inline(always)]
+    fn scale(self, factor: V) -> Self {
+        Self::new(self.re * factor, self.im * factor)
+    }
+}
+
+impl<V: Add<Output = V>> Add for Complex<V> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, other: Self) -> Self {
+        Self {
+            re: self.re + other.re,
+            im: self.im + other.im,
+        }
+    }
+}
+
+impl<V: Sub<Output = V>> Sub for Complex<V> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, other: Self) -> Self {
+        Self {
+            re: self.re - other.re,
+            im: self.im - other.im,
+        }
+    }
+}
+
+impl<V: Copy + Add<Output = V> + Sub<Output = V> + Mul<Output = V>> Mul for Complex<V> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, other: Self) -> Self {
+        Self {
+            re: self.re * other.re - self.im * other.im,
+            im: self.re * other.im + self.im * other.re,
+        }
+    }
+}
+
+/// Plan of transforms of power-of-two lengths up to its own, which holds their twiddle factors.
+///
+/// The table is borrowed from the caller, so the length of a plan is chosen at run time and its
+/// storage, e.g. a `Vec` or an array, is up to the caller as well.
+#[derive(Clone, Copy, Debug)]
+pub struct Fft<'a, T> {
+    re: &'a [T],
+    im: &'a [T],
+}
+
+impl<'a, T: FftFloat> Fft<'a, T> {
+    /// Computes the twiddle factors of the plan into `re` and `im`, whose length is the length
+    /// of the plan.
+    ///
+    /// # Panics
+    /// Panics if the lengths of `re` and `im` differ, or if they aren't a power of two.
+    #[inline]
+    pub fn new(re: &'a mut [T], im: &'a mut [T]) -> Self {
+        assert_eq!(
+            re.len(),
+            im.len(),
+            "the real and the imaginary parts of the table differ in length"
+        );
+        assert!(
+            re.len().is_power_of_two(),
+            "the length of the plan isn't a power of two"
+        );
+        // element `half + j` is the twiddle factor `exp(-πi·j/half)` of the stage combining
+        // halves of length `half`, so the table of each length is the beginning of the longer
+        // ones
+        re.fill(T::from(1.0));
+        im.fill(T::from(0.0));
+        T::twiddles(re, im);
+        Self { re, im }
+    }
+
+    #[inline(always)]
+    fn twiddles(&self) -> Twiddles<'_, T> {
+        Twiddles {
+            re: self.re,
+            im: self.im,
+        }
+    }
+
+    /// Checks that `len` is a length of transforms of the plan.
+    #[inline(always)]
+    fn check_len(&self, len: usize) {
+        assert!(
+            len.is_power_of_two() && len <= self.re.len(),
+            "the length of the transform isn't a power of two up to the length of the plan"
+        );
+    }
+
+    /// Replaces complex numbers with real parts `re` and imaginary parts `im` with their
+    /// discrete Fourier transform, `X[k] = Σ x[j]·exp(-2πi·jk/n)`.
+    ///
+    /// # Panics
+    /// Panics if the lengths of `re` and `im` differ, or if they aren't a power of two up to
+    /// the length of the plan.
+    #[inline]
+    pub fn forward(&self, re: &mut [T], im: &mut [T]) {
+        assert_eq!(
+            re.len(),
+            im.len(),
+            "the real and the imaginary parts differ in length"
+        );
+        self.check_len(re.len());
+        T::transform(self.re, self.im, re, im);
+    }
+
+    /// Replaces complex numbers with real parts `re` and imaginary parts `im` with their inverse
+    /// discrete Fourier transform, `x[j] = Σ X[k]·exp(2πi·jk/n) / n`, which restores the input of
+    /// [`forward`](Self::forward).
+    ///
+    /// # Panics
+    /// Panics if the lengths of `re` and `im` differ, or if they aren't a power of two up to
+    /// the length of the plan.
+    #[inline]
+    pub fn inverse(&self, re: &mut [T], im: &mut [T]) {
+        // the inverse transform is the forward one of the numbers with swapped parts, i.e.
+        // multiplied by `i` and conjugated, with swapped parts of the result
+        self.forward(im, re);
+        let scale = T::from(1.0) / T::from(re.len() as f32);
+        for x in re.iter_mut().chain(im.iter_mut()) {
+            *x = *x * scale;
+        }
+    }
+}
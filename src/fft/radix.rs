@@ -0,0 +1,234 @@
+use crate::{
+    fft::{Complex, FftFloat, Twiddles},
+    math::{SimdFloatMath, Trigonometry},
+    simd::{LaneCount, Simd, SupportedLaneCount, Swizzle},
+};
+
+/// Fills the table of twiddle factors, element `half + j` becomes `exp(-πi·j/half)`.
+#[inline(always)]
+pub(super) fn twiddles<T, const N: usize>(re: &mut [T], im: &mut [T])
+where
+    T: FftFloat,
+    Simd<T, N>: SimdFloatMath<Scalar = T>,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let iota = Simd::<T, N>::from_array(core::array::from_fn(|i| T::from(i as f32)));
+    let mut half = 1;
+    while half < re.len() {
+        // powers of two are exact, so is the index, thus the angle is rounded only once
+        let step = -Simd::<T, N>::PI / Simd::splat(T::from(half as f32));
+        let mut index = iota;
+        for start in (0..half).step_by(N) {
+            let (sin, cos) = (index * step).sin_cos();
+            let count = N.min(half - start);
+            re[half + start..][..count].copy_from_slice(&cos.to_array()[..count]);
+            im[half + start..][..count].copy_from_slice(&sin.to_array()[..count]);
+            index += Simd::splat(T::from(N as f32));
+        }
+        half *= 2;
+    }
+}
+
+/// Forward transform of `re.len()` complex numbers in place, the length is a power of two.
+#[inline(always)]
+pub(super) fn transform<T, const N: usize>(twiddles: &Twiddles<'_, T>, re: &mut [T], im: &mut [T])
+where
+    T: FftFloat,
+    Simd<T, N>: SimdFloatMath<Scalar = T>,
+    Simd<T, 1>: SimdFloatMath<Scalar = T>,
+    LaneCount<N>: SupportedLaneCount,
+{
+    bit_reverse(re, im);
+    if re.len() < N {
+        radix_stages::<T, 1>(twiddles, re, im, 1);
+        return;
+    }
+    short_stage::<T, 1, N>(twiddles, re, im);
+    short_stage::<T, 2, N>(twiddles, re, im);
+    short_stage::<T, 4, N>(twiddles, re, im);
+    short_stage::<T, 8, N>(twiddles, re, im);
+    short_stage::<T, 16, N>(twiddles, re, im);
+    short_stage::<T, 32, N>(twiddles, re, im);
+    radix_stages::<T, N>(twiddles, re, im, N);
+}
+
+/// Swaps the elements with bit-reversed indices.
+#[inline(always)]
+fn bit_reverse<T: FftFloat>(re: &mut [T], im: &mut [T]) {
+    let len = re.len();
+    if len < 2 {
+        return;
+    }
+    let shift = usize::BITS - len.trailing_zeros();
+    for i in 0..len {
+        let j = i.reverse_bits() >> shift;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+}
+
+#[inline(always)]
+fn load<T, const N: usize>(re: &[T], im: &[T], start: usize) -> Complex<Simd<T, N>>
+where
+    T: FftFloat,
+    LaneCount<N>: SupportedLaneCount,
+{
+    Complex {
+        re: Simd::from_slice(&re[start..]),
+        im: Simd::from_slice(&im[start..]),
+    }
+}
+
+#[inline(always)]
+fn store<T, const N: usize>(re: &mut [T], im: &mut [T], start: usize, value: Complex<Simd<T, N>>)
+where
+    T: FftFloat,
+    LaneCount<N>: SupportedLaneCount,
+{
+    value.re.copy_to_slice(&mut re[start..start + N]);
+    value.im.copy_to_slice(&mut im[start..start + N]);
+}
+
+/// Moves the first element of each butterfly of two halves of length `H` to both lanes of the
+/// butterfly, if it fits into a vector.
+struct FirstHalf<const H: usize>;
+
+/// Moves the second element of each butterfly of two halves of length `H` to both lanes of the
+/// butterfly, if it fits into a vector.
+struct SecondHalf<const H: usize>;
+
+const fn half_index<const N: usize>(half: usize, second: bool) -> [usize; N] {
+    let mut index = [0; N];
+    let mut i = 0;
+    while i < N {
+        index[i] = if half >= N {
+            i
+        } else if second {
+            i | half
+        } else {
+            i & !half
+        };
+        i += 1;
+    }
+    index
+}
+
+impl<const H: usize, const N: usize> Swizzle<N> for FirstHalf<H> {
+    const INDEX: [usize; N] = half_index::<N>(H, false);
+}
+
+impl<const H: usize, const N: usize> Swizzle<N> for SecondHalf<H> {
+    const INDEX: [usize; N] = half_index::<N>(H, true);
+}
+
+/// The stage of butterflies of two halves of length `H`, if they fit into a vector of `N` lanes.
+#[inline(always)]
+fn short_stage<T, const H: usize, const N: usize>(
+    twiddles: &Twiddles<'_, T>,
+    re: &mut [T],
+    im: &mut [T],
+) where
+    T: FftFloat,
+    Simd<T, N>: SimdFloatMath<Scalar = T>,
+    LaneCount<N>: SupportedLaneCount,
+{
+    if H >= N {
+        return;
+    }
+    // the first half of a butterfly gets `a + wb`, the second one gets `a - wb`, so the twiddle
+    // factors of the second half are negated
+    let twiddle = |i: usize| {
+        let w = twiddles.get(H, i & (H - 1));
+        if i & H == 0 {
+            w
+        } else {
+            Complex::new(-w.re, -w.im)
+        }
+    };
+    let w = Complex {
+        re: Simd::<T, N>::from_array(core::array::from_fn(|i| twiddle(i).re)),
+        im: Simd::<T, N>::from_array(core::array::from_fn(|i| twiddle(i).im)),
+    };
+    for start in (0..re.len()).step_by(N) {
+        let x = load::<T, N>(re, im, start);
+        let a = Complex::new(FirstHalf::<H>::swizzle(x.re), FirstHalf::<H>::swizzle(x.im));
+        let b = Complex::new(
+            SecondHalf::<H>::swizzle(x.re),
+            SecondHalf::<H>::swizzle(x.im),
+        );
+        store(re, im, start, a + w * b);
+    }
+}
+
+/// The remaining stages, starting with the butterflies of two halves of length `half`, which is a
+/// multiple of `N`.
+#[inline(always)]
+fn radix_stages<T, const N: usize>(
+    twiddles: &Twiddles<'_, T>,
+    re: &mut [T],
+    im: &mut [T],
+    mut half: usize,
+) where
+    T: FftFloat,
+    Simd<T, N>: SimdFloatMath<Scalar = T>,
+    LaneCount<N>: SupportedLaneCount,
+{
+    let len = re.len();
+    while 4 * half <= len {
+        radix_4::<T, N>(twiddles, re, im, half);
+        half *= 4;
+    }
+    if 2 * half <= len {
+        radix_2::<T, N>(twiddles, re, im, half);
+    }
+}
+
+/// The stage of butterflies of two halves of length `half`.
+#[inline(always)]
+fn radix_2<T, const N: usize>(twiddles: &Twiddles<'_, T>, re: &mut [T], im: &mut [T], half: usize)
+where
+    T: FftFloat,
+    Simd<T, N>: SimdFloatMath<Scalar = T>,
+    LaneCount<N>: SupportedLaneCount,
+{
+    for block in (0..re.len()).step_by(2 * half) {
+        for j in (0..half).step_by(N) {
+            let w = load::<T, N>(twiddles.re, twiddles.im, half + j);
+            let a = load::<T, N>(re, im, block + j);
+            let b = w * load::<T, N>(re, im, block + j + half);
+            store(re, im, block + j, a + b);
+            store(re, im, block + j + half, a - b);
+        }
+    }
+}
+
+/// The stages of butterflies of two halves of length `half` and then of `2 * half` at once.
+#[inline(always)]
+fn radix_4<T, const N: usize>(twiddles: &Twiddles<'_, T>, re: &mut [T], im: &mut [T], half: usize)
+where
+    T: FftFloat,
+    Simd<T, N>: SimdFloatMath<Scalar = T>,
+    LaneCount<N>: SupportedLaneCount,
+{
+    for block in (0..re.len()).step_by(4 * half) {
+        for j in (0..half).step_by(N) {
+            let w1 = load::<T, N>(twiddles.re, twiddles.im, half + j);
+            // the twiddle factor of `j + half` of the second stage is the one of `j` times `-i`
+            let w2 = load::<T, N>(twiddles.re, twiddles.im, 2 * half + j);
+
+            let x0 = load::<T, N>(re, im, block + j);
+            let x1 = w1 * load::<T, N>(re, im, block + j + half);
+            let x2 = load::<T, N>(re, im, block + j + 2 * half);
+            let x3 = w1 * load::<T, N>(re, im, block + j + 3 * half);
+            let (y0, y1) = (x0 + x1, x0 - x1);
+            let (y2, y3) = (w2 * (x2 + x3), (w2 * (x2 - x3)).mul_neg_i());
+
+            store(re, im, block + j, y0 + y2);
+            store(re, im, block + j + half, y1 + y3);
+            store(re, im, block + j + 2 * half, y0 - y2);
+            store(re, im, block + j + 3 * half, y1 - y3);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::fft::{Complex, Fft, FftFloat};
+
+impl<T: FftFloat> Fft<'_, T> {
+    /// Checks that `len` is a length of real transforms of the plan, and that the spectrum
+    /// `re` and `im` is half as long plus one.
+    #[inline(always)]
+    fn check_real_len(&self, len: usize, re: &[T], im: &[T]) {
+        assert!(len >= 2, "the length of a real transform is less than two");
+        self.check_len(len);
+        assert!(
+            re.len() == len / 2 + 1 && im.len() == len / 2 + 1,
+            "the length of the spectrum isn't half the length of the transform plus one"
+        );
+    }
+
+    /// Writes the first half of the discrete Fourier transform of real `input` to `re` and `im`,
+    /// i.e. the `n / 2 + 1` frequencies from zero to the Nyquist one. The rest of it is their
+    /// conjugate, `X[n - k] = conj(X[k])`.
+    ///
+    /// The even and the odd elements of `input` are transformed as the real and the imaginary
+    /// parts of a complex transform of half the length, and then they are separated.
+    ///
+    /// # Panics
+    /// Panics if the length of `input` isn't a power of two from 2 up to the length of the plan,
+    /// or if the lengths of `re` and `im` aren't half of it plus one.
+    #[inline]
+    pub fn forward_real(&self, input: &[T], re: &mut [T], im: &mut [T]) {
+        self.check_real_len(input.len(), re, im);
+        let half = input.len() / 2;
+        for (k, pair) in input.chunks_exact(2).enumerate() {
+            re[k] = pair[0];
+            im[k] = pair[1];
+        }
+        T::transform(self.re, self.im, &mut re[..half], &mut im[..half]);
+
+        // `Z[k] = E[k] + iO[k]` of the transforms `E` and `O` of the even and the odd elements,
+        // which are Hermitian, so they are separated by `Z[half - k]`, then
+        // `X[k] = E[k] + exp(-2πi·k/n)·O[k]`
+        let (z_re, z_im) = (re[0], im[0]);
+        (re[0], im[0]) = (z_re + z_im, T::from(0.0));
+        (re[half], im[half]) = (z_re - z_im, T::from(0.0));
+        let one_half = T::from(0.5);
+        let twiddles = self.twiddles();
+        for k in 1..=half / 2 {
+            let z = Complex::new(re[k], im[k]);
+            let mirror = Complex::new(re[half - k], im[half - k]).conj();
+            let even = (z + mirror).scale(one_half);
+            let odd = (z - mirror).scale(one_half).mul_neg_i();
+            let odd = twiddles.get(half, k) * odd;
+            (re[k], im[k]) = (even.re + odd.re, even.im + odd.im);
+            (re[half - k], im[half - k]) = (even.re - odd.re, odd.im - even.im);
+        }
+    }
+
+    /// Writes the real inverse discrete Fourier transform of the first half of a Hermitian
+    /// spectrum `re` and `im`, as returned by [`forward_real`](Self::forward_real), to `output`.
+    /// The imaginary parts of the zero and the Nyquist frequencies are ignored.
+    ///
+    /// `re` and `im` are used as the buffers of a complex transform of half the length, so they
+    /// are overwritten.
+    ///
+    /// # Panics
+    /// Panics if the length of `output` isn't a power of two from 2 up to the length of the
+    /// plan, or if the lengths of `re` and `im` aren't half of it plus one.
+    #[inline]
+    pub fn inverse_real(&self, re: &mut [T], im: &mut [T], output: &mut [T]) {
+        self.check_real_len(output.len(), re, im);
+        let half = output.len() / 2;
+
+        // the inverse of the separation in `forward_real`: `E[k]` and `O[k]` are restored from
+        // `X[k]` and `X[half - k]`, and they are combined into `Z[k] = E[k] + iO[k]`
+        let one_half = T::from(0.5);
+        let (first, last) = (re[0], re[half]);
+        (re[0], im[0]) = ((first + last) * one_half, (first - last) * one_half);
+        let twiddles = self.twiddles();
+        for k in 1..=half / 2 {
+            let x = Complex::new(re[k], im[k]);
+            let mirror = Complex::new(re[half - k], im[half - k]).conj();
+            let even = (x + mirror).scale(one_half);
+            let odd = twiddles.get(half, k).conj() * (x - mirror).scale(one_half);
+            let (z, z_mirror) = (even + odd.mul_i(), even.conj() + odd.conj().mul_i());
+            (re[k], im[k]) = (z.re, z.im);
+            (re[half - k], im[half - k]) = (z_mirror.re, z_mirror.im);
+        }
+
+        self.inverse(&mut re[..half], &mut im[..half]);
+        for (k, pair) in output.chunks_exact_mut(2).enumerate() {
+            pair[0] = re[k];
+            pair[1] = im[k];
+        }
+    }
+}
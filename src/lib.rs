@@ -1,3 +1,17 @@
-#![feature(portable_simd)]
+#![no_std]
+#![cfg_attr(not(simd_shim), feature(portable_simd))]
+#![cfg_attr(
+    all(not(simd_shim), target_feature = "avx512f"),
+    feature(stdarch_x86_avx512)
+)]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod algo;
+pub mod codec;
+pub mod fft;
+pub mod linalg;
 pub mod math;
+pub mod rng;
+pub mod simd;
@@ -0,0 +1,225 @@
+use core::ops::Mul;
+
+use crate::{
+    linalg::{Vec2, Vec3, Vec4},
+    math::SimdFloatMath,
+};
+
+macro_rules! impl_matrix {
+    ($( $(#[$attr: meta])* $name: ident, $vector: ident, $dim: literal );+ $(;)?) => {
+        $(
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        pub struct $name<V> {
+            pub rows: [$vector<V>; $dim],
+        }
+
+        impl<V: SimdFloatMath> $name<V> {
+            /// Makes a matrix of its elements, `rows[i][j]` is the element of row `i` and column
+            /// `j`.
+            #[inline]
+            pub fn from_rows(rows: [[V; $dim]; $dim]) -> Self {
+                Self {
+                    rows: rows.map($vector::from_array),
+                }
+            }
+
+            #[inline]
+            pub fn to_rows(self) -> [[V; $dim]; $dim] {
+                self.rows.map($vector::to_array)
+            }
+
+            /// Makes a matrix with the element of row `i` and column `j` equal to `f(i, j)`.
+            #[inline]
+            pub fn from_fn(mut f: impl FnMut(usize, usize) -> V) -> Self {
+                Self::from_rows(core::array::from_fn(|i| core::array::from_fn(|j| f(i, j))))
+            }
+
+            #[inline]
+            pub fn identity() -> Self {
+                Self::from_fn(|i, j| if i == j { V::ONE } else { V::ZERO })
+            }
+
+            #[inline]
+            pub fn transpose(self) -> Self {
+                let rows = self.to_rows();
+                Self::from_fn(|i, j| rows[j][i])
+            }
+        }
+
+        impl<V: SimdFloatMath> Mul for $name<V> {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, other: Self) -> Self {
+                // row `i` of the product is the combination of the rows of `other` with the
+                // elements of row `i` of `self`
+                let rows = self.rows.map(|row| {
+                    let row = row.to_array();
+                    (1..$dim).fold(other.rows[0] * row[0], |sum, k| sum + other.rows[k] * row[k])
+                });
+                Self { rows }
+            }
+        }
+
+        impl<V: SimdFloatMath> Mul<$vector<V>> for $name<V> {
+            type Output = $vector<V>;
+
+            #[inline]
+            fn mul(self, vector: $vector<V>) -> $vector<V> {
+                $vector::from_array(self.rows.map(|row| row.dot(vector)))
+            }
+        }
+        )+
+    };
+}
+
+impl_matrix!(
+    /// 2×2 matrices, one per lane of `V`.
+    Mat2, Vec2, 2;
+    /// 3×3 matrices, one per lane of `V`.
+    Mat3, Vec3, 3;
+    /// 4×4 matrices, one per lane of `V`.
+    Mat4, Vec4, 4;
+);
+
+impl<V: SimdFloatMath> Mat2<V> {
+    /// Counterclockwise rotation by `angle` in radians.
+    #[inline]
+    pub fn from_angle(angle: V) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::from_rows([[cos, -sin], [sin, cos]])
+    }
+
+    #[inline]
+    pub fn determinant(self) -> V {
+        let [[a, b], [c, d]] = self.to_rows();
+        a * d - b * c
+    }
+
+    /// Inverse of the matrices. Lanes of singular matrices, i.e. with zero
+    /// [`determinant`](Self::determinant), become infinite or NaN.
+    #[inline]
+    pub fn inverse(self) -> Self {
+        let [[a, b], [c, d]] = self.to_rows();
+        let scale = V::ONE / (a * d - b * c);
+        Self::from_rows([[d * scale, -b * scale], [-c * scale, a * scale]])
+    }
+}
+
+impl<V: SimdFloatMath> Mat3<V> {
+    /// Rotation by `angle` in radians around `axis`, which must be a unit vector. The rotation is
+    /// counterclockwise when the axis points towards the viewer.
+    #[inline]
+    pub fn from_axis_angle(axis: Vec3<V>, angle: V) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let Vec3 { x, y, z } = axis;
+        let scaled = axis * (V::ONE - cos);
+        let Vec3 {
+            x: sin_x,
+            y: sin_y,
+            z: sin_z,
+        } = axis * sin;
+        Self::from_rows([
+            [
+                scaled.x * x + cos,
+                scaled.x * y - sin_z,
+                scaled.x * z + sin_y,
+            ],
+            [
+                scaled.y * x + sin_z,
+                scaled.y * y + cos,
+                scaled.y * z - sin_x,
+            ],
+            [
+                scaled.z * x - sin_y,
+                scaled.z * y + sin_x,
+                scaled.z * z + cos,
+            ],
+        ])
+    }
+
+    #[inline]
+    pub fn determinant(self) -> V {
+        let [a, b, c] = self.rows;
+        a.dot(b.cross(c))
+    }
+
+    /// Inverse of the matrices. Lanes of singular matrices, i.e. with zero
+    /// [`determinant`](Self::determinant), become infinite or NaN.
+    #[inline]
+    pub fn inverse(self) -> Self {
+        let [a, b, c] = self.rows;
+        // the cross products are orthogonal to all rows but one, with which their dot product
+        // is the determinant, so they are the columns of the inverse scaled by it
+        let columns = [b.cross(c), c.cross(a), a.cross(b)];
+        let scale = V::ONE / a.dot(columns[0]);
+        Self {
+            rows: columns.map(|column| column * scale),
+        }
+        .transpose()
+    }
+}
+
+impl<V: SimdFloatMath> Mat4<V> {
+    /// Determinants of the 2×2 submatrices of the upper and the lower two rows.
+    #[inline(always)]
+    fn minors(self) -> ([V; 6], [V; 6]) {
+        let m = self.to_rows();
+        let minors = |r: usize| {
+            let minor = |i: usize, j: usize| m[r][i] * m[r + 1][j] - m[r + 1][i] * m[r][j];
+            [
+                minor(0, 1),
+                minor(0, 2),
+                minor(0, 3),
+                minor(1, 2),
+                minor(1, 3),
+                minor(2, 3),
+            ]
+        };
+        (minors(0), minors(2))
+    }
+
+    /// Computed by the Laplace expansion along the upper two rows.
+    #[inline]
+    pub fn determinant(self) -> V {
+        let ([s0, s1, s2, s3, s4, s5], [c0, c1, c2, c3, c4, c5]) = self.minors();
+        s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0
+    }
+
+    /// Inverse of the matrices by cofactors, which are made of the 2×2 minors of
+    /// [`determinant`](Self::determinant). Lanes of singular matrices become infinite or NaN.
+    #[inline]
+    pub fn inverse(self) -> Self {
+        let m = self.to_rows();
+        let ([s0, s1, s2, s3, s4, s5], [c0, c1, c2, c3, c4, c5]) = self.minors();
+        let scale = V::ONE / (s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0);
+        let cofactors = [
+            [
+                m[1][1] * c5 - m[1][2] * c4 + m[1][3] * c3,
+                m[0][2] * c4 - m[0][1] * c5 - m[0][3] * c3,
+                m[3][1] * s5 - m[3][2] * s4 + m[3][3] * s3,
+                m[2][2] * s4 - m[2][1] * s5 - m[2][3] * s3,
+            ],
+            [
+                m[1][2] * c2 - m[1][0] * c5 - m[1][3] * c1,
+                m[0][0] * c5 - m[0][2] * c2 + m[0][3] * c1,
+                m[3][2] * s2 - m[3][0] * s5 - m[3][3] * s1,
+                m[2][0] * s5 - m[2][2] * s2 + m[2][3] * s1,
+            ],
+            [
+                m[1][0] * c4 - m[1][1] * c2 + m[1][3] * c0,
+                m[0][1] * c2 - m[0][0] * c4 - m[0][3] * c0,
+                m[3][0] * s4 - m[3][1] * s2 + m[3][3] * s0,
+                m[2][1] * s2 - m[2][0] * s4 - m[2][3] * s0,
+            ],
+            [
+                m[1][1] * c1 - m[1][0] * c3 - m[1][2] * c0,
+                m[0][0] * c3 - m[0][1] * c1 + m[0][2] * c0,
+                m[3][1] * s1 - m[3][0] * s3 - m[3][2] * s0,
+                m[2][0] * s3 - m[2][1] * s1 + m[2][2] * s0,
+            ],
+        ];
+        Self::from_rows(cofactors.map(|row| row.map(|x| x * scale)))
+    }
+}
@@ -0,0 +1,57 @@
+//! Batched linear algebra of small vectors and matrices, with one vector or matrix per lane.
+//!
+//! The components of [`Vec2`], [`Vec3`], [`Vec4`] and [`Quat`] and the elements of [`Mat2`],
+//! [`Mat3`] and [`Mat4`] are float vectors, i.e. the layout is a struct of arrays: lane `i` of
+//! every component makes the `i`-th vector or matrix. So all operations are done by plain
+//! lane-wise arithmetic with no shuffles, and they work for any float vector type implementing
+//! [`SimdFloatMath`](crate::math::SimdFloatMath).
+//!
+//! Rotations are made by [`sin_cos`](crate::math::Trigonometry::sin_cos) of the crate and
+//! interpolated by its [`acos`](crate::math::Trigonometry::acos), vectors are normalized by its
+//! [`rsqrt`](crate::math::SimdFloatMath::rsqrt).
+//!
+//! # Examples
+//! ```
+//! # #![feature(portable_simd)]
+//! # use std::simd::prelude::*;
+//! use simd_addons::linalg::{Mat3, Quat, Vec3};
+//!
+//! // rotations of the x axis around the z axis by different angles
+//! let axis = Vec3::new(f32x4::splat(0.0), f32x4::splat(0.0), f32x4::splat(1.0));
+//! let angles = f32x4::from_array([0.0, 0.5, 1.0, 2.0]) * f32x4::splat(std::f32::consts::PI);
+//! let rotation = Mat3::from_axis_angle(axis, angles);
+//! let x = Vec3::new(f32x4::splat(1.0), f32x4::splat(0.0), f32x4::splat(0.0));
+//! let rotated = rotation * x;
+//! let expected = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [-1.0, 0.0, 0.0], [1.0, 0.0, 0.0]];
+//! for (i, expected) in expected.into_iter().enumerate() {
+//!     let lane = rotated.to_array().map(|component| component[i]);
+//!     assert!(lane.iter().zip(expected).all(|(x, y)| (x - y).abs() < 1e-6));
+//! }
+//!
+//! // rotations preserve the length and the determinant of their matrices is one
+//! assert!((rotated.length() - f32x4::splat(1.0)).abs().simd_lt(f32x4::splat(1e-6)).all());
+//! let determinant = rotation.determinant();
+//! assert!((determinant - f32x4::splat(1.0)).abs().simd_lt(f32x4::splat(1e-6)).all());
+//! // and the inverse rotates the vectors back
+//! let back = rotation.inverse() * rotated - x;
+//! assert!(back.length().simd_lt(f32x4::splat(1e-6)).all());
+//!
+//! // the same rotations by quaternions
+//! let quat = Quat::from_axis_angle(axis, angles);
+//! assert!(((quat * x) - rotated).length().simd_lt(f32x4::splat(1e-6)).all());
+//!
+//! // interpolation of rotations with constant angular velocity
+//! let angles = f32x4::from_array([0.0, 0.5, 0.75, 0.9]) * f32x4::splat(std::f32::consts::PI);
+//! let quat = Quat::from_axis_angle(axis, angles);
+//! let third = Quat::IDENTITY.slerp(quat, f32x4::splat(1.0 / 3.0));
+//! let expected = Quat::from_axis_angle(axis, angles / f32x4::splat(3.0));
+//! assert!((third - expected).length().simd_lt(f32x4::splat(1e-6)).all());
+//! ```
+
+mod matrix;
+mod quat;
+mod vector;
+
+pub use matrix::*;
+pub use quat::*;
+pub use vector::*;
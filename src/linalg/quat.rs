@@ -0,0 +1,185 @@
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::{
+    linalg::{vector::impl_vector, Mat3, Mat4, Vec3},
+    math::SimdFloatMath,
+};
+
+impl_vector!(
+    /// Quaternions `w + xi + yj + zk`, one per lane of `V`. Unit quaternions represent rotations,
+    /// they rotate vectors by multiplication and are composed by the Hamilton product, like
+    /// matrices.
+    Quat, 4, [x, y, z, w];
+);
+
+impl<V: SimdFloatMath> Quat<V> {
+    /// The identity rotation.
+    pub const IDENTITY: Self = Self::new(V::ZERO, V::ZERO, V::ZERO, V::ONE);
+
+    /// Rotation by `angle` in radians around `axis`, which must be a unit vector, the same one as
+    /// [`Mat3::from_axis_angle`].
+    #[inline]
+    pub fn from_axis_angle(axis: Vec3<V>, angle: V) -> Self {
+        let (sin, cos) = (angle * V::HALF).sin_cos();
+        let Vec3 { x, y, z } = axis * sin;
+        Self::new(x, y, z, cos)
+    }
+
+    /// The vector part `(x, y, z)`.
+    #[inline]
+    pub fn xyz(self) -> Vec3<V> {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    /// The conjugate `w - xi - yj - zk`, which is the inverse rotation for unit quaternions.
+    #[inline]
+    pub fn conjugate(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Inverse of the quaternions. Zero lanes become infinite or NaN.
+    #[inline]
+    pub fn inverse(self) -> Self {
+        self.conjugate() / self.length_squared()
+    }
+
+    /// Chooses the quaternions of `true_values` in lanes where `mask` is set and the ones of
+    /// `false_values` otherwise.
+    #[inline(always)]
+    fn select(mask: <V as SimdFloatMath>::Mask, true_values: Self, false_values: Self) -> Self {
+        let (true_values, false_values) = (true_values.to_array(), false_values.to_array());
+        Self::from_array(core::array::from_fn(|i| {
+            V::select(mask, true_values[i], false_values[i])
+        }))
+    }
+
+    /// Normalized linear interpolation from `self` at `t = 0` to `other` at `t = 1` along the
+    /// shorter arc. Unlike [`slerp`](Self::slerp), the angular velocity isn't constant, but it
+    /// is faster.
+    #[inline]
+    pub fn nlerp(self, other: Self, t: V) -> Self {
+        let other = Self::select(self.dot(other).simd_lt(V::ZERO), -other, other);
+        (self * (V::ONE - t) + other * t).normalize()
+    }
+
+    /// Spherical linear interpolation of unit quaternions from `self` at `t = 0` to `other` at
+    /// `t = 1` along the shorter arc, i.e. rotation with constant angular velocity. Falls back to
+    /// [`nlerp`](Self::nlerp) for nearly parallel quaternions, where both are the same up to
+    /// rounding.
+    #[inline]
+    pub fn slerp(self, other: Self, t: V) -> Self {
+        let cos = self.dot(other);
+        let other = Self::select(cos.simd_lt(V::ZERO), -other, other);
+        let cos = cos.abs();
+        let angle = cos.acos();
+        let scale = V::ONE / angle.sin();
+        let slerp =
+            self * (((V::ONE - t) * angle).sin() * scale) + other * ((t * angle).sin() * scale);
+        let nlerp = (self * (V::ONE - t) + other * t).normalize();
+        // the formula of slerp is 0/0 for parallel quaternions, while nlerp differs from it by
+        // about `angle³ / 64`, which is negligible below `angle = sqrt(32ε)`
+        let four = V::TWO * V::TWO;
+        let parallel = cos.simd_gt(V::ONE - V::EPSILON * four * four);
+        Self::select(parallel, nlerp, slerp)
+    }
+
+    /// Rotation of the unit quaternions as a matrix.
+    #[inline]
+    pub fn to_mat3(self) -> Mat3<V> {
+        let Self { x, y, z, w } = self;
+        let (x2, y2, z2) = (x * V::TWO, y * V::TWO, z * V::TWO);
+        let (xx, yy, zz) = (x * x2, y * y2, z * z2);
+        let (xy, xz, yz) = (x * y2, x * z2, y * z2);
+        let (wx, wy, wz) = (w * x2, w * y2, w * z2);
+        Mat3::from_rows([
+            [V::ONE - yy - zz, xy - wz, xz + wy],
+            [xy + wz, V::ONE - xx - zz, yz - wx],
+            [xz - wy, yz + wx, V::ONE - xx - yy],
+        ])
+    }
+
+    /// Rotation of the unit quaternions as a matrix of homogeneous coordinates.
+    #[inline]
+    pub fn to_mat4(self) -> Mat4<V> {
+        let [a, b, c] = self.to_mat3().to_rows();
+        let zero = V::ZERO;
+        Mat4::from_rows([
+            [a[0], a[1], a[2], zero],
+            [b[0], b[1], b[2], zero],
+            [c[0], c[1], c[2], zero],
+            [zero, zero, zero, V::ONE],
+        ])
+    }
+
+    /// Unit quaternions of rotation matrices, with non-negative `w`. Matrices which aren't
+    /// orthogonal give the quaternions of approximately the same rotations.
+    #[inline]
+    pub fn from_mat3(matrix: Mat3<V>) -> Self {
+        let [[m00, m01, m02], [m10, m11, m12], [m20, m21, m22]] = matrix.to_rows();
+        let one = V::ONE;
+        // the diagonal gives the squares of the components times 4, the sums and differences of
+        // the symmetric elements give their products times 4; the products with the largest
+        // component are the most accurate, they are the quaternion scaled by it
+        let ww = one + m00 + m11 + m22;
+        let xx = one + m00 - m11 - m22;
+        let yy = one - m00 + m11 - m22;
+        let zz = one - m00 - m11 + m22;
+        let (wx, wy, wz) = (m21 - m12, m02 - m20, m10 - m01);
+        let (xy, xz, yz) = (m01 + m10, m02 + m20, m12 + m21);
+
+        let mut largest = ww;
+        let mut scaled = Self::new(wx, wy, wz, ww);
+        for (square, candidate) in [
+            (xx, Self::new(xx, xy, xz, wx)),
+            (yy, Self::new(xy, yy, yz, wy)),
+            (zz, Self::new(xz, yz, zz, wz)),
+        ] {
+            let larger = square.simd_gt(largest);
+            largest = V::select(larger, square, largest);
+            scaled = Self::select(larger, candidate, scaled);
+        }
+        let quat = scaled.normalize();
+        Self::select(quat.w.simd_lt(V::ZERO), -quat, quat)
+    }
+
+    /// Unit quaternions of the rotations of matrices of homogeneous coordinates, see
+    /// [`from_mat3`](Self::from_mat3). The translation and the projection are ignored.
+    #[inline]
+    pub fn from_mat4(matrix: Mat4<V>) -> Self {
+        let [a, b, c, _] = matrix.to_rows();
+        Self::from_mat3(Mat3::from_rows([
+            [a[0], a[1], a[2]],
+            [b[0], b[1], b[2]],
+            [c[0], c[1], c[2]],
+        ]))
+    }
+}
+
+impl<V: SimdFloatMath> Mul for Quat<V> {
+    type Output = Self;
+
+    /// The Hamilton product, which is the rotation by `other` followed by the rotation by `self`.
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        let Self { x, y, z, w } = self;
+        Self::new(
+            w * other.x + x * other.w + y * other.z - z * other.y,
+            w * other.y - x * other.z + y * other.w + z * other.x,
+            w * other.z + x * other.y - y * other.x + z * other.w,
+            w * other.w - x * other.x - y * other.y - z * other.z,
+        )
+    }
+}
+
+impl<V: SimdFloatMath> Mul<Vec3<V>> for Quat<V> {
+    type Output = Vec3<V>;
+
+    /// Rotation of `vector` by the unit quaternions.
+    #[inline]
+    fn mul(self, vector: Vec3<V>) -> Vec3<V> {
+        // `v + 2w(q × v) + 2q × (q × v)`, where `q` is the vector part
+        let xyz = self.xyz();
+        let t = xyz.cross(vector) * V::TWO;
+        vector + t * self.w + xyz.cross(t)
+    }
+}
@@ -0,0 +1,141 @@
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::math::SimdFloatMath;
+
+macro_rules! impl_vector {
+    ($( $(#[$attr: meta])* $name: ident, $dim: literal, [$( $field: ident ),+] );+ $(;)?) => {
+        $(
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        pub struct $name<V> {
+            $( pub $field: V, )+
+        }
+
+        impl<V: SimdFloatMath> $name<V> {
+            /// The vector with all components zero.
+            pub const ZERO: Self = Self { $( $field: V::ZERO ),+ };
+
+            #[inline]
+            pub const fn new($( $field: V ),+) -> Self {
+                Self { $( $field ),+ }
+            }
+
+            #[inline]
+            pub fn from_array([$( $field ),+]: [V; $dim]) -> Self {
+                Self { $( $field ),+ }
+            }
+
+            #[inline]
+            pub fn to_array(self) -> [V; $dim] {
+                [$( self.$field ),+]
+            }
+
+            /// Applies `f` to each component.
+            #[inline]
+            pub fn map(self, mut f: impl FnMut(V) -> V) -> Self {
+                Self { $( $field: f(self.$field) ),+ }
+            }
+
+            #[inline]
+            pub fn dot(self, other: Self) -> V {
+                let products = [$( self.$field * other.$field ),+];
+                products[1..].iter().fold(products[0], |sum, &product| sum + product)
+            }
+
+            #[inline]
+            pub fn length_squared(self) -> V {
+                self.dot(self)
+            }
+
+            #[inline]
+            pub fn length(self) -> V {
+                self.length_squared().sqrt()
+            }
+
+            /// Scales the vectors to the unit length by [`rsqrt`](SimdFloatMath::rsqrt), within a
+            /// few ulps. Zero vectors stay zero.
+            ///
+            /// The components are first divided by the largest absolute one, so the squared
+            /// length neither overflows nor underflows for any finite vector.
+            #[inline]
+            pub fn normalize(self) -> Self {
+                let max = [$( self.$field.abs() ),+]
+                    .into_iter()
+                    .fold(V::ZERO, |max, x| max.simd_max(x));
+                let zero = max.simd_eq(V::ZERO);
+                let scaled = self.map(|x| x / max);
+                let scale = scaled.length_squared().rsqrt();
+                scaled.map(|x| V::select(zero, V::ZERO, x * scale))
+            }
+        }
+
+        impl<V: SimdFloatMath> Add for $name<V> {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, other: Self) -> Self {
+                Self { $( $field: self.$field + other.$field ),+ }
+            }
+        }
+
+        impl<V: SimdFloatMath> Sub for $name<V> {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, other: Self) -> Self {
+                Self { $( $field: self.$field - other.$field ),+ }
+            }
+        }
+
+        impl<V: SimdFloatMath> Neg for $name<V> {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                self.map(Neg::neg)
+            }
+        }
+
+        impl<V: SimdFloatMath> Mul<V> for $name<V> {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, scale: V) -> Self {
+                self.map(|x| x * scale)
+            }
+        }
+
+        impl<V: SimdFloatMath> Div<V> for $name<V> {
+            type Output = Self;
+
+            #[inline]
+            fn div(self, divisor: V) -> Self {
+                self.map(|x| x / divisor)
+            }
+        }
+        )+
+    };
+}
+
+pub(crate) use impl_vector;
+
+impl_vector!(
+    /// Two-dimensional vectors, one per lane of `V`.
+    Vec2, 2, [x, y];
+    /// Three-dimensional vectors, one per lane of `V`.
+    Vec3, 3, [x, y, z];
+    /// Four-dimensional vectors, one per lane of `V`.
+    Vec4, 4, [x, y, z, w];
+);
+
+impl<V: SimdFloatMath> Vec3<V> {
+    /// Cross product, which is orthogonal to both vectors for a right-handed basis.
+    #[inline]
+    pub fn cross(self, other: Self) -> Self {
+        Self {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+}
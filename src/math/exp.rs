@@ -1,3 +1,24 @@
+use crate::math::{
+    util::{LaneMask, Select},
+    FpFlags,
+};
+
+/// Exponential functions of floating-point vectors.
+///
+/// Also implemented for scalar `f32` and `f64` using the same kernels, so the results are
+/// bit-identical to the ones of corresponding vector lanes. Note that inherent methods of `f32`
+/// and `f64` take precedence in method-call syntax, i.e. `x.exp()` calls [`f32::exp`], while
+/// `Exponent::exp(x)` calls this trait's implementation.
+///
+/// # Masked variants
+/// Every function has a `_masked` variant which calculates the function only for lanes where
+/// `mask` is set and returns `fallback` lanes otherwise. Values of inactive lanes are replaced
+/// with zeros before the calculation, so they affect neither the result nor the control flow,
+/// e.g. NaNs in inactive lanes don't trigger special values handling.
+///
+/// # Checked variants
+/// Every function has a `_checked` variant which additionally returns [`FpFlags`] of lanes
+/// which overflowed or underflowed.
 pub trait Exponent {
     /// Returns exponent, i.e. `e^(self)`, of each lane.
     fn exp(self) -> Self;
@@ -5,4 +26,57 @@ pub trait Exponent {
     fn exp2(self) -> Self;
     /// Returns `e^(self) - 1` of each lane.
     fn exp_m1(self) -> Self;
+
+    /// Checked variant of [`exp`](Self::exp).
+    fn exp_checked(self) -> (Self, FpFlags<Self::Mask>)
+    where
+        Self: LaneMask;
+    /// Checked variant of [`exp2`](Self::exp2).
+    fn exp2_checked(self) -> (Self, FpFlags<Self::Mask>)
+    where
+        Self: LaneMask;
+    /// Checked variant of [`exp_m1`](Self::exp_m1).
+    fn exp_m1_checked(self) -> (Self, FpFlags<Self::Mask>)
+    where
+        Self: LaneMask;
+
+    /// Masked variant of [`exp`](Self::exp).
+    ///
+    /// # Examples
+    /// ```
+    /// # #![feature(portable_simd)]
+    /// # use std::simd::prelude::*;
+    /// # use simd_addons::math::*;
+    /// let x = f32x4::from_array([0.0, f32::NAN, 1.0, 1e6]);
+    /// let mask = mask32x4::from_array([true, false, true, false]);
+    /// assert_eq!(
+    ///     x.exp_masked(mask, f32x4::splat(-1.0)),
+    ///     f32x4::from_array([1.0, -1.0, Exponent::exp(1.0), -1.0])
+    /// );
+    /// ```
+    #[inline]
+    fn exp_masked<M: Select<Self>>(self, mask: M, fallback: Self) -> Self
+    where
+        Self: Default + Sized,
+    {
+        mask.select(mask.select(self, Self::default()).exp(), fallback)
+    }
+
+    /// Masked variant of [`exp2`](Self::exp2).
+    #[inline]
+    fn exp2_masked<M: Select<Self>>(self, mask: M, fallback: Self) -> Self
+    where
+        Self: Default + Sized,
+    {
+        mask.select(mask.select(self, Self::default()).exp2(), fallback)
+    }
+
+    /// Masked variant of [`exp_m1`](Self::exp_m1).
+    #[inline]
+    fn exp_m1_masked<M: Select<Self>>(self, mask: M, fallback: Self) -> Self
+    where
+        Self: Default + Sized,
+    {
+        mask.select(mask.select(self, Self::default()).exp_m1(), fallback)
+    }
 }
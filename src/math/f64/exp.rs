@@ -1,11 +1,13 @@
-use std::{
-    f64::consts::{LN_2, LOG2_E},
-    simd::{prelude::*, LaneCount, StdFloat, SupportedLaneCount},
-};
+use core::f64::consts::{LN_2, LOG2_E};
 
 use crate::{
-    math::{util::FastRound, Exponent},
+    math::{
+        ops::CoreFloat,
+        util::{FastRound, LaneMask},
+        Exponent, FpFlags,
+    },
     polynomial_simd,
+    simd::{prelude::*, LaneCount, SupportedLaneCount},
 };
 
 fn exp_m1_taylor<const N: usize>(x: Simd<f64, N>) -> Simd<f64, N>
@@ -42,7 +44,7 @@ where
     )
 }
 
-/// Returns reduced argument `r` and an integer power of 2.0 `n`, s.t. `exp(x) = exp(r) * n`.
+/// Returns reduced argument `r` and an integer `n`, s.t. `exp(x) = exp(r) * 2ⁿ`.
 #[inline]
 fn exp_arg_reduction<const N: usize>(x: Simd<f64, N>) -> (Simd<f64, N>, Simd<f64, N>)
 where
@@ -54,16 +56,16 @@ where
 
     let n = (x * Simd::splat(LOG2_E)).fast_round();
     let reduced_x = n.mul_add(Simd::splat(-LN2_LO), n.mul_add(Simd::splat(-LN2_HI), x));
-    (reduced_x, pow2i(n))
+    (reduced_x, n)
 }
 
 // using macro instead of function here because `f64` cannot be passed as a generic parameter
 macro_rules! exp_handle_overflow_and_special {
-    ($LIMIT: literal, $NEGINF_VAL: literal, $x: expr, $exp: expr) => {{
+    ($LIMIT: literal, $NEGINF_VAL: literal, $x: expr, $exp: expr, $underflow: expr) => {{
         let x = $x;
         let exp = $exp;
         let in_range = x.abs().simd_le(Simd::splat($LIMIT));
-        if in_range.all() {
+        let result = if in_range.all() {
             exp
         } else {
             in_range.select(
@@ -74,45 +76,148 @@ macro_rules! exp_handle_overflow_and_special {
                         .select(Simd::splat(f64::INFINITY), Simd::splat($NEGINF_VAL)),
                 ),
             )
-        }
+        };
+        // finite lanes out of range are the ones replaced by infinity or `NEGINF_VAL`, the latter
+        // underflows if it is zero, and lanes in range underflow by `underflow`
+        let out_of_range = !in_range & x.is_finite();
+        let flags = FpFlags {
+            overflow: out_of_range & x.is_sign_positive(),
+            underflow: (in_range & $underflow)
+                | (out_of_range & x.is_sign_negative() & Mask::splat($NEGINF_VAL == 0.0)),
+            invalid: Mask::default(),
+            divide_by_zero: Mask::default(),
+        };
+        (result, flags)
     }};
 }
 
+/// Lanes of `exp(r) * 2ⁿ` which aren't normal, as `exp(r)` is close to 1: the ones with `n` below
+/// the exponent of [`MIN_POSITIVE`](f64::MIN_POSITIVE), or equal to it and `r < 0`.
+#[inline]
+fn scale_underflow<const N: usize>(r: Simd<f64, N>, n: Simd<f64, N>) -> Mask<i64, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    const MIN_EXP: f64 = (f64::MIN_EXP - 1) as f64;
+    n.simd_lt(Simd::splat(MIN_EXP)) | (n.simd_eq(Simd::splat(MIN_EXP)) & r.simd_lt(Simd::default()))
+}
+
+#[inline]
+fn exp_with_flags<const N: usize>(x: Simd<f64, N>) -> (Simd<f64, N>, FpFlags<Mask<i64, N>>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let (reduced, n) = exp_arg_reduction(x);
+    exp_handle_overflow_and_special!(
+        709.0,
+        0.0,
+        x,
+        (exp_m1_taylor(reduced) + Simd::splat(1.0)) * pow2i(n),
+        scale_underflow(reduced, n)
+    )
+}
+
+#[inline]
+fn exp2_with_flags<const N: usize>(x: Simd<f64, N>) -> (Simd<f64, N>, FpFlags<Mask<i64, N>>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let n = x.fast_round();
+    let reduced = (x - n) * Simd::splat(LN_2);
+    exp_handle_overflow_and_special!(
+        1023.0,
+        0.0,
+        x,
+        (exp_m1_taylor(reduced) + Simd::splat(1.0)) * pow2i(n),
+        scale_underflow(reduced, n)
+    )
+}
+
+#[inline]
+fn exp_m1_with_flags<const N: usize>(x: Simd<f64, N>) -> (Simd<f64, N>, FpFlags<Mask<i64, N>>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let (reduced, n) = exp_arg_reduction(x);
+    let scale = pow2i(n);
+    // `r = x` for `n = 0`, then the result is as tiny as `x`
+    let tiny =
+        reduced.simd_ne(Simd::default()) & reduced.abs().simd_lt(Simd::splat(f64::MIN_POSITIVE));
+    exp_handle_overflow_and_special!(
+        709.0,
+        -1.0,
+        x,
+        exp_m1_taylor(reduced).mul_add(scale, scale - Simd::splat(1.0)),
+        n.simd_eq(Simd::default()) & tiny
+    )
+}
+
 impl<const N: usize> Exponent for Simd<f64, N>
 where
     LaneCount<N>: SupportedLaneCount,
 {
     #[inline]
     fn exp(self) -> Self {
-        let (reduced, n) = exp_arg_reduction(self);
-        exp_handle_overflow_and_special!(
-            709.0,
-            0.0,
-            self,
-            (exp_m1_taylor(reduced) + Simd::splat(1.0)) * n
-        )
+        exp_with_flags(self).0
     }
 
     #[inline]
     fn exp2(self) -> Self {
-        let r = self.fast_round();
-        let reduced = (self - r) * Simd::splat(LN_2);
-        exp_handle_overflow_and_special!(
-            1023.0,
-            0.0,
-            self,
-            (exp_m1_taylor(reduced) + Simd::splat(1.0)) * pow2i(r)
-        )
+        exp2_with_flags(self).0
     }
 
     #[inline]
     fn exp_m1(self) -> Self {
-        let (reduced, n) = exp_arg_reduction(self);
-        exp_handle_overflow_and_special!(
-            709.0,
-            -1.0,
-            self,
-            exp_m1_taylor(reduced).mul_add(n, n - Simd::splat(1.0))
-        )
+        exp_m1_with_flags(self).0
+    }
+
+    #[inline]
+    fn exp_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        exp_with_flags(self)
+    }
+
+    #[inline]
+    fn exp2_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        exp2_with_flags(self)
+    }
+
+    #[inline]
+    fn exp_m1_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        exp_m1_with_flags(self)
+    }
+}
+
+impl Exponent for f64 {
+    #[inline]
+    fn exp(self) -> Self {
+        Exponent::exp(Simd::<f64, 1>::splat(self))[0]
+    }
+
+    #[inline]
+    fn exp2(self) -> Self {
+        Exponent::exp2(Simd::<f64, 1>::splat(self))[0]
+    }
+
+    #[inline]
+    fn exp_m1(self) -> Self {
+        Exponent::exp_m1(Simd::<f64, 1>::splat(self))[0]
+    }
+
+    #[inline]
+    fn exp_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        let (exp, flags) = Exponent::exp_checked(Simd::<f64, 1>::splat(self));
+        (exp[0], flags.map(|mask| mask.test(0)))
+    }
+
+    #[inline]
+    fn exp2_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        let (exp2, flags) = Exponent::exp2_checked(Simd::<f64, 1>::splat(self));
+        (exp2[0], flags.map(|mask| mask.test(0)))
+    }
+
+    #[inline]
+    fn exp_m1_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        let (exp_m1, flags) = Exponent::exp_m1_checked(Simd::<f64, 1>::splat(self));
+        (exp_m1[0], flags.map(|mask| mask.test(0)))
     }
 }
@@ -1,45 +1,56 @@
-use std::{
-    f64::consts::*,
-    simd::{prelude::*, LaneCount, StdFloat, SupportedLaneCount},
-};
+use core::f64::consts::*;
 
 use crate::{
-    math::{util::FloatBitUtils, Trigonometry},
+    math::{
+        ops::{CoreFloat, FUSED_MUL_ADD},
+        util::{FloatBitUtils, LaneMask, Select},
+        FpFlags, Trigonometry,
+    },
     polynomial_simd,
+    simd::{prelude::*, LaneCount, SupportedLaneCount},
 };
 
+// TODO: TRIG_INPUT_LIMIT should be larger
+const TRIG_INPUT_LIMIT: f64 = 1e13;
+
+/// π/4 reduction of `x` and the mask of invalid lanes. Large values (greater than 10¹³) and
+/// infinities are treated as zeros and are invalid, NaNs are treated as zeros too.
 #[inline]
-fn trig_reduction<const N: usize>(x: Simd<f64, N>) -> (Simd<f64, N>, Simd<u64, N>)
+fn trig_reduction<const N: usize>(x: Simd<f64, N>) -> (Simd<f64, N>, Simd<u64, N>, Mask<i64, N>)
 where
     LaneCount<N>: SupportedLaneCount,
 {
-    // TODO: INPUT_LIMIT should be larger
-    const INPUT_LIMIT: f64 = 1e13;
-
     // src: https://github.com/vectorclass/version2/blob/master/vectormath_trig.h#L64-L66
     const PI2_A: f64 = 7.853_981_554_508_209E-1 * 2.;
     const PI2_B: f64 = 7.946_627_356_147_928E-9 * 2.;
     const PI2_C: f64 = 3.061_616_997_868_383E-17 * 2.;
 
     let mut abs_x = x.abs();
-    // NaNs, INFs and large values are mapped to 0
-    abs_x = abs_x
-        .simd_lt(Simd::splat(INPUT_LIMIT))
-        .select(abs_x, Simd::default());
+    // NaNs, INFs and large values are mapped to 0, all but NaNs are invalid
+    let in_range = abs_x.simd_lt(Simd::splat(TRIG_INPUT_LIMIT));
+    abs_x = in_range.select(abs_x, Simd::default());
+    let invalid = !(in_range | x.is_nan());
     // (abs_x * Simd::splat(FRAC_2_PI)).round() generates more instruntions and hence is slower
     let quadrants_float = abs_x
         .mul_add(Simd::splat(FRAC_2_PI), Simd::splat(0.5))
         .trunc();
 
-    // SAFETY: INPUT_LIMIT guarantees that values in `quadrants_float` are representable in u64
+    // SAFETY: TRIG_INPUT_LIMIT guarantees that values in `quadrants_float` are representable in u64
     let quadrants = unsafe { quadrants_float.to_int_unchecked::<i64>().cast() };
 
-    let reduced_x = quadrants_float.mul_add(
-        Simd::splat(-PI2_C),
-        quadrants_float.mul_add(Simd::splat(-PI2_B - PI2_A), abs_x),
-    );
+    let reduced_x = if FUSED_MUL_ADD {
+        quadrants_float.mul_add(
+            Simd::splat(-PI2_C),
+            quadrants_float.mul_add(Simd::splat(-PI2_B - PI2_A), abs_x),
+        )
+    } else {
+        // products by `PI2_A` and `PI2_B` are exact, so no precision is lost without FMA
+        let reduced_x = quadrants_float.mul_add(Simd::splat(-PI2_A), abs_x);
+        let reduced_x = quadrants_float.mul_add(Simd::splat(-PI2_B), reduced_x);
+        quadrants_float.mul_add(Simd::splat(-PI2_C), reduced_x)
+    };
 
-    (reduced_x, quadrants)
+    (reduced_x, quadrants, invalid)
 }
 
 #[inline]
@@ -93,52 +104,106 @@ where
         .mul_add(t * t2, t)
 }
 
+/// Sine of `x` and the flags of [`trig_reduction`].
+#[inline]
+fn sin_with_flags<const N: usize>(x: Simd<f64, N>) -> (Simd<f64, N>, FpFlags<Mask<i64, N>>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let (reduced_x, quadrants, invalid) = trig_reduction(x);
+    let (sin, cos) = sin_cos_taylor(reduced_x);
+
+    let sin_cos_swap = (quadrants & Simd::splat(1)).simd_eq(Simd::default());
+    let sin_vals = sin_cos_swap.select(sin, cos);
+    let sin = sin_vals.sign_combine(Simd::from_bits(x.to_bits() ^ (quadrants << 62)));
+    let flags = FpFlags {
+        invalid,
+        ..Default::default()
+    };
+    (sin, flags)
+}
+
+/// Cosine of `x` and the flags of [`trig_reduction`].
+#[inline]
+fn cos_with_flags<const N: usize>(x: Simd<f64, N>) -> (Simd<f64, N>, FpFlags<Mask<i64, N>>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let (reduced_x, quadrants, invalid) = trig_reduction(x);
+    let (sin, cos) = sin_cos_taylor(reduced_x);
+
+    let sin_cos_swap = (quadrants & Simd::splat(1)).simd_eq(Simd::default());
+    let cos_vals = sin_cos_swap.select(cos, sin);
+    let cos = cos_vals.sign_combine(Simd::from_bits((quadrants + Simd::splat(1)) << 62));
+    let flags = FpFlags {
+        invalid,
+        ..Default::default()
+    };
+    (cos, flags)
+}
+
+/// Tangent of `x` and the flags of [`trig_reduction`].
+#[inline]
+fn tan_with_flags<const N: usize>(x: Simd<f64, N>) -> (Simd<f64, N>, FpFlags<Mask<i64, N>>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    // src: https://github.com/vectorclass/version2/blob/master/vectormath_trig.h#L444-L451
+    const P0: f64 = -1.795_652_519_764_848_8E7;
+    const P1: f64 = 1.153_516_648_385_874_2E6;
+    const P2: f64 = -1.309_369_391_813_837_9E4;
+
+    const Q0: f64 = -5.386_957_559_294_546_4E7;
+    const Q1: f64 = 2.500_838_018_233_579E7;
+    const Q2: f64 = -1.320_892_344_402_109_7E6;
+    const Q3: f64 = 1.368_129_634_706_929_6E4;
+
+    let (reduced_x, quadrants, invalid) = trig_reduction(x);
+    let reduced_x2 = reduced_x * reduced_x;
+    let tan_vals = (polynomial_simd!(reduced_x2; P0, P1, P2)
+        / polynomial_simd!(reduced_x2; Q0, Q1, Q2, Q3, 1.0))
+    .mul_add(reduced_x * reduced_x2, reduced_x);
+
+    let tan = (quadrants & Simd::splat(1))
+        .simd_eq(Simd::default())
+        .select(tan_vals, -tan_vals.recip())
+        .sign_combine(x);
+    let flags = FpFlags {
+        invalid,
+        ..Default::default()
+    };
+    (tan, flags)
+}
+
+/// Inputs outside of `[-1, 1]` are invalid.
+#[inline]
+fn asin_fp_flags<const N: usize>(x: Simd<f64, N>) -> FpFlags<Mask<i64, N>>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    FpFlags {
+        invalid: x.abs().simd_gt(Simd::splat(1.0)),
+        ..Default::default()
+    }
+}
+
 impl<const N: usize> Trigonometry for Simd<f64, N>
 where
     LaneCount<N>: SupportedLaneCount,
 {
     #[inline]
     fn sin(self) -> Self {
-        let (reduced_x, quadrants) = trig_reduction(self);
-        let (sin, cos) = sin_cos_taylor(reduced_x);
-
-        let sin_cos_swap = (quadrants & Simd::splat(1)).simd_eq(Simd::default());
-        let sin_vals = sin_cos_swap.select(sin, cos);
-        sin_vals.sign_combine(Simd::from_bits(self.to_bits() ^ (quadrants << 62)))
+        sin_with_flags(self).0
     }
 
     #[inline]
     fn cos(self) -> Self {
-        let (reduced_x, quadrants) = trig_reduction(self);
-        let (sin, cos) = sin_cos_taylor(reduced_x);
-
-        let sin_cos_swap = (quadrants & Simd::splat(1)).simd_eq(Simd::default());
-        let cos_vals = sin_cos_swap.select(cos, sin);
-        cos_vals.sign_combine(Simd::from_bits((quadrants + Simd::splat(1)) << 62))
+        cos_with_flags(self).0
     }
 
     #[inline]
     fn tan(self) -> Self {
-        // src: https://github.com/vectorclass/version2/blob/master/vectormath_trig.h#L444-L451
-        const P0: f64 = -1.795_652_519_764_848_8E7;
-        const P1: f64 = 1.153_516_648_385_874_2E6;
-        const P2: f64 = -1.309_369_391_813_837_9E4;
-
-        const Q0: f64 = -5.386_957_559_294_546_4E7;
-        const Q1: f64 = 2.500_838_018_233_579E7;
-        const Q2: f64 = -1.320_892_344_402_109_7E6;
-        const Q3: f64 = 1.368_129_634_706_929_6E4;
-
-        let (reduced_x, quadrants) = trig_reduction(self);
-        let reduced_x2 = reduced_x * reduced_x;
-        let tan_vals = (polynomial_simd!(reduced_x2; P0, P1, P2)
-            / polynomial_simd!(reduced_x2; Q0, Q1, Q2, Q3, 1.0))
-        .mul_add(reduced_x * reduced_x2, reduced_x);
-
-        (quadrants & Simd::splat(1))
-            .simd_eq(Simd::default())
-            .select(tan_vals, -tan_vals.recip())
-            .sign_combine(self)
+        tan_with_flags(self).0
     }
 
     fn asin(self) -> Self {
@@ -199,31 +264,178 @@ where
     }
 
     fn atan2(self, x: Self) -> Self {
-        let abs_y = self.abs();
-        let abs_x = x.abs();
-
-        let not_big = (abs_x * Simd::splat(SQRT_2 + 1.0)).simd_ge(abs_y);
-        let not_small = (abs_x * Simd::splat(0.66)).simd_le(abs_y);
-        let reduced_ratio = {
+        // the ratio is reduced to `y / x`, `(y - x) / (x + y)` or `-x / y` depending on the octant
+        let reduction = |abs_y: Self, abs_x: Self| {
+            let not_big = (abs_x * Simd::splat(SQRT_2 + 1.0)).simd_ge(abs_y);
+            // multiplication by a factor below 1 would underflow for subnormal `x`
+            let not_small = (abs_y * Simd::splat(1.0 / 0.66)).simd_ge(abs_x);
             let a =
                 not_big.select(abs_y, Simd::default()) - not_small.select(abs_x, Simd::default());
             let b =
                 not_big.select(abs_x, Simd::default()) + not_small.select(abs_y, Simd::default());
-            (abs_y.is_finite() | abs_x.is_finite()).select(a / b, Simd::default())
+            (not_big, not_small, a, b)
         };
+        let (abs_y, abs_x) = (self.abs(), x.abs());
+        let (mut not_big, mut not_small, mut a, mut b) = reduction(abs_y, abs_x);
+        let infinite = b.simd_eq(Simd::splat(f64::INFINITY));
+        if infinite.any() {
+            // the ratio is invariant under scaling, which prevents overflows near `MAX`, and `∞/∞`
+            // is NaN, but the angle is π/4
+            let scale = infinite.select(Simd::splat(0.25), Simd::splat(1.0));
+            (not_big, not_small, a, b) = reduction(abs_y * scale, abs_x * scale);
+            a = (abs_y.is_infinite() & abs_x.is_infinite()).select(Simd::default(), a);
+        }
+        let reduced_ratio = a / b;
         let pade_result = atan_pade(reduced_ratio);
         let mut atan_abs = pade_result
             + not_small.select(
                 not_big.select(Simd::splat(FRAC_PI_4), Simd::splat(FRAC_PI_2)),
                 Simd::default(),
             );
-        // fix NaNs when x and y are both zeros
+        // fix NaNs when x and y are both zeros, the signs are restored below
         atan_abs = Simd::from_bits(x.to_bits() | self.to_bits())
             .simd_eq(Simd::<f64, N>::default())
-            .select(Simd::from_bits(x.to_bits() ^ self.to_bits()), atan_abs);
+            .select(Simd::default(), atan_abs);
         x.sign_bit()
             .simd_eq(Simd::default())
             .select(atan_abs, Simd::splat(PI) - atan_abs)
             .sign_combine(self)
     }
+
+    #[inline]
+    fn sin_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        sin_with_flags(self)
+    }
+
+    #[inline]
+    fn cos_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        cos_with_flags(self)
+    }
+
+    #[inline]
+    fn tan_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        tan_with_flags(self)
+    }
+
+    #[inline]
+    fn tan_masked<M: Select<Self>>(self, mask: M, fallback: Self) -> Self {
+        mask.select(mask.select(self, Self::default()).tan(), fallback)
+    }
+
+    #[inline]
+    fn sin_cos_checked(self) -> ((Self, Self), FpFlags<<Self as LaneMask>::Mask>) {
+        let (sin, flags) = sin_with_flags(self);
+        ((sin, Trigonometry::cos(self)), flags)
+    }
+
+    #[inline]
+    fn asin_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        (self.asin(), asin_fp_flags(self))
+    }
+
+    #[inline]
+    fn acos_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        (self.acos(), asin_fp_flags(self))
+    }
+}
+
+impl Trigonometry for f64 {
+    #[inline]
+    fn sin(self) -> Self {
+        Trigonometry::sin(Simd::<f64, 1>::splat(self))[0]
+    }
+
+    #[inline]
+    fn cos(self) -> Self {
+        Trigonometry::cos(Simd::<f64, 1>::splat(self))[0]
+    }
+
+    #[inline]
+    fn tan(self) -> Self {
+        Trigonometry::tan(Simd::<f64, 1>::splat(self))[0]
+    }
+
+    #[inline]
+    fn sin_cos(self) -> (Self, Self) {
+        let (sin, cos) = Trigonometry::sin_cos(Simd::<f64, 1>::splat(self));
+        (sin[0], cos[0])
+    }
+
+    #[inline]
+    fn asin(self) -> Self {
+        Trigonometry::asin(Simd::<f64, 1>::splat(self))[0]
+    }
+
+    #[inline]
+    fn acos(self) -> Self {
+        Trigonometry::acos(Simd::<f64, 1>::splat(self))[0]
+    }
+
+    #[inline]
+    fn atan(self) -> Self {
+        Trigonometry::atan(Simd::<f64, 1>::splat(self))[0]
+    }
+
+    #[inline]
+    fn atan2(self, x: Self) -> Self {
+        Trigonometry::atan2(Simd::<f64, 1>::splat(self), Simd::splat(x))[0]
+    }
+
+    #[inline]
+    fn sin_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        let (sin, flags) = Trigonometry::sin_checked(Simd::<f64, 1>::splat(self));
+        (sin[0], flags.map(|mask| mask.test(0)))
+    }
+
+    #[inline]
+    fn cos_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        let (cos, flags) = Trigonometry::cos_checked(Simd::<f64, 1>::splat(self));
+        (cos[0], flags.map(|mask| mask.test(0)))
+    }
+
+    #[inline]
+    fn tan_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        let (tan, flags) = Trigonometry::tan_checked(Simd::<f64, 1>::splat(self));
+        (tan[0], flags.map(|mask| mask.test(0)))
+    }
+
+    #[inline]
+    fn tan_masked<M: Select<Self>>(self, mask: M, fallback: Self) -> Self {
+        // with `std` linked, the inherent `tan` would be called instead
+        mask.select(
+            Trigonometry::tan(mask.select(self, Self::default())),
+            fallback,
+        )
+    }
+
+    #[inline]
+    fn sin_cos_checked(self) -> ((Self, Self), FpFlags<<Self as LaneMask>::Mask>) {
+        let ((sin, cos), flags) = Trigonometry::sin_cos_checked(Simd::<f64, 1>::splat(self));
+        ((sin[0], cos[0]), flags.map(|mask| mask.test(0)))
+    }
+
+    #[inline]
+    fn asin_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        let (asin, flags) = Trigonometry::asin_checked(Simd::<f64, 1>::splat(self));
+        (asin[0], flags.map(|mask| mask.test(0)))
+    }
+
+    #[inline]
+    fn acos_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        let (acos, flags) = Trigonometry::acos_checked(Simd::<f64, 1>::splat(self));
+        (acos[0], flags.map(|mask| mask.test(0)))
+    }
+
+    #[inline]
+    fn atan_checked(self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        let (atan, flags) = Trigonometry::atan_checked(Simd::<f64, 1>::splat(self));
+        (atan[0], flags.map(|mask| mask.test(0)))
+    }
+
+    #[inline]
+    fn atan2_checked(self, x: Self) -> (Self, FpFlags<<Self as LaneMask>::Mask>) {
+        let (atan2, flags) =
+            Trigonometry::atan2_checked(Simd::<f64, 1>::splat(self), Simd::splat(x));
+        (atan2[0], flags.map(|mask| mask.test(0)))
+    }
 }
@@ -0,0 +1,56 @@
+use core::ops::BitOr;
+
+/// Floating-point exceptions raised by lanes of a math function, returned by its `_checked`
+/// variant. Each field is a mask of lanes which raised the corresponding IEEE 754 condition.
+///
+/// `M` is [`Mask`](core::simd::Mask) for vectors and `bool` for scalars.
+///
+/// # Examples
+/// ```
+/// # #![feature(portable_simd)]
+/// # use std::simd::prelude::*;
+/// # use simd_addons::math::*;
+/// let (exp, flags) = f32x4::from_array([1.0, 100.0, -100.0, f32::INFINITY]).exp_checked();
+/// assert_eq!(exp[1], f32::INFINITY);
+/// assert_eq!(flags.overflow, mask32x4::from_array([false, true, false, false]));
+/// assert_eq!(flags.underflow, mask32x4::from_array([false, false, true, false]));
+/// assert!(!flags.invalid.any());
+/// assert!(!flags.divide_by_zero.any());
+///
+/// let (_, flags) = Trigonometry::asin_checked(2.0f64);
+/// assert!(flags.invalid);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FpFlags<M> {
+    /// Finite input produced a result too large to be represented, i.e. infinity.
+    pub overflow: M,
+    /// Nonzero finite input produced a result too small to be represented as a normal value.
+    pub underflow: M,
+    /// Input is outside of the domain of the function, e.g. `asin(2.0)` or `sin(∞)`.
+    pub invalid: M,
+    /// Finite input produced an exactly infinite result at a pole. None of the functions has a pole
+    /// at a representable input, so it's never set.
+    pub divide_by_zero: M,
+}
+
+impl<M> FpFlags<M> {
+    /// Applies `f` to each of the masks.
+    #[inline]
+    pub fn map<U>(self, mut f: impl FnMut(M) -> U) -> FpFlags<U> {
+        FpFlags {
+            overflow: f(self.overflow),
+            underflow: f(self.underflow),
+            invalid: f(self.invalid),
+            divide_by_zero: f(self.divide_by_zero),
+        }
+    }
+
+    /// Returns mask of lanes which raised any of the conditions.
+    #[inline]
+    pub fn any(self) -> M
+    where
+        M: BitOr<Output = M>,
+    {
+        self.overflow | self.underflow | self.invalid | self.divide_by_zero
+    }
+}
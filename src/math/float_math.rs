@@ -0,0 +1,260 @@
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+use crate::{
+    math::{
+        ops::CoreFloat,
+        util::{FastRound, FloatBitUtils, LaneMask, Select},
+        Exponent, Trigonometry,
+    },
+    simd::{
+        cmp::{SimdPartialEq, SimdPartialOrd},
+        num::{SimdFloat, SimdInt, SimdUint},
+        LaneCount, Mask, Simd, SimdElement, SupportedLaneCount,
+    },
+};
+
+/// Umbrella trait for vectors of floats which unites all math capabilities of the crate.
+///
+/// Intended to be used as a single bound in generic code over both `f32` and `f64` vectors.
+/// Associated types and constants of this trait share names with ones of [`SimdFloat`], so they
+/// have to be qualified, e.g. `<V as SimdFloatMath>::Mask`.
+///
+/// [`StdFloat`] is not a supertrait since its `exp`, `sin`, etc. would clash with the ones of
+/// [`Exponent`] and [`Trigonometry`]. Its remaining methods are provided by this trait instead,
+/// so they are available without `std` too.
+///
+/// [`StdFloat`]: https://doc.rust-lang.org/std/simd/trait.StdFloat.html
+///
+/// # Examples
+/// ```
+/// # #![feature(portable_simd)]
+/// # use std::simd::prelude::*;
+/// # use simd_addons::math::*;
+/// // sigmoid(x) = 1 / (1 + e^(-x))
+/// fn sigmoid<V: SimdFloatMath>(x: V) -> V {
+///     V::ONE / (V::ONE + (-x).exp())
+/// }
+///
+/// fn hypot<V: SimdFloatMath>(x: V, y: V) -> V {
+///     x.mul_add(x, y * y).sqrt()
+/// }
+///
+/// fn sin_or_zero<V: SimdFloatMath>(x: V) -> V {
+///     V::select(x.abs().simd_le(V::FRAC_PI_2), x.sin(), V::ZERO)
+/// }
+///
+/// assert_eq!(sigmoid(f32x4::splat(0.0)), f32x4::splat(0.5));
+/// assert_eq!(sigmoid(f64x8::splat(0.0)), f64x8::splat(0.5));
+/// assert_eq!(hypot(f32x4::splat(3.0), f32x4::splat(4.0)), f32x4::splat(5.0));
+/// assert_eq!(
+///     sin_or_zero(f32x4::from_array([0.0, 3.0, -1.0, f32::NAN])),
+///     f32x4::from_array([0.0, 0.0, Trigonometry::sin(-1.0), 0.0])
+/// );
+/// ```
+pub trait SimdFloatMath:
+    Exponent
+    + Trigonometry
+    + FastRound
+    + FloatBitUtils<UnsignedBits = <Self as SimdFloatMath>::UInt>
+    + LaneMask<Mask = <Self as SimdFloatMath>::Mask>
+    + SimdFloat<
+        Scalar = <Self as SimdFloatMath>::Scalar,
+        Bits = <Self as SimdFloatMath>::UInt,
+        Mask = <Self as SimdFloatMath>::Mask,
+    > + SimdPartialOrd<Mask = <Self as SimdFloatMath>::Mask>
+    + Copy
+    + Default
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    /// Type of a single lane, i.e. `f32` or `f64`.
+    type Scalar: SimdElement + Copy;
+    /// Vector of signed integers with the same lane width, e.g. `Simd<i32, N>` for `Simd<f32, N>`.
+    type Int: SimdInt<Mask = <Self as SimdFloatMath>::Mask>;
+    /// Vector of unsigned integers with the same lane width, e.g. `Simd<u32, N>` for
+    /// `Simd<f32, N>`.
+    type UInt: SimdUint;
+    /// Mask returned by lane-wise comparisons of `Self`.
+    type Mask: Select<Self>;
+
+    /// Number of lanes.
+    const LANES: usize;
+
+    const ZERO: Self;
+    const HALF: Self;
+    const ONE: Self;
+    const TWO: Self;
+    const NAN: Self;
+    const INFINITY: Self;
+    const NEG_INFINITY: Self;
+    const MIN: Self;
+    const MAX: Self;
+    const MIN_POSITIVE: Self;
+    const EPSILON: Self;
+
+    const PI: Self;
+    const TAU: Self;
+    const FRAC_PI_2: Self;
+    const FRAC_PI_4: Self;
+    const E: Self;
+    const LN_2: Self;
+    const LN_10: Self;
+    const LOG2_E: Self;
+    const SQRT_2: Self;
+
+    /// Constructs a vector with all lanes set to `value`.
+    fn splat(value: <Self as SimdFloatMath>::Scalar) -> Self;
+
+    /// Chooses lanes from `true_values` where `mask` is set and from `false_values` otherwise.
+    fn select(mask: <Self as SimdFloatMath>::Mask, true_values: Self, false_values: Self) -> Self;
+
+    /// Fused multiply-add, i.e. `self * a + b` with a single rounding.
+    fn mul_add(self, a: Self, b: Self) -> Self;
+    fn sqrt(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn trunc(self) -> Self;
+    fn fract(self) -> Self;
+
+    /// Reciprocal square root, i.e. `1 / sqrt(self)`, within a few ulps of the exact one.
+    ///
+    /// Computed by Newton's iterations from an estimate made of the bits of `self`, which is
+    /// usually faster than a square root and a division. Subnormal numbers are scaled before the
+    /// estimate, so they are as accurate as the normal ones. Zeros give infinities of the same
+    /// sign, infinity gives zero, negative numbers and NaN give NaN.
+    ///
+    /// # Examples
+    /// ```
+    /// # #![feature(portable_simd)]
+    /// # use std::simd::prelude::*;
+    /// # use simd_addons::math::*;
+    /// let x = f32x4::from_array([1.0, 4.0, 0.25, 2.0]).rsqrt();
+    /// let expected = f32x4::from_array([1.0, 0.5, 2.0, std::f32::consts::FRAC_1_SQRT_2]);
+    /// assert!(((x - expected) / expected).abs().simd_lt(f32x4::splat(4.0 * f32::EPSILON)).all());
+    /// ```
+    fn rsqrt(self) -> Self;
+}
+
+macro_rules! impl_simd_float_math {
+    ($ftype: ident, $itype: ty, $utype: ty, $magic: literal, $iterations: literal, $subnormal_shift: literal) => {
+        impl<const N: usize> SimdFloatMath for Simd<$ftype, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+        {
+            type Scalar = $ftype;
+            type Int = Simd<$itype, N>;
+            type UInt = Simd<$utype, N>;
+            type Mask = Mask<$itype, N>;
+
+            const LANES: usize = N;
+
+            const ZERO: Self = Simd::from_array([0.0; N]);
+            const HALF: Self = Simd::from_array([0.5; N]);
+            const ONE: Self = Simd::from_array([1.0; N]);
+            const TWO: Self = Simd::from_array([2.0; N]);
+            const NAN: Self = Simd::from_array([$ftype::NAN; N]);
+            const INFINITY: Self = Simd::from_array([$ftype::INFINITY; N]);
+            const NEG_INFINITY: Self = Simd::from_array([$ftype::NEG_INFINITY; N]);
+            const MIN: Self = Simd::from_array([$ftype::MIN; N]);
+            const MAX: Self = Simd::from_array([$ftype::MAX; N]);
+            const MIN_POSITIVE: Self = Simd::from_array([$ftype::MIN_POSITIVE; N]);
+            const EPSILON: Self = Simd::from_array([$ftype::EPSILON; N]);
+
+            const PI: Self = Simd::from_array([core::$ftype::consts::PI; N]);
+            const TAU: Self = Simd::from_array([core::$ftype::consts::TAU; N]);
+            const FRAC_PI_2: Self = Simd::from_array([core::$ftype::consts::FRAC_PI_2; N]);
+            const FRAC_PI_4: Self = Simd::from_array([core::$ftype::consts::FRAC_PI_4; N]);
+            const E: Self = Simd::from_array([core::$ftype::consts::E; N]);
+            const LN_2: Self = Simd::from_array([core::$ftype::consts::LN_2; N]);
+            const LN_10: Self = Simd::from_array([core::$ftype::consts::LN_10; N]);
+            const LOG2_E: Self = Simd::from_array([core::$ftype::consts::LOG2_E; N]);
+            const SQRT_2: Self = Simd::from_array([core::$ftype::consts::SQRT_2; N]);
+
+            #[inline]
+            fn splat(value: $ftype) -> Self {
+                Simd::splat(value)
+            }
+
+            #[inline]
+            fn select(mask: Mask<$itype, N>, true_values: Self, false_values: Self) -> Self {
+                mask.select(true_values, false_values)
+            }
+
+            #[inline]
+            fn mul_add(self, a: Self, b: Self) -> Self {
+                CoreFloat::mul_add(self, a, b)
+            }
+
+            #[inline]
+            fn sqrt(self) -> Self {
+                CoreFloat::sqrt(self)
+            }
+
+            #[inline]
+            fn floor(self) -> Self {
+                CoreFloat::floor(self)
+            }
+
+            #[inline]
+            fn ceil(self) -> Self {
+                CoreFloat::ceil(self)
+            }
+
+            #[inline]
+            fn round(self) -> Self {
+                CoreFloat::round(self)
+            }
+
+            #[inline]
+            fn trunc(self) -> Self {
+                CoreFloat::trunc(self)
+            }
+
+            #[inline]
+            fn fract(self) -> Self {
+                CoreFloat::fract(self)
+            }
+
+            #[inline]
+            fn rsqrt(self) -> Self {
+                // subnormal numbers are scaled into normal ones by an even power of two, whose
+                // root is exact
+                const SCALE: $ftype = (1u64 << $subnormal_shift) as $ftype;
+                const ROOT_SCALE: $ftype = (1u64 << ($subnormal_shift / 2)) as $ftype;
+                let subnormal = self.simd_lt(Simd::splat($ftype::MIN_POSITIVE));
+                let x = subnormal.select(self * Simd::splat(SCALE), self);
+
+                let half = x * Simd::splat(0.5);
+                // the subtraction from the magic constant halves and negates the exponent, with
+                // the relative error below 3.5%, which is about squared by every iteration
+                let mut y = Self::from_bits(Simd::splat($magic) - (x.to_bits() >> 1));
+                for _ in 0..$iterations {
+                    y = y * (Simd::splat(1.5) - half * y * y);
+                }
+                let y = subnormal.select(y * Simd::splat(ROOT_SCALE), y);
+
+                // zero, infinity, negative numbers and NaN
+                let special = self.simd_eq(Simd::splat(0.0)).select(
+                    Simd::splat($ftype::INFINITY).copysign(self),
+                    self.simd_eq(Simd::splat($ftype::INFINITY))
+                        .select(Simd::splat(0.0), Simd::splat($ftype::NAN)),
+                );
+                let positive_finite =
+                    self.simd_gt(Simd::splat(0.0)) & self.simd_lt(Simd::splat($ftype::INFINITY));
+                positive_finite.select(y, special)
+            }
+        }
+    };
+}
+
+impl_simd_float_math!(f32, i32, u32, 0x5f37_5a86, 3, 24);
+impl_simd_float_math!(f64, i64, u64, 0x5fe6_eb50_c7b5_37a9, 4, 54);
@@ -1,12 +1,17 @@
 mod exp;
+mod flags;
+mod float_math;
 mod trig;
 
 mod f32;
 mod f64;
 
+pub(crate) mod ops;
 mod poly;
 
 pub mod util;
 
 pub use exp::*;
+pub use flags::*;
+pub use float_math::*;
 pub use trig::*;
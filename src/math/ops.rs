@@ -0,0 +1,298 @@
+#[cfg(not(any(target_feature = "fma", target_arch = "aarch64", feature = "std")))]
+use crate::math::util::ErrorFreeTransform;
+use crate::{
+    math::util::FloatBitUtils,
+    simd::{intrinsics, prelude::*, LaneCount, SupportedLaneCount},
+};
+
+/// Whether [`CoreFloat::mul_add`] is a hardware instruction. Otherwise it's correctly rounded as
+/// well, but much slower, so kernels which can do without FMA should check it.
+pub(crate) const FUSED_MUL_ADD: bool = cfg!(any(target_feature = "fma", target_arch = "aarch64"));
+
+/// Replacement of [`StdFloat`](https://doc.rust-lang.org/std/simd/trait.StdFloat.html) methods
+/// which are used by the kernels but are not available in `core`.
+///
+/// Operations which are supported by hardware of the target are lowered to the corresponding
+/// `core::arch` intrinsics, the others are emulated. The results of a target don't depend on
+/// enabled crate features: `mul_add` without FMA is done by `libm` with the `std` feature and in
+/// software otherwise, and both are correctly rounded, i.e. the same as the hardware one.
+pub(crate) trait CoreFloat: Sized {
+    /// Fused multiply-add, i.e. `self * a + b` with a single rounding.
+    fn mul_add(self, a: Self, b: Self) -> Self;
+    fn sqrt(self) -> Self;
+    fn trunc(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    /// Rounds half-way cases away from zero.
+    fn round(self) -> Self;
+    fn fract(self) -> Self;
+}
+
+macro_rules! impl_core_float {
+    ($ftype: ident, $itype: ident, $utype: ident, $soft_fma: ident) => {
+        impl<const N: usize> CoreFloat for Simd<$ftype, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+        {
+            #[inline]
+            fn mul_add(self, a: Self, b: Self) -> Self {
+                #[cfg(any(target_feature = "fma", target_arch = "aarch64"))]
+                {
+                    intrinsics::FloatVector::fma(self, a, b)
+                }
+                #[cfg(all(
+                    not(any(target_feature = "fma", target_arch = "aarch64")),
+                    feature = "std"
+                ))]
+                {
+                    let (x, a, b) = (self.to_array(), a.to_array(), b.to_array());
+                    Simd::from_array(core::array::from_fn(|i| x[i].mul_add(a[i], b[i])))
+                }
+                #[cfg(not(any(target_feature = "fma", target_arch = "aarch64", feature = "std")))]
+                {
+                    $soft_fma(self, a, b)
+                }
+            }
+
+            #[inline]
+            fn sqrt(self) -> Self {
+                intrinsics::FloatVector::sqrt(self)
+            }
+
+            #[inline]
+            fn trunc(self) -> Self {
+                #[cfg(any(target_feature = "sse4.1", target_arch = "aarch64"))]
+                {
+                    intrinsics::FloatVector::trunc(self)
+                }
+                #[cfg(not(any(target_feature = "sse4.1", target_arch = "aarch64")))]
+                {
+                    const MANTISSA_BITS: $itype = $ftype::MANTISSA_DIGITS as $itype - 1;
+                    const EXP_MASK: $utype = ($ftype::MAX_EXP as $utype) * 2 - 1;
+                    const BIAS: $itype = $ftype::MAX_EXP as $itype - 1;
+
+                    let bits = self.to_bits();
+                    let exp = ((bits >> MANTISSA_BITS as $utype) & Simd::splat(EXP_MASK))
+                        .cast::<$itype>()
+                        - Simd::splat(BIAS);
+                    // number of mantissa bits representing the fractional part
+                    let frac_bits = Simd::splat(MANTISSA_BITS) - exp;
+                    let int_mask = frac_bits.simd_gt(Simd::splat(MANTISSA_BITS)).select(
+                        Simd::splat(1 << ($utype::BITS - 1)),
+                        Simd::splat($utype::MAX)
+                            << frac_bits
+                                .simd_clamp(Simd::splat(0), Simd::splat(MANTISSA_BITS))
+                                .cast::<$utype>(),
+                    );
+                    Simd::from_bits(bits & int_mask)
+                }
+            }
+
+            #[inline]
+            fn floor(self) -> Self {
+                #[cfg(any(target_feature = "sse4.1", target_arch = "aarch64"))]
+                {
+                    intrinsics::FloatVector::floor(self)
+                }
+                #[cfg(not(any(target_feature = "sse4.1", target_arch = "aarch64")))]
+                {
+                    let trunc = self.trunc();
+                    self.simd_lt(trunc).select(trunc - Simd::splat(1.0), trunc)
+                }
+            }
+
+            #[inline]
+            fn ceil(self) -> Self {
+                #[cfg(any(target_feature = "sse4.1", target_arch = "aarch64"))]
+                {
+                    intrinsics::FloatVector::ceil(self)
+                }
+                #[cfg(not(any(target_feature = "sse4.1", target_arch = "aarch64")))]
+                {
+                    let trunc = self.trunc();
+                    self.simd_gt(trunc).select(trunc + Simd::splat(1.0), trunc)
+                }
+            }
+
+            #[inline]
+            fn round(self) -> Self {
+                let trunc = self.trunc();
+                // `self - trunc` is exact
+                (self - trunc)
+                    .abs()
+                    .simd_ge(Simd::splat(0.5))
+                    .select(trunc + Simd::splat(1.0).sign_combine(self), trunc)
+            }
+
+            #[inline]
+            fn fract(self) -> Self {
+                self - self.trunc()
+            }
+        }
+    };
+}
+
+impl_core_float!(f32, i32, u32, soft_fma_f32);
+impl_core_float!(f64, i64, u64, soft_fma_f64);
+
+/// Rounds `sum + error` to odd, where `sum` is the sum rounded to nearest and `error` is its
+/// rounding error: an inexact sum with an even last mantissa bit is moved by an ulp towards the
+/// exact one. Rounding the result again to at least two bits less gives the correctly rounded sum.
+#[cfg(not(any(target_feature = "fma", target_arch = "aarch64", feature = "std")))]
+#[inline]
+fn round_to_odd<const N: usize>(sum: Simd<f64, N>, error: Simd<f64, N>) -> Simd<f64, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let bits = sum.to_bits();
+    let even = (bits & Simd::splat(1)).simd_eq(Simd::splat(0));
+    let inexact = error.simd_ne(Simd::splat(0.0)) & sum.is_finite();
+    // the magnitude grows by an ulp if the error has the same sign
+    let step = (sum.is_sign_negative() ^ error.is_sign_negative())
+        .select(Simd::splat(u64::MAX), Simd::splat(1));
+    Simd::from_bits((even & inexact).cast().select(bits + step, bits))
+}
+
+/// Correctly rounded `a * b + c` of `f32` lanes. The product is exact in `f64`, and the sum is
+/// rounded to odd (Boldo and Melquiond, "Emulation of FMA and correctly rounded sums: proved
+/// algorithms using rounding to odd").
+#[cfg(not(any(target_feature = "fma", target_arch = "aarch64", feature = "std")))]
+#[inline]
+fn soft_fma_f32<const N: usize>(a: Simd<f32, N>, b: Simd<f32, N>, c: Simd<f32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let (sum, error) = (a.cast::<f64>() * b.cast::<f64>()).two_sum(c.cast());
+    round_to_odd(sum, error).cast()
+}
+
+/// Correctly rounded `a * b + c` of `f64` lanes by the error-free transforms and rounding to odd
+/// (Boldo and Melquiond, Algorithm 5.4). The lanes where the transforms may overflow or underflow
+/// are computed by [`scalar_fma_f64`].
+#[cfg(not(any(target_feature = "fma", target_arch = "aarch64", feature = "std")))]
+#[inline]
+fn soft_fma_f64<const N: usize>(a: Simd<f64, N>, b: Simd<f64, N>, c: Simd<f64, N>) -> Simd<f64, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let (product, product_low) = a.two_prod(b);
+    let (c_high, c_low) = c.two_sum(product_low);
+    let (sum, sum_low) = product.two_sum(c_high);
+    let (rest, rest_error) = c_low.two_sum(sum_low);
+    let result = sum + round_to_odd(rest, rest_error);
+
+    let pow2 = |exp: i32| Simd::splat(f64::from_bits(((exp + 1023) as u64) << 52));
+    // `2^min <= |x| < 2^max`
+    let within =
+        |x: Simd<f64, N>, min, max| x.abs().simd_ge(pow2(min)) & x.abs().simd_lt(pow2(max));
+    // the splitting of the factors doesn't overflow, their halves and the low part of the product
+    // are normal, and so is the result
+    let exact = within(a, -969, 995)
+        & within(b, -969, 995)
+        & within(product, -900, 1000)
+        & within(sum, -900, 1000)
+        & c.abs().simd_lt(pow2(1000));
+    // the product by zero is exact, and a product far below the addend doesn't change it
+    let zero_product = (a.simd_eq(Simd::splat(0.0)) | b.simd_eq(Simd::splat(0.0)))
+        & (a.is_finite() & b.is_finite() & c.is_finite());
+    let negligible = (product.abs() * pow2(110)).simd_lt(c.abs()) & c.is_finite();
+    let result = zero_product.select(a * b + c, result);
+    let result = negligible.select(c, result);
+    let rest = !(exact | zero_product | negligible);
+    if !rest.any() {
+        return result;
+    }
+    let (a, b, c) = (a.to_array(), b.to_array(), c.to_array());
+    Simd::from_array(core::array::from_fn(|i| {
+        if rest.test(i) {
+            scalar_fma_f64(a[i], b[i], c[i])
+        } else {
+            result[i]
+        }
+    }))
+}
+
+/// Correctly rounded `a * b + c` by integer arithmetic.
+#[cfg(not(any(target_feature = "fma", target_arch = "aarch64", feature = "std")))]
+fn scalar_fma_f64(a: f64, b: f64, c: f64) -> f64 {
+    const MANTISSA_BITS: u32 = f64::MANTISSA_DIGITS - 1;
+    // the lowest exponent of the mantissas as integers
+    const MIN_EXP: i32 = f64::MIN_EXP - f64::MANTISSA_DIGITS as i32;
+
+    if a == 0.0 || b == 0.0 || !(a.is_finite() && b.is_finite()) {
+        // the product is exact
+        return a * b + c;
+    }
+    if !c.is_finite() {
+        return c;
+    }
+    // `x = ±mantissa * 2^exp`, with the top bit of the mantissa moved to bit 125, which leaves
+    // room for a carry and for the bits shifted out of the smaller operand
+    let unpack = |bits: u64, mantissa: u128| {
+        let biased = (bits >> MANTISSA_BITS) as i32 & 0x7ff;
+        let (mantissa, exp) = if biased == 0 {
+            (mantissa, MIN_EXP)
+        } else {
+            (mantissa | 1 << MANTISSA_BITS, MIN_EXP + biased - 1)
+        };
+        (mantissa, exp)
+    };
+    let normalize = |(mantissa, exp): (u128, i32)| {
+        let shift = mantissa.leading_zeros() as i32 - 2;
+        (mantissa << shift, exp - shift)
+    };
+    let low_bits = |x: f64| x.to_bits() & ((1 << MANTISSA_BITS) - 1);
+    let (a_mantissa, a_exp) = unpack(a.to_bits(), low_bits(a) as u128);
+    let (b_mantissa, b_exp) = unpack(b.to_bits(), low_bits(b) as u128);
+    let product = normalize((a_mantissa * b_mantissa, a_exp + b_exp));
+    let product_negative = a.is_sign_negative() != b.is_sign_negative();
+
+    let ((mantissa, exp), negative) = if c == 0.0 {
+        (product, product_negative)
+    } else {
+        let addend = normalize(unpack(c.to_bits(), low_bits(c) as u128));
+        let ((large, large_exp), large_negative, (small, small_exp)) =
+            if (product.1, product.0) >= (addend.1, addend.0) {
+                (product, product_negative, addend)
+            } else {
+                (addend, c.is_sign_negative(), product)
+            };
+        // the bits shifted out only matter as a sticky bit far below the rounding position
+        let shift = (large_exp - small_exp) as u32;
+        let small = match small.checked_shr(shift) {
+            Some(shifted) => shifted | (shifted << shift != small) as u128,
+            None => 1,
+        };
+        let mantissa = if product_negative == c.is_sign_negative() {
+            large + small
+        } else {
+            large - small
+        };
+        ((mantissa, large_exp), large_negative)
+    };
+    if mantissa == 0 {
+        // exact cancellation
+        return 0.0;
+    }
+
+    // the exponent of the last bit of the result, the bits below it are rounded to nearest even
+    let top = exp + 127 - mantissa.leading_zeros() as i32;
+    let last = (top - MANTISSA_BITS as i32).max(MIN_EXP);
+    let rounded = match last - exp {
+        shift @ ..=0 => mantissa << -shift,
+        shift @ 1..=127 => {
+            let (rounded, rest, half) = (
+                mantissa >> shift,
+                mantissa & ((1 << shift) - 1),
+                1 << (shift - 1),
+            );
+            rounded + (rest > half || (rest == half && rounded & 1 == 1)) as u128
+        }
+        // less than half of the smallest subnormal
+        _ => 0,
+    };
+    // a carry of the rounding goes into the exponent, subnormals have the exponent field of 1
+    let bits = (((last - MIN_EXP + 1) as u128) << MANTISSA_BITS) + rounded - (1 << MANTISSA_BITS);
+    let bits = bits.min(f64::INFINITY.to_bits() as u128) as u64;
+    f64::from_bits(bits | (negative as u64) << 63)
+}
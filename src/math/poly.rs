@@ -1,4 +1,4 @@
-/// Evaluates polynomial with argument of [`Simd`](std::simd::Simd) type using [Estrin's scheme][scheme_wiki].
+/// Evaluates polynomial with argument of [`Simd`](core::simd::Simd) type using [Estrin's scheme][scheme_wiki].
 ///
 /// [scheme_wiki]: https://en.wikipedia.org/wiki/Estrin's_scheme
 ///
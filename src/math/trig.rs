@@ -1,3 +1,18 @@
+use crate::math::{
+    util::{LaneMask, Select},
+    FpFlags,
+};
+
+/// Trigonometric functions of floating-point vectors.
+///
+/// Also implemented for scalar `f32` and `f64` in the same manner as [`Exponent`].
+///
+/// Every function also has `_masked` and `_checked` variants, see [`Exponent`] for details.
+/// Checked variants of `sin`, `cos` and `tan` raise `invalid` for infinite inputs as well as for
+/// inputs which are too large for argument reduction. Ones of `asin` and `acos` raise `invalid`
+/// for inputs outside of `[-1, 1]`.
+///
+/// [`Exponent`]: crate::math::Exponent
 pub trait Trigonometry {
     /// Calculates sine of each lane. Overflows on large values (greater than 10⁵) and return `0.0`
     /// in the case.
@@ -21,7 +36,7 @@ pub trait Trigonometry {
     #[inline]
     fn tan(self) -> Self
     where
-        Self: std::ops::Div<Output = Self> + Copy,
+        Self: core::ops::Div<Output = Self> + Copy,
     {
         let (sin, cos) = self.sin_cos();
         sin / cos
@@ -53,4 +68,119 @@ pub trait Trigonometry {
     fn acos(self) -> Self;
     fn atan(self) -> Self;
     fn atan2(self, x: Self) -> Self;
+
+    /// Checked variant of [`sin`](Self::sin).
+    fn sin_checked(self) -> (Self, FpFlags<Self::Mask>)
+    where
+        Self: LaneMask;
+    /// Checked variant of [`cos`](Self::cos).
+    fn cos_checked(self) -> (Self, FpFlags<Self::Mask>)
+    where
+        Self: LaneMask;
+    /// Checked variant of [`tan`](Self::tan).
+    fn tan_checked(self) -> (Self, FpFlags<Self::Mask>)
+    where
+        Self: LaneMask;
+    /// Checked variant of [`sin_cos`](Self::sin_cos).
+    fn sin_cos_checked(self) -> ((Self, Self), FpFlags<Self::Mask>)
+    where
+        Self: LaneMask;
+    /// Checked variant of [`asin`](Self::asin).
+    fn asin_checked(self) -> (Self, FpFlags<Self::Mask>)
+    where
+        Self: LaneMask;
+    /// Checked variant of [`acos`](Self::acos).
+    fn acos_checked(self) -> (Self, FpFlags<Self::Mask>)
+    where
+        Self: LaneMask;
+
+    /// Checked variant of [`atan`](Self::atan). `atan` never raises any exceptions.
+    #[inline]
+    fn atan_checked(self) -> (Self, FpFlags<Self::Mask>)
+    where
+        Self: LaneMask,
+    {
+        (self.atan(), FpFlags::default())
+    }
+
+    /// Checked variant of [`atan2`](Self::atan2). `atan2` never raises any exceptions.
+    #[inline]
+    fn atan2_checked(self, x: Self) -> (Self, FpFlags<Self::Mask>)
+    where
+        Self: LaneMask,
+    {
+        (self.atan2(x), FpFlags::default())
+    }
+
+    /// Masked variant of [`sin`](Self::sin).
+    #[inline]
+    fn sin_masked<M: Select<Self>>(self, mask: M, fallback: Self) -> Self
+    where
+        Self: Default + Sized,
+    {
+        mask.select(mask.select(self, Self::default()).sin(), fallback)
+    }
+
+    /// Masked variant of [`cos`](Self::cos).
+    #[inline]
+    fn cos_masked<M: Select<Self>>(self, mask: M, fallback: Self) -> Self
+    where
+        Self: Default + Sized,
+    {
+        mask.select(mask.select(self, Self::default()).cos(), fallback)
+    }
+
+    /// Masked variant of [`tan`](Self::tan).
+    fn tan_masked<M: Select<Self>>(self, mask: M, fallback: Self) -> Self
+    where
+        Self: Default + Sized;
+
+    /// Masked variant of [`sin_cos`](Self::sin_cos). Inactive lanes of sine and cosine are taken
+    /// from the first and the second `fallback` values respectively.
+    #[inline]
+    fn sin_cos_masked<M: Select<Self>>(self, mask: M, fallback: (Self, Self)) -> (Self, Self)
+    where
+        Self: Copy + Default,
+    {
+        let (sin, cos) = mask.select(self, Self::default()).sin_cos();
+        (mask.select(sin, fallback.0), mask.select(cos, fallback.1))
+    }
+
+    /// Masked variant of [`asin`](Self::asin).
+    #[inline]
+    fn asin_masked<M: Select<Self>>(self, mask: M, fallback: Self) -> Self
+    where
+        Self: Default + Sized,
+    {
+        mask.select(mask.select(self, Self::default()).asin(), fallback)
+    }
+
+    /// Masked variant of [`acos`](Self::acos).
+    #[inline]
+    fn acos_masked<M: Select<Self>>(self, mask: M, fallback: Self) -> Self
+    where
+        Self: Default + Sized,
+    {
+        mask.select(mask.select(self, Self::default()).acos(), fallback)
+    }
+
+    /// Masked variant of [`atan`](Self::atan).
+    #[inline]
+    fn atan_masked<M: Select<Self>>(self, mask: M, fallback: Self) -> Self
+    where
+        Self: Default + Sized,
+    {
+        mask.select(mask.select(self, Self::default()).atan(), fallback)
+    }
+
+    /// Masked variant of [`atan2`](Self::atan2).
+    #[inline]
+    fn atan2_masked<M: Select<Self>>(self, x: Self, mask: M, fallback: Self) -> Self
+    where
+        Self: Default + Sized,
+    {
+        let y = mask.select(self, Self::default());
+        let x = mask.select(x, Self::default());
+        mask.select(y.atan2(x), fallback)
+    }
 }
@@ -1,9 +1,14 @@
-use std::{
-    ops::BitXor,
-    simd::{num::SimdFloat, LaneCount, Simd, SupportedLaneCount},
+use crate::{
+    math::ops::{CoreFloat, FUSED_MUL_ADD},
+    simd::{num::SimdFloat, LaneCount, Mask, MaskElement, Simd, SimdElement, SupportedLaneCount},
 };
 
-pub trait FloatBitUtils: SimdFloat {
+/// Bit manipulations of floats, implemented for vectors and for scalar `f32` and `f64`.
+pub trait FloatBitUtils: Sized {
+    /// Unsigned integer (or vector of unsigned integers) with the same layout as `Self`. Same as
+    /// [`SimdFloat::Bits`] for vectors.
+    type UnsignedBits;
+
     /// Returns zero if sign-bit of the value is zero or sign bit mask otherwise.
     ///
     /// # Examples
@@ -19,8 +24,9 @@ pub trait FloatBitUtils: SimdFloat {
     ///     f64x4::from_array([0.0, -0.0, 1.0, -f64::INFINITY]).sign_bit(),
     ///     u64x4::from_array([0, 1, 0, 1]) << 63
     /// );
+    /// assert_eq!((-2.0f32).sign_bit(), 1 << 31);
     /// ```
-    fn sign_bit(self) -> Self::Bits;
+    fn sign_bit(self) -> Self::UnsignedBits;
 
     /// Returns each element with the magnitude `self` and sign combined from signs of `self` and `other`.
     /// Does roughly the same as `self * other.signum()`.
@@ -37,33 +43,69 @@ pub trait FloatBitUtils: SimdFloat {
     ///     Simd::from_array([1.0, -0.0, 0.0, 2.0])
     /// )
     /// ```
-    #[inline]
-    fn sign_combine(self, other: Self) -> Self
-    where
-        Self::Bits: BitXor<Output = Self::Bits>,
-    {
-        Self::from_bits(self.to_bits() ^ other.sign_bit())
-    }
+    fn sign_combine(self, other: Self) -> Self;
 }
 
 impl<const N: usize> FloatBitUtils for Simd<f32, N>
 where
     LaneCount<N>: SupportedLaneCount,
 {
+    type UnsignedBits = Simd<u32, N>;
+
     #[inline]
     fn sign_bit(self) -> Simd<u32, N> {
         self.to_bits() & Simd::splat(1 << 31)
     }
+
+    #[inline]
+    fn sign_combine(self, other: Self) -> Self {
+        Self::from_bits(self.to_bits() ^ other.sign_bit())
+    }
 }
 
 impl<const N: usize> FloatBitUtils for Simd<f64, N>
 where
     LaneCount<N>: SupportedLaneCount,
 {
+    type UnsignedBits = Simd<u64, N>;
+
     #[inline]
     fn sign_bit(self) -> Simd<u64, N> {
         self.to_bits() & Simd::splat(1 << 63)
     }
+
+    #[inline]
+    fn sign_combine(self, other: Self) -> Self {
+        Self::from_bits(self.to_bits() ^ other.sign_bit())
+    }
+}
+
+impl FloatBitUtils for f32 {
+    type UnsignedBits = u32;
+
+    #[inline]
+    fn sign_bit(self) -> u32 {
+        self.to_bits() & (1 << 31)
+    }
+
+    #[inline]
+    fn sign_combine(self, other: Self) -> Self {
+        Self::from_bits(self.to_bits() ^ other.sign_bit())
+    }
+}
+
+impl FloatBitUtils for f64 {
+    type UnsignedBits = u64;
+
+    #[inline]
+    fn sign_bit(self) -> u64 {
+        self.to_bits() & (1 << 63)
+    }
+
+    #[inline]
+    fn sign_combine(self, other: Self) -> Self {
+        Self::from_bits(self.to_bits() ^ other.sign_bit())
+    }
 }
 
 pub trait FastRound {
@@ -78,7 +120,21 @@ where
 {
     #[inline]
     fn fast_round(self) -> Self {
-        self.to_array().map(f32::round_ties_even).into()
+        #[cfg(any(target_feature = "sse4.1", target_arch = "aarch64"))]
+        {
+            crate::simd::intrinsics::FloatVector::round_ties_even(self)
+        }
+        #[cfg(not(any(target_feature = "sse4.1", target_arch = "aarch64")))]
+        {
+            use crate::simd::cmp::SimdPartialOrd;
+
+            // adding and subtracting 2^(mantissa bits) rounds all the smaller values to integers
+            const MAGIC: f32 = (1u64 << (f32::MANTISSA_DIGITS - 1)) as f32;
+            let abs = self.abs();
+            let rounded = (abs + Simd::splat(MAGIC)) - Simd::splat(MAGIC);
+            abs.simd_lt(Simd::splat(MAGIC))
+                .select(Simd::from_bits(rounded.to_bits() | self.sign_bit()), self)
+        }
     }
 }
 
@@ -88,6 +144,210 @@ where
 {
     #[inline]
     fn fast_round(self) -> Self {
-        self.to_array().map(f64::round_ties_even).into()
+        #[cfg(any(target_feature = "sse4.1", target_arch = "aarch64"))]
+        {
+            crate::simd::intrinsics::FloatVector::round_ties_even(self)
+        }
+        #[cfg(not(any(target_feature = "sse4.1", target_arch = "aarch64")))]
+        {
+            use crate::simd::cmp::SimdPartialOrd;
+
+            // adding and subtracting 2^(mantissa bits) rounds all the smaller values to integers
+            const MAGIC: f64 = (1u64 << (f64::MANTISSA_DIGITS - 1)) as f64;
+            let abs = self.abs();
+            let rounded = (abs + Simd::splat(MAGIC)) - Simd::splat(MAGIC);
+            abs.simd_lt(Simd::splat(MAGIC))
+                .select(Simd::from_bits(rounded.to_bits() | self.sign_bit()), self)
+        }
+    }
+}
+
+impl FastRound for f32 {
+    #[inline]
+    fn fast_round(self) -> Self {
+        Simd::<f32, 1>::splat(self).fast_round()[0]
+    }
+}
+
+impl FastRound for f64 {
+    #[inline]
+    fn fast_round(self) -> Self {
+        Simd::<f64, 1>::splat(self).fast_round()[0]
+    }
+}
+
+/// Error-free transformations: results of floating-point operations together with their exact
+/// rounding errors. They are the building blocks of compensated algorithms, e.g. the compensated
+/// summation of [`algo::reduce`](crate::algo::reduce).
+///
+/// The results are exact unless the operations overflow.
+pub trait ErrorFreeTransform: Sized {
+    /// Returns `(s, e)` where `s = self + other` rounded and `self + other = s + e` exactly,
+    /// by Knuth's branchless TwoSum.
+    ///
+    /// # Examples
+    /// ```
+    /// # #![feature(portable_simd)]
+    /// # use std::simd::prelude::*;
+    /// # use simd_addons::math::util::*;
+    /// let (sum, error) = f32x2::from_array([1.0, 1e8]).two_sum(f32x2::from_array([1e-8, 1.0]));
+    /// assert_eq!(sum, f32x2::from_array([1.0, 1e8]));
+    /// assert_eq!(error, f32x2::from_array([1e-8, 1.0]));
+    /// assert_eq!(1e16.two_sum(-1.0), (1e16, -1.0));
+    /// ```
+    fn two_sum(self, other: Self) -> (Self, Self);
+
+    /// Same as [`two_sum`](Self::two_sum), but faster and valid only if the exponent of `self` is
+    /// not less than the one of `other`, e.g. if `|self| >= |other|` (Dekker's FastTwoSum).
+    fn fast_two_sum(self, other: Self) -> (Self, Self);
+
+    /// Returns `(p, e)` where `p = self * other` rounded and `self * other = p + e` exactly. Uses
+    /// fused multiply-add where available and Veltkamp's splitting otherwise, which is also exact
+    /// unless the operands are close to overflow.
+    ///
+    /// # Examples
+    /// ```
+    /// # use simd_addons::math::util::*;
+    /// let x = 1.0 + f64::EPSILON;
+    /// assert_eq!(x.two_prod(x), (1.0 + 2.0 * f64::EPSILON, f64::EPSILON * f64::EPSILON));
+    /// ```
+    fn two_prod(self, other: Self) -> (Self, Self);
+}
+
+macro_rules! impl_error_free_transform {
+    ($ftype: ident) => {
+        impl<const N: usize> ErrorFreeTransform for Simd<$ftype, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+        {
+            #[inline]
+            fn two_sum(self, other: Self) -> (Self, Self) {
+                let sum = self + other;
+                let other_rounded = sum - self;
+                let self_rounded = sum - other_rounded;
+                (sum, (self - self_rounded) + (other - other_rounded))
+            }
+
+            #[inline]
+            fn fast_two_sum(self, other: Self) -> (Self, Self) {
+                let sum = self + other;
+                (sum, other - (sum - self))
+            }
+
+            #[inline]
+            fn two_prod(self, other: Self) -> (Self, Self) {
+                let product = self * other;
+                if FUSED_MUL_ADD {
+                    return (product, self.mul_add(other, -product));
+                }
+                // splits `x` into `high + low`, both of half of the mantissa digits, so the
+                // products of the halves are exact
+                let split = |x: Self| {
+                    const FACTOR: $ftype =
+                        (1u64 << $ftype::MANTISSA_DIGITS.div_ceil(2)) as $ftype + 1.0;
+                    let scaled = x * Simd::splat(FACTOR);
+                    let high = scaled - (scaled - x);
+                    (high, x - high)
+                };
+                let (a_high, a_low) = split(self);
+                let (b_high, b_low) = split(other);
+                let error =
+                    ((a_high * b_high - product) + a_high * b_low + a_low * b_high) + a_low * b_low;
+                (product, error)
+            }
+        }
+
+        impl ErrorFreeTransform for $ftype {
+            #[inline]
+            fn two_sum(self, other: Self) -> (Self, Self) {
+                let (sum, error) = Simd::<$ftype, 1>::splat(self).two_sum(Simd::splat(other));
+                (sum[0], error[0])
+            }
+
+            #[inline]
+            fn fast_two_sum(self, other: Self) -> (Self, Self) {
+                let (sum, error) = Simd::<$ftype, 1>::splat(self).fast_two_sum(Simd::splat(other));
+                (sum[0], error[0])
+            }
+
+            #[inline]
+            fn two_prod(self, other: Self) -> (Self, Self) {
+                let (product, error) = Simd::<$ftype, 1>::splat(self).two_prod(Simd::splat(other));
+                (product[0], error[0])
+            }
+        }
+    };
+}
+
+impl_error_free_transform!(f32);
+impl_error_free_transform!(f64);
+
+pub trait Select<T>: Copy {
+    /// Chooses lanes from `true_values` where `self` is set and from `false_values` otherwise.
+    /// Same as [`Mask::select`], but also implemented for `bool` as a mask of scalars.
+    ///
+    /// # Examples
+    /// ```
+    /// # #![feature(portable_simd)]
+    /// # use std::simd::prelude::*;
+    /// # use simd_addons::math::util::*;
+    /// let mask = mask32x4::from_array([true, false, false, true]);
+    /// assert_eq!(
+    ///     Select::select(mask, f32x4::splat(1.0), f32x4::splat(2.0)),
+    ///     f32x4::from_array([1.0, 2.0, 2.0, 1.0])
+    /// );
+    /// assert_eq!(Select::select(false, 1.0, 2.0), 2.0);
+    /// ```
+    fn select(self, true_values: T, false_values: T) -> T;
+}
+
+impl<T, U, const N: usize> Select<Simd<U, N>> for Mask<T, N>
+where
+    T: MaskElement,
+    U: SimdElement<Mask = T>,
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn select(self, true_values: Simd<U, N>, false_values: Simd<U, N>) -> Simd<U, N> {
+        Mask::select(self, true_values, false_values)
     }
 }
+
+impl<T> Select<T> for bool {
+    #[inline]
+    fn select(self, true_values: T, false_values: T) -> T {
+        if self {
+            true_values
+        } else {
+            false_values
+        }
+    }
+}
+
+/// Maps a float type to the type of masks of its lanes, i.e. [`Mask`] for vectors and `bool` for
+/// scalars.
+pub trait LaneMask: Sized {
+    type Mask: Select<Self> + Default;
+}
+
+impl<const N: usize> LaneMask for Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    type Mask = Mask<i32, N>;
+}
+
+impl<const N: usize> LaneMask for Simd<f64, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    type Mask = Mask<i64, N>;
+}
+
+impl LaneMask for f32 {
+    type Mask = bool;
+}
+
+impl LaneMask for f64 {
+    type Mask = bool;
+}
@@ -0,0 +1,141 @@
+//! Pseudorandom number generators which produce a vector of numbers at once, and distributions of
+//! floats built upon them.
+//!
+//! Each lane of a generator is an independent stream, the streams are chosen such that they don't
+//! overlap: [`Xoshiro256PlusPlus`] jumps the state of every next lane ahead by `2^128` steps,
+//! [`Pcg32`] gives lanes different increments and [`Philox4x32`] gives them different counters.
+//! The lanes of [`Xoshiro256PlusPlus`] and [`Pcg32`] produce exactly the same numbers as the
+//! scalar generators of the `rand_xoshiro` and `rand_pcg` crates seeded accordingly.
+//!
+//! Random bits are turned into floats uniformly distributed in `[0, 1)` by [`unit_f32`] and
+//! [`unit_f64`], and into the normal and the exponential distributions by the ziggurat method,
+//! which uses the crate's own [`exp`](crate::math::Exponent::exp) and no logarithm.
+//!
+//! # Examples
+//! ```
+//! # #![feature(portable_simd)]
+//! # use std::simd::prelude::*;
+//! use simd_addons::rng::{self, Pcg32, SimdRng, Xoshiro256PlusPlus};
+//!
+//! let mut rng = Xoshiro256PlusPlus::<8>::seed_from_u64(42);
+//! let dice = rng.next_u32() % u32x8::splat(6) + u32x8::splat(1);
+//! assert!(dice.simd_ge(u32x8::splat(1)).all() && dice.simd_le(u32x8::splat(6)).all());
+//!
+//! let x: f32x8 = rng::uniform(&mut rng);
+//! assert!(x.simd_ge(f32x8::splat(0.0)).all() && x.simd_lt(f32x8::splat(1.0)).all());
+//!
+//! let mut rng = Pcg32::<4>::new(7, 0);
+//! let z: f64x4 = rng::normal(&mut rng);
+//! let t: f64x4 = rng::exponential(&mut rng);
+//! assert!(z.is_finite().all() && t.simd_ge(f64x4::splat(0.0)).all());
+//! ```
+
+use crate::simd::{num::SimdFloat, LaneCount, Simd, SimdElement, SupportedLaneCount};
+
+mod pcg;
+mod philox;
+mod xoshiro;
+mod ziggurat;
+
+pub use pcg::*;
+pub use philox::*;
+pub use xoshiro::*;
+
+/// Generator of `N` independent streams of random bits.
+pub trait SimdRng<const N: usize>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Returns the next 32 random bits of each stream.
+    fn next_u32(&mut self) -> Simd<u32, N>;
+
+    /// Returns the next 64 random bits of each stream.
+    fn next_u64(&mut self) -> Simd<u64, N>;
+}
+
+/// Converts the high 23 bits of each lane to a float uniformly distributed in `[0, 1)`, with all
+/// multiples of `2^-23` equally likely.
+///
+/// The bits become the mantissa of a float in `[1, 2)`, which is exactly shifted down then.
+///
+/// # Examples
+/// ```
+/// # #![feature(portable_simd)]
+/// # use std::simd::prelude::*;
+/// use simd_addons::rng::unit_f32;
+///
+/// let bits = u32x4::from_array([0, 1 << 31, 3 << 30, u32::MAX]);
+/// assert_eq!(unit_f32(bits), f32x4::from_array([0.0, 0.5, 0.75, 1.0 - f32::EPSILON]));
+/// ```
+#[inline]
+pub fn unit_f32<const N: usize>(bits: Simd<u32, N>) -> Simd<f32, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    Simd::<f32, N>::from_bits((bits >> 9) | Simd::splat(0x3f80_0000)) - Simd::splat(1.0)
+}
+
+/// Converts the high 52 bits of each lane to a float uniformly distributed in `[0, 1)`, with all
+/// multiples of `2^-52` equally likely.
+///
+/// See [`unit_f32`] for details.
+#[inline]
+pub fn unit_f64<const N: usize>(bits: Simd<u64, N>) -> Simd<f64, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    Simd::<f64, N>::from_bits((bits >> 12) | Simd::splat(0x3ff0_0000_0000_0000)) - Simd::splat(1.0)
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Types of random floats: `f32`, made of 32 random bits, and `f64`, made of 64 random bits.
+pub trait RandomFloat: SimdElement + sealed::Sealed {
+    #[doc(hidden)]
+    fn uniform<R: SimdRng<N>, const N: usize>(rng: &mut R) -> Simd<Self, N>
+    where
+        LaneCount<N>: SupportedLaneCount;
+
+    #[doc(hidden)]
+    fn normal<R: SimdRng<N>, const N: usize>(rng: &mut R) -> Simd<Self, N>
+    where
+        LaneCount<N>: SupportedLaneCount;
+
+    #[doc(hidden)]
+    fn exponential<R: SimdRng<N>, const N: usize>(rng: &mut R) -> Simd<Self, N>
+    where
+        LaneCount<N>: SupportedLaneCount;
+}
+
+/// Returns floats uniformly distributed in `[0, 1)`, see [`unit_f32`] and [`unit_f64`].
+#[inline]
+pub fn uniform<T: RandomFloat, R: SimdRng<N>, const N: usize>(rng: &mut R) -> Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    T::uniform(rng)
+}
+
+/// Returns floats of the standard normal distribution, with the mean 0 and the variance 1.
+///
+/// The number of random values taken from `rng` varies, as rejected lanes are sampled again.
+#[inline]
+pub fn normal<T: RandomFloat, R: SimdRng<N>, const N: usize>(rng: &mut R) -> Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    T::normal(rng)
+}
+
+/// Returns floats of the exponential distribution with the rate 1, i.e. with the mean 1.
+///
+/// The number of random values taken from `rng` varies, as rejected lanes are sampled again.
+#[inline]
+pub fn exponential<T: RandomFloat, R: SimdRng<N>, const N: usize>(rng: &mut R) -> Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    T::exponential(rng)
+}
@@ -0,0 +1,67 @@
+use crate::{
+    rng::SimdRng,
+    simd::{num::SimdUint, LaneCount, Simd, SupportedLaneCount},
+};
+
+/// Multiplier of the linear congruential generator.
+const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+
+/// The PCG32 generator of O'Neill (the XSH RR 64/32 variant): a 64-bit linear congruential
+/// generator with a permutation of its high bits as the 32-bit output. It has a small state and
+/// good statistical quality, but it isn't cryptographically secure.
+///
+/// Lane `i` is the stream `stream + i`, so it produces the same numbers as the scalar
+/// `rand_pcg::Pcg32::new(state, stream + i)`. [`next_u64`](SimdRng::next_u64) takes two outputs,
+/// the first one is the low half.
+#[derive(Clone, Debug)]
+pub struct Pcg32<const N: usize>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    state: Simd<u64, N>,
+    increment: Simd<u64, N>,
+}
+
+impl<const N: usize> Pcg32<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Creates a generator with the initial `state` of all lanes and the `stream` of lane 0.
+    pub fn new(state: u64, stream: u64) -> Self {
+        let streams = Simd::from_array(core::array::from_fn(|i| stream.wrapping_add(i as u64)));
+        // the increment must be odd
+        let increment = (streams << 1) | Simd::splat(1);
+        let mut rng = Self {
+            state: Simd::splat(state) + increment,
+            increment,
+        };
+        rng.step();
+        rng
+    }
+
+    #[inline(always)]
+    fn step(&mut self) {
+        self.state = self.state * Simd::splat(MULTIPLIER) + self.increment;
+    }
+}
+
+impl<const N: usize> SimdRng<N> for Pcg32<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn next_u32(&mut self) -> Simd<u32, N> {
+        let state = self.state;
+        self.step();
+        let rotation: Simd<u32, N> = (state >> 59).cast();
+        let x: Simd<u32, N> = (((state >> 18) ^ state) >> 27).cast();
+        (x >> rotation) | (x << ((Simd::splat(0) - rotation) & Simd::splat(31)))
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> Simd<u64, N> {
+        let low: Simd<u64, N> = self.next_u32().cast();
+        let high: Simd<u64, N> = self.next_u32().cast();
+        low | (high << 32)
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{
+    rng::SimdRng,
+    simd::{num::SimdUint, LaneCount, Simd, SupportedLaneCount},
+};
+
+/// Multipliers of the rounds.
+const MULTIPLIERS: [u32; 2] = [0xd251_1f53, 0xcd9e_8d57];
+
+/// Increments of the key between the rounds, the golden ratio and `√3 - 1`.
+const WEYL: [u32; 2] = [0x9e37_79b9, 0xbb67_ae85];
+
+const ROUNDS: usize = 10;
+
+/// High and low halves of the products of `a` and the lanes of `b`.
+#[inline(always)]
+fn mul_hi_lo<const N: usize>(a: u32, b: Simd<u32, N>) -> (Simd<u32, N>, Simd<u32, N>)
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    if N == 1 {
+        // LLVM 18 crashes on the vector product of a single lane at opt-level 3, so it's done by
+        // scalars
+        let product = a as u64 * b[0] as u64;
+        return (
+            Simd::splat((product >> 32) as u32),
+            Simd::splat(product as u32),
+        );
+    }
+    let product = Simd::splat(a as u64) * b.cast::<u64>();
+    ((product >> 32).cast(), product.cast())
+}
+
+/// The Philox4x32-10 function of Salmon et al.: returns four random words for the four words of
+/// the counter of each lane and the `key`.
+///
+/// The result is a bijection of the counter for every key, so distinct counters give
+/// uncorrelated numbers which never repeat. Being a pure function, it gives any part of a random
+/// sequence without generating the preceding parts, e.g. in parallel.
+///
+/// # Examples
+/// ```
+/// # #![feature(portable_simd)]
+/// # use std::simd::prelude::*;
+/// use simd_addons::rng::philox4x32;
+///
+/// let counter = [u32x4::from_array([0, 1, 2, 3]), u32x4::splat(0), u32x4::splat(0), u32x4::splat(0)];
+/// let words = philox4x32(counter, [0, 0]);
+/// assert_eq!(words[0][0], 0x6627e8d5);
+/// ```
+#[inline]
+pub fn philox4x32<const N: usize>(counter: [Simd<u32, N>; 4], key: [u32; 2]) -> [Simd<u32, N>; 4]
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let mut counter = counter;
+    let mut key = key;
+    for round in 0..ROUNDS {
+        if round > 0 {
+            key = [key[0].wrapping_add(WEYL[0]), key[1].wrapping_add(WEYL[1])];
+        }
+        let (high0, low0) = mul_hi_lo(MULTIPLIERS[0], counter[0]);
+        let (high1, low1) = mul_hi_lo(MULTIPLIERS[1], counter[2]);
+        counter = [
+            high1 ^ counter[1] ^ Simd::splat(key[0]),
+            low1,
+            high0 ^ counter[3] ^ Simd::splat(key[1]),
+            low0,
+        ];
+    }
+    counter
+}
+
+/// Counter-based generator built upon [`philox4x32`]: each lane encrypts its own sequence of
+/// counters with the same key.
+///
+/// The numbers are generated by blocks of four vectors. The 64-bit counter of lane `i` in block
+/// `b` is `b · N + i`, which makes the low two words of the counter, and the `stream` makes the
+/// high two words. So the generators with different streams or keys are independent, and a block
+/// is generated in constant time by [`seek`](Self::seek).
+#[derive(Clone, Debug)]
+pub struct Philox4x32<const N: usize>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    key: [u32; 2],
+    stream: u64,
+    block: u64,
+    buffer: [Simd<u32, N>; 4],
+    next: usize,
+}
+
+impl<const N: usize> Philox4x32<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Creates a generator at the beginning of the block 0 of `stream`.
+    pub fn new(key: u64, stream: u64) -> Self {
+        Self {
+            key: [key as u32, (key >> 32) as u32],
+            stream,
+            block: 0,
+            buffer: [Simd::splat(0); 4],
+            next: 4,
+        }
+    }
+
+    /// Moves the generator to the beginning of `block`, the next numbers are generated from it.
+    pub fn seek(&mut self, block: u64) {
+        self.block = block;
+        self.next = 4;
+    }
+
+    #[inline(always)]
+    fn refill(&mut self) {
+        let start = self.block.wrapping_mul(N as u64);
+        let counters: Simd<u64, N> =
+            Simd::from_array(core::array::from_fn(|i| start.wrapping_add(i as u64)));
+        let counter = [
+            counters.cast(),
+            (counters >> 32).cast(),
+            Simd::splat(self.stream as u32),
+            Simd::splat((self.stream >> 32) as u32),
+        ];
+        self.buffer = philox4x32(counter, self.key);
+        self.block = self.block.wrapping_add(1);
+        self.next = 0;
+    }
+}
+
+impl<const N: usize> SimdRng<N> for Philox4x32<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn next_u32(&mut self) -> Simd<u32, N> {
+        if self.next == 4 {
+            self.refill();
+        }
+        self.next += 1;
+        self.buffer[self.next - 1]
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> Simd<u64, N> {
+        let low: Simd<u64, N> = self.next_u32().cast();
+        let high: Simd<u64, N> = self.next_u32().cast();
+        low | (high << 32)
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{
+    rng::SimdRng,
+    simd::{num::SimdUint, LaneCount, Simd, SupportedLaneCount},
+};
+
+/// Polynomial of the jump by `2^128` steps, see [`Xoshiro256PlusPlus::from_state`].
+const JUMP: [u64; 4] = [
+    0x180e_c6d3_3cfd_0aba,
+    0xd5a6_1266_f0c9_392c,
+    0xa958_2618_e03f_c9aa,
+    0x39ab_dc45_29b1_661c,
+];
+
+/// Increment of SplitMix64, the golden ratio.
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+#[inline(always)]
+fn rotate_left<const N: usize>(x: Simd<u64, N>, k: u64) -> Simd<u64, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    (x << k) | (x >> (64 - k))
+}
+
+/// Advances `state` and returns the output of xoshiro256++ before the advance.
+#[inline(always)]
+fn step<const N: usize>(s: &mut [Simd<u64, N>; 4]) -> Simd<u64, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let result = rotate_left(s[0] + s[3], 23) + s[0];
+    let t = s[1] << 17;
+    s[2] ^= s[0];
+    s[3] ^= s[1];
+    s[1] ^= s[2];
+    s[0] ^= s[3];
+    s[2] ^= t;
+    s[3] = rotate_left(s[3], 45);
+    result
+}
+
+/// Returns the state `2^128` steps after `state`.
+fn jump(state: [u64; 4]) -> [u64; 4] {
+    let mut s = state.map(Simd::<u64, 1>::splat);
+    let mut jumped = [0; 4];
+    for word in JUMP {
+        for bit in 0..64 {
+            if (word >> bit) & 1 == 1 {
+                for (x, y) in jumped.iter_mut().zip(&s) {
+                    *x ^= y[0];
+                }
+            }
+            step(&mut s);
+        }
+    }
+    jumped
+}
+
+/// The xoshiro256++ generator of Blackman and Vigna: 256 bits of state per lane, period
+/// `2^256 - 1`, fast and of excellent statistical quality, but not cryptographically secure.
+///
+/// Lane `i` starts at the state of lane 0 jumped ahead by `i · 2^128` steps, so it produces the
+/// same numbers as the scalar `rand_xoshiro::Xoshiro256PlusPlus` with the same seed after `i`
+/// calls of `jump`. [`next_u32`](SimdRng::next_u32) takes the high 32 bits of
+/// [`next_u64`](SimdRng::next_u64).
+#[derive(Clone, Debug)]
+pub struct Xoshiro256PlusPlus<const N: usize>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    state: [Simd<u64, N>; 4],
+}
+
+impl<const N: usize> Xoshiro256PlusPlus<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Creates a generator with the state of lane 0, the states of the other lanes are jumped.
+    ///
+    /// # Panics
+    /// Panics if `state` is all zeros, which is a fixed point of the generator.
+    pub fn from_state(state: [u64; 4]) -> Self {
+        assert!(state != [0; 4], "the state of xoshiro256++ is all zeros");
+        let mut lanes = [state; N];
+        for i in 1..N {
+            lanes[i] = jump(lanes[i - 1]);
+        }
+        Self {
+            state: core::array::from_fn(|j| Simd::from_array(lanes.map(|lane| lane[j]))),
+        }
+    }
+
+    /// Creates a generator with the state of lane 0 made of `seed` by the SplitMix64 generator,
+    /// like `rand_xoshiro` does.
+    pub fn seed_from_u64(seed: u64) -> Self {
+        let mut x = seed;
+        let state = [(); 4].map(|()| {
+            x = x.wrapping_add(GOLDEN_GAMMA);
+            let z = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            let z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        });
+        // SplitMix64 never gives four zeros in a row
+        Self::from_state(state)
+    }
+}
+
+impl<const N: usize> SimdRng<N> for Xoshiro256PlusPlus<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline]
+    fn next_u32(&mut self) -> Simd<u32, N> {
+        (self.next_u64() >> 32).cast()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> Simd<u64, N> {
+        step(&mut self.state)
+    }
+}
@@ -0,0 +1,560 @@
+//! Sampling of the normal and the exponential distributions by the ziggurat method of Marsaglia
+//! and Tsang, in the variant of Doornik, "An Improved Ziggurat Method to Generate Normal Random
+//! Samples" (2005).
+//!
+//! The region under the density is covered by layers of equal area: rectangles stacked upon a
+//! base layer which also covers the tail. A layer is chosen by the low random bits and a point of
+//! it by the high bits. The point is accepted right away if it is within the next layer, which is
+//! the case about 99% of the time. Otherwise it is in a wedge between the rectangle and the
+//! density, where it is accepted by comparing it with the density computed by
+//! [`Exponent::exp`], or in the base layer, whose tail is sampled from exponential variates. So
+//! no logarithm is needed, unlike for the Box–Muller transform, and there is no logarithm kernel
+//! in the crate anyway. Rejected lanes are sampled again until all lanes are accepted.
+
+use crate::{
+    math::Exponent,
+    rng::{sealed, unit_f32, unit_f64, RandomFloat, SimdRng},
+    simd::{
+        cmp::{SimdPartialEq, SimdPartialOrd},
+        num::SimdFloat,
+        LaneCount, Mask, Simd, SupportedLaneCount,
+    },
+};
+
+/// Start of the tail of the normal distribution, the width of the layer 1.
+const NORMAL_R: f64 = 3.442619855899;
+
+/// Widths of the layers of the normal distribution (its density is scaled to 1 at 0). The width
+/// of the base layer is its area divided by the height, the last width is 0.
+const NORMAL_X: [f64; 129] = [
+    3.7130862467425505,
+    3.442619855899,
+    3.2230849845811416,
+    3.0832288582168683,
+    2.9786962526477803,
+    2.894344007021529,
+    2.8231253505489105,
+    2.761169372387177,
+    2.7061135731218195,
+    2.6564064112613597,
+    2.6109722484318474,
+    2.569033625924938,
+    2.5300096723888275,
+    2.493454522095372,
+    2.4590181774118305,
+    2.42642064553375,
+    2.3954342780110625,
+    2.3658713701176386,
+    2.3375752413392368,
+    2.310413683698763,
+    2.2842740596774718,
+    2.2590595738691985,
+    2.2346863955909795,
+    2.2110814088787034,
+    2.188180432076049,
+    2.165926793748922,
+    2.1442701823603953,
+    2.1231657086739766,
+    2.1025731351892385,
+    2.082456237992017,
+    2.0627822745083084,
+    2.0435215366550676,
+    2.0246469733773855,
+    2.006133869963472,
+    1.98795957412762,
+    1.9701032608543265,
+    1.9525457295535567,
+    1.9352692282966228,
+    1.9182573008645099,
+    1.901494653105151,
+    1.884967035707759,
+    1.8686611409944887,
+    1.8525645117280911,
+    1.836665460258446,
+    1.8209529965961255,
+    1.8054167642192285,
+    1.7900469825998586,
+    1.7748343955860695,
+    1.7597702248995934,
+    1.7448461281138004,
+    1.7300541605637305,
+    1.7153867407136676,
+    1.7008366185699169,
+    1.6863968467791681,
+    1.672060754097601,
+    1.6578219209540241,
+    1.6436741568628686,
+    1.6296114794706347,
+    1.615628095043161,
+    1.6017183802213781,
+    1.5878768648905761,
+    1.5740982160230008,
+    1.560377222366169,
+    1.5467087798599104,
+    1.5330878776740433,
+    1.5195095847659401,
+    1.5059690368632033,
+    1.492461423781354,
+    1.4789819769899242,
+    1.4655259573427108,
+    1.4520886428892246,
+    1.4386653166845635,
+    1.42525125451406,
+    1.4118417124470577,
+    1.3984319141310053,
+    1.3850170377326518,
+    1.3715922024273426,
+    1.3581524543301435,
+    1.344692751753547,
+    1.3312079496656273,
+    1.317692783209414,
+    1.3041418501286168,
+    1.2905495919261964,
+    1.2769102735601556,
+    1.263217961454621,
+    1.2494664995730682,
+    1.2356494832633627,
+    1.2217602305399964,
+    1.2077917504159497,
+    1.1937367078331287,
+    1.1795873846639882,
+    1.1653356361647524,
+    1.1509728421488674,
+    1.1364898520131608,
+    1.1218769225825422,
+    1.107123647534036,
+    1.0922188769072774,
+    1.0771506248928957,
+    1.0619059636948243,
+    1.0464709007640454,
+    1.0308302360681956,
+    1.0149673952513305,
+    0.9988642334929836,
+    0.982500803515429,
+    0.9658550794011499,
+    0.9489026255113064,
+    0.9316161966151508,
+    0.9139652510230323,
+    0.8959153525809377,
+    0.8774274291129234,
+    0.8584568431938132,
+    0.8389522142975774,
+    0.8188539067003573,
+    0.7980920606440569,
+    0.7765839878947599,
+    0.7542306644540556,
+    0.7309119106424888,
+    0.7064796113354365,
+    0.6807479186691546,
+    0.6534786387399752,
+    0.6243585973360507,
+    0.5929629424714483,
+    0.5586921784081852,
+    0.5206560387620606,
+    0.4774378372966898,
+    0.4265479863554235,
+    0.36287143109703196,
+    0.27232086481396467,
+    0.0,
+];
+
+/// Start of the tail of the exponential distribution, the width of the layer 1.
+const EXP_R: f64 = 7.69711747013105;
+
+/// Widths of the layers of the exponential distribution, like [`NORMAL_X`].
+const EXP_X: [f64; 257] = [
+    8.697117470131085,
+    7.69711747013105,
+    6.941033629377211,
+    6.478378493832567,
+    6.14416466577247,
+    5.882144315795396,
+    5.66641016745403,
+    5.482890627526059,
+    5.3230905057543945,
+    5.1814872813014965,
+    5.0542884899813005,
+    4.938777085901247,
+    4.832939741025108,
+    4.735242996601737,
+    4.644491885420081,
+    4.559737061707347,
+    4.4802117465284175,
+    4.405287693473568,
+    4.334443680317268,
+    4.267242480277361,
+    4.20331371373518,
+    4.142340865664047,
+    4.084051310408293,
+    4.028208544647932,
+    3.9746060666737844,
+    3.9230625001354853,
+    3.8734176703995047,
+    3.8255294185223323,
+    3.7792709924116634,
+    3.734528894039793,
+    3.6912010902374144,
+    3.6491955157608493,
+    3.608428813128905,
+    3.568825265648333,
+    3.5303158891293394,
+    3.4928376547740556,
+    3.456332821132756,
+    3.420748357251116,
+    3.386035442460297,
+    3.3521490309001054,
+    3.319047470970744,
+    3.2866921715990647,
+    3.255047308570446,
+    3.22407956528626,
+    3.1937579032122363,
+    3.164053358025969,
+    3.134938858084436,
+    3.10638906233982,
+    3.078380215254086,
+    3.0508900166154507,
+    3.023897504455672,
+    2.997382949516126,
+    2.971327759921085,
+    2.9457143948950413,
+    2.9205262865127364,
+    2.8957477686001374,
+    2.871364012015532,
+    2.8473609656351844,
+    2.823725302450031,
+    2.8004443702507333,
+    2.777506146439752,
+    2.75489919656234,
+    2.7326126361946956,
+    2.7106360958679243,
+    2.688959688741799,
+    2.6675739807732617,
+    2.646469963151804,
+    2.625639026797783,
+    2.6050729387408302,
+    2.5847638202141354,
+    2.5647041263169,
+    2.5448866271118646,
+    2.5253043900378223,
+    2.5059507635285883,
+    2.486819361740204,
+    2.4679040502973595,
+    2.4491989329782444,
+    2.4306983392644144,
+    2.4123968126888653,
+    2.3942890999214526,
+    2.3763701405361353,
+    2.358635057409332,
+    2.341079147703029,
+    2.3236978743901906,
+    2.306486858283574,
+    2.2894418705322637,
+    2.272558825553149,
+    2.2558337743672134,
+    2.2392628983129033,
+    2.222842503111031,
+    2.206569013257658,
+    2.1904389667232143,
+    2.174449009937769,
+    2.15859589304388,
+    2.1428764653998362,
+    2.1272876713173625,
+    2.1118265460190364,
+    2.0964902118017092,
+    2.0812758743932194,
+    2.06618081949057,
+    2.0512024094685795,
+    2.0363380802487643,
+    2.021585338318921,
+    2.006941757894513,
+    1.992404978213571,
+    1.9779727009573547,
+    1.9636426877895423,
+    1.949412758007179,
+    1.9352807862970454,
+    1.9212447005915219,
+    1.9073024800183813,
+    1.8934521529393018,
+    1.879691795072205,
+    1.8660195276928215,
+    1.8524335159111693,
+    1.8389319670188735,
+    1.8255131289035134,
+    1.8121752885263842,
+    1.7989167704602844,
+    1.7857359354841194,
+    1.7726311792312988,
+    1.759600930889068,
+    1.7466436519460677,
+    1.733757834985565,
+    1.7209420025219289,
+    1.7081947058780513,
+    1.6955145241015315,
+    1.6829000629175475,
+    1.6703499537164457,
+    1.6578628525741663,
+    1.6454374393037172,
+    1.633072416535985,
+    1.6207665088282515,
+    1.608518461798852,
+    1.596327041286477,
+    1.5841910325326825,
+    1.5721092393862233,
+    1.5600804835278816,
+    1.5481036037145068,
+    1.5361774550410254,
+    1.5243009082192196,
+    1.5124728488721104,
+    1.5006921768428103,
+    1.4889578055167394,
+    1.4772686611561272,
+    1.4656236822457387,
+    1.454021818848787,
+    1.442462031972006,
+    1.4309432929388732,
+    1.4194645827699766,
+    1.408024891569529,
+    1.3966232179170355,
+    1.3852585682631156,
+    1.373929956328484,
+    1.3626364025050801,
+    1.3513769332583287,
+    1.3401505805294984,
+    1.3289563811371101,
+    1.3177933761763183,
+    1.3066606104151677,
+    1.2955571316865944,
+    1.284481990275006,
+    1.2734342382962345,
+    1.2624129290696087,
+    1.2514171164808459,
+    1.2404458543343997,
+    1.2294981956938424,
+    1.2185731922087835,
+    1.2076698934267542,
+    1.196787346088396,
+    1.185924593404195,
+    1.1750806743109043,
+    1.1642546227056716,
+    1.1534454666557674,
+    1.1426522275816655,
+    1.1318739194110714,
+    1.1211095477013233,
+    1.110358108727404,
+    1.0996185885325902,
+    1.0888899619385397,
+    1.0781711915113652,
+    1.0674612264799606,
+    1.0567590016025443,
+    1.0460634359770369,
+    1.0353734317905212,
+    1.02468787300261,
+    1.0140056239570894,
+    1.0033255279156894,
+    0.9926464055072685,
+    0.9819670530850552,
+    0.9712862409838959,
+    0.9606027116686591,
+    0.9499151777640685,
+    0.9392223199552548,
+    0.928522784747203,
+    0.9178151820700368,
+    0.9070980827156827,
+    0.8963700155898824,
+    0.8856294647617439,
+    0.8748748662910174,
+    0.8641046048109967,
+    0.8533170098423655,
+    0.8425103518103606,
+    0.8316828377342651,
+    0.8208326065544038,
+    0.8099577240574102,
+    0.799056177355479,
+    0.7881258688694843,
+    0.7771646097591214,
+    0.7661701127354262,
+    0.7551399841819736,
+    0.7440717155004994,
+    0.7329626735843566,
+    0.7218100903087473,
+    0.710611050909646,
+    0.699362481103223,
+    0.6880611327737388,
+    0.6767035680295135,
+    0.6652861413926686,
+    0.6538049798476555,
+    0.6422559604245269,
+    0.6306346849334806,
+    0.6189364513948664,
+    0.6071562216202903,
+    0.595288584291493,
+    0.5833277127487596,
+    0.5712673165325781,
+    0.5591005855115302,
+    0.5468201251632998,
+    0.5344178812371547,
+    0.5218850515921241,
+    0.5092119824436432,
+    0.49638804551865967,
+    0.48340149165345014,
+    0.4702392750821571,
+    0.45688684093140813,
+    0.44332786607354013,
+    0.42954394022539827,
+    0.4155141696003436,
+    0.40121467889626466,
+    0.3866179779411062,
+    0.3716921453299035,
+    0.3563997602583797,
+    0.34069648106483463,
+    0.3245291170168944,
+    0.3078329546749166,
+    0.29052795549121424,
+    0.2725131854784478,
+    0.25365836338589415,
+    0.23379048305965566,
+    0.21267151063094616,
+    0.1899586896224097,
+    0.1651276225641628,
+    0.1373049809399847,
+    0.10483850756578511,
+    0.06385216381495624,
+    0.0,
+];
+
+/// Looks up the widths of `layer` and the next layer in `table`.
+macro_rules! widths {
+    ($table: ident, $layer: expr, $ftype: ident) => {{
+        let layer = $layer.to_array();
+        (
+            Simd::from_array(core::array::from_fn(|i| {
+                $table[layer[i] as usize] as $ftype
+            })),
+            Simd::from_array(core::array::from_fn(|i| {
+                $table[layer[i] as usize + 1] as $ftype
+            })),
+        )
+    }};
+}
+
+macro_rules! impl_random_float {
+    ($( $ftype: ident, $next: ident, $unit: ident );+ $(;)?) => {
+        $(
+        impl sealed::Sealed for $ftype {}
+
+        impl RandomFloat for $ftype {
+            #[inline]
+            fn uniform<R: SimdRng<N>, const N: usize>(rng: &mut R) -> Simd<$ftype, N>
+            where
+                LaneCount<N>: SupportedLaneCount,
+            {
+                $unit(rng.$next())
+            }
+
+            fn normal<R: SimdRng<N>, const N: usize>(rng: &mut R) -> Simd<$ftype, N>
+            where
+                LaneCount<N>: SupportedLaneCount,
+            {
+                let one = Simd::splat(1.0);
+                let half = Simd::splat(0.5);
+                let mut result = Simd::splat(0.0);
+                let mut pending = Mask::splat(true);
+                loop {
+                    // the unit float is made of the high bits, the low 7 ones are free
+                    let bits = rng.$next();
+                    let layer = bits & Simd::splat(127);
+                    let u = $unit(bits) * Simd::splat(2.0) - one;
+                    let (width, next_width): (Simd<$ftype, N>, _) = widths!(NORMAL_X, layer, $ftype);
+                    let mut x = u * width;
+                    let mut accepted = x.abs().simd_lt(next_width);
+
+                    let rest = pending & !accepted;
+                    if rest.any() {
+                        let tail = rest & layer.simd_eq(Simd::splat(0));
+                        let wedge = rest & !tail;
+                        if wedge.any() {
+                            let x2 = x * x;
+                            let density = Exponent::exp(half * (x2 - width * width));
+                            let next_density = Exponent::exp(half * (x2 - next_width * next_width));
+                            let v = Self::uniform(rng);
+                            let y = next_density + v * (density - next_density);
+                            accepted |= wedge & y.simd_lt(one);
+                        }
+                        if tail.any() {
+                            // Marsaglia's method: the excess `t` over the start of the tail is
+                            // exponential with the rate `R`, accepted with the probability
+                            // `exp(-t²/2)`
+                            let r = Simd::splat(NORMAL_R as $ftype);
+                            let mut excess = Simd::splat(0.0);
+                            let mut tail_pending = tail;
+                            while tail_pending.any() {
+                                let t = Self::exponential(rng) / r;
+                                let e = Self::exponential(rng);
+                                let tail_accepted = tail_pending & (e + e).simd_gt(t * t);
+                                excess = tail_accepted.select(t, excess);
+                                tail_pending &= !tail_accepted;
+                            }
+                            x = tail.select((r + excess).copysign(u), x);
+                            accepted |= tail;
+                        }
+                    }
+
+                    result = (pending & accepted).select(x, result);
+                    pending &= !accepted;
+                    if !pending.any() {
+                        return result;
+                    }
+                }
+            }
+
+            fn exponential<R: SimdRng<N>, const N: usize>(rng: &mut R) -> Simd<$ftype, N>
+            where
+                LaneCount<N>: SupportedLaneCount,
+            {
+                let one = Simd::splat(1.0);
+                let mut result = Simd::splat(0.0);
+                let mut pending = Mask::splat(true);
+                loop {
+                    // the unit float is made of the high bits, the low 8 ones are free
+                    let bits = rng.$next();
+                    let layer = bits & Simd::splat(255);
+                    let (width, next_width): (Simd<$ftype, N>, _) = widths!(EXP_X, layer, $ftype);
+                    let mut x = $unit(bits) * width;
+                    let mut accepted = x.simd_lt(next_width);
+
+                    let rest = pending & !accepted;
+                    if rest.any() {
+                        let tail = rest & layer.simd_eq(Simd::splat(0));
+                        let wedge = rest & !tail;
+                        if wedge.any() {
+                            let density = Exponent::exp(x - width);
+                            let next_density = Exponent::exp(x - next_width);
+                            let v = Self::uniform(rng);
+                            let y = next_density + v * (density - next_density);
+                            accepted |= wedge & y.simd_lt(one);
+                        }
+                        if tail.any() {
+                            // the distribution is memoryless, so the tail is the start of it
+                            // plus another variate
+                            let tail_x = Simd::splat(EXP_R as $ftype) + Self::exponential(rng);
+                            x = tail.select(tail_x, x);
+                            accepted |= tail;
+                        }
+                    }
+
+                    result = (pending & accepted).select(x, result);
+                    pending &= !accepted;
+                    if !pending.any() {
+                        return result;
+                    }
+                }
+            }
+        }
+        )+
+    };
+}
+
+impl_random_float!(
+    f32, next_u32, unit_f32;
+    f64, next_u64, unit_f64;
+);
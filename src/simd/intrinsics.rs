@@ -0,0 +1,308 @@
+//! Float operations of vectors which have no equivalent in `core`.
+//!
+//! Lanes are processed in chunks of the native vector width with `core::arch` intrinsics. An
+//! operation is available only if the target supports it, so the choice of the algorithm depends
+//! on target features alone. Square root is computed in software as the last resort, so it is
+//! correctly rounded on every target.
+
+use super::{LaneCount, Simd, SupportedLaneCount};
+
+#[cfg(target_arch = "aarch64")]
+use core::arch::aarch64::*;
+#[cfg(all(target_arch = "x86", target_feature = "sse2"))]
+use core::arch::x86::*;
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+use core::arch::x86_64::*;
+
+pub(crate) trait FloatVector: Copy {
+    /// Fused multiply-add, i.e. `self * a + b` with a single rounding.
+    #[cfg(any(target_feature = "fma", target_arch = "aarch64"))]
+    fn fma(self, a: Self, b: Self) -> Self;
+    fn sqrt(self) -> Self;
+    #[cfg(any(target_feature = "sse4.1", target_arch = "aarch64"))]
+    fn trunc(self) -> Self;
+    #[cfg(any(target_feature = "sse4.1", target_arch = "aarch64"))]
+    fn floor(self) -> Self;
+    #[cfg(any(target_feature = "sse4.1", target_arch = "aarch64"))]
+    fn ceil(self) -> Self;
+    /// Rounds half-way cases to even.
+    #[cfg(any(target_feature = "sse4.1", target_arch = "aarch64"))]
+    fn round_ties_even(self) -> Self;
+}
+
+/// Applies `f` to chunks of `W` lanes of the `K` arguments. Both `N` and `W` are powers of two,
+/// so a vector shorter than a chunk is padded with zeros, and a longer one is split evenly.
+#[allow(dead_code)]
+#[inline(always)]
+fn chunked<T: Copy + Default, const N: usize, const K: usize, const W: usize>(
+    args: [[T; N]; K],
+    f: impl Fn([[T; W]; K]) -> [T; W],
+) -> [T; N] {
+    let mut result = [T::default(); N];
+    if N < W {
+        let mut chunk = [[T::default(); W]; K];
+        for (chunk, arg) in chunk.iter_mut().zip(&args) {
+            chunk[..N].copy_from_slice(arg);
+        }
+        result.copy_from_slice(&f(chunk)[..N]);
+    } else {
+        // the lengths of the copies are constant, so they are optimized out
+        for start in (0..N).step_by(W) {
+            let chunk = core::array::from_fn(|k| {
+                let mut lanes = [T::default(); W];
+                lanes.copy_from_slice(&args[k][start..start + W]);
+                lanes
+            });
+            result[start..start + W].copy_from_slice(&f(chunk));
+        }
+    }
+    result
+}
+
+/// Loads the arguments into native vectors, evaluates `$op` and stores the result.
+#[allow(unused_macros)]
+macro_rules! arch_map {
+    ($width: literal, $load: ident, $store: ident, [$( $arg: ident ),+] => $op: expr) => {
+        Simd::from_array(chunked([$( $arg.to_array() ),+], |[$( $arg ),+]| {
+            let mut result = [0.0; $width];
+            // SAFETY: the pointers are valid for `$width` lanes and the target supports `$op`
+            unsafe {
+                $( let $arg = $load($arg.as_ptr()); )+
+                $store(result.as_mut_ptr(), $op);
+            }
+            result
+        }))
+    };
+}
+
+/// Evaluates an x86 operation with the widest vectors supported by the target, but not wider
+/// than `N` lanes unless nothing narrower is available. The macro returns from the enclosing
+/// function. AVX-512 is used only by nightly compilers, since its intrinsics are unstable.
+#[allow(unused_macros)]
+macro_rules! x86_map {
+    (
+        $args: tt =>
+        sse: $sse_width: literal, $mm_load: ident, $mm_store: ident, $mm_op: expr;
+        avx: $avx_width: literal, $mm256_load: ident, $mm256_store: ident, $mm256_op: expr;
+        avx512: $avx512_width: literal, $mm512_load: ident, $mm512_store: ident, $mm512_op: expr $(;)?
+    ) => {{
+        #[cfg(all(target_feature = "avx512f", not(simd_shim)))]
+        if N >= $avx512_width {
+            return arch_map!($avx512_width, $mm512_load, $mm512_store, $args => $mm512_op);
+        }
+        #[cfg(target_feature = "avx")]
+        if N < $avx_width {
+            return arch_map!($sse_width, $mm_load, $mm_store, $args => $mm_op);
+        }
+        #[cfg(not(target_feature = "avx"))]
+        {
+            arch_map!($sse_width, $mm_load, $mm_store, $args => $mm_op)
+        }
+        #[cfg(target_feature = "avx")]
+        {
+            arch_map!($avx_width, $mm256_load, $mm256_store, $args => $mm256_op)
+        }
+    }};
+}
+
+macro_rules! impl_float_vector {
+    (
+        $ftype: ident, $utype: ident, $wide: ident,
+        sse: $sse_width: literal, $mm_load: ident, $mm_store: ident, $mm_sqrt: ident,
+        $mm_fmadd: ident, $mm_round: ident;
+        avx: $avx_width: literal, $mm256_load: ident, $mm256_store: ident, $mm256_sqrt: ident,
+        $mm256_fmadd: ident, $mm256_round: ident;
+        avx512: $avx512_width: literal, $mm512_load: ident, $mm512_store: ident,
+        $mm512_sqrt: ident, $mm512_fmadd: ident, $mm512_roundscale: ident;
+        aarch64: $neon_width: literal, $vld: ident, $vst: ident, $vsqrt: ident, $vfma: ident,
+        $vrnd: ident, $vrndm: ident, $vrndp: ident, $vrndn: ident
+    ) => {
+        impl<const N: usize> FloatVector for Simd<$ftype, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+        {
+            #[cfg(any(target_feature = "fma", target_arch = "aarch64"))]
+            #[inline(always)]
+            fn fma(self, a: Self, b: Self) -> Self {
+                let x = self;
+                #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "fma"))]
+                {
+                    x86_map!([x, a, b] =>
+                        sse: $sse_width, $mm_load, $mm_store, $mm_fmadd(x, a, b);
+                        avx: $avx_width, $mm256_load, $mm256_store, $mm256_fmadd(x, a, b);
+                        avx512: $avx512_width, $mm512_load, $mm512_store, $mm512_fmadd(x, a, b);
+                    )
+                }
+                #[cfg(target_arch = "aarch64")]
+                {
+                    arch_map!($neon_width, $vld, $vst, [x, a, b] => $vfma(b, x, a))
+                }
+            }
+
+            #[inline(always)]
+            fn sqrt(self) -> Self {
+                let x = self;
+                #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+                {
+                    x86_map!([x] =>
+                        sse: $sse_width, $mm_load, $mm_store, $mm_sqrt(x);
+                        avx: $avx_width, $mm256_load, $mm256_store, $mm256_sqrt(x);
+                        avx512: $avx512_width, $mm512_load, $mm512_store, $mm512_sqrt(x);
+                    )
+                }
+                #[cfg(target_arch = "aarch64")]
+                {
+                    arch_map!($neon_width, $vld, $vst, [x] => $vsqrt(x))
+                }
+                #[cfg(not(any(
+                    all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"),
+                    target_arch = "aarch64"
+                )))]
+                {
+                    const MANTISSA_BITS: u32 = $ftype::MANTISSA_DIGITS - 1;
+                    const BIAS: i32 = $ftype::MAX_EXP - 1;
+                    // the root of an integer of `WIDE_BITS` bits has at least 2 extra bits for
+                    // rounding
+                    const WIDE_BITS: u32 = 2 * $ftype::MANTISSA_DIGITS + 6;
+
+                    Simd::from_array(x.to_array().map(|x| {
+                        if x.is_nan() || x < 0.0 {
+                            return $ftype::NAN;
+                        }
+                        if x == 0.0 || x.is_infinite() {
+                            return x;
+                        }
+                        // x = mantissa * 2^exp
+                        let bits = x.to_bits();
+                        let exp_bits = (bits >> MANTISSA_BITS) as i32;
+                        let frac = bits & ((1 << MANTISSA_BITS) - 1);
+                        let (mantissa, exp) = if exp_bits == 0 {
+                            (frac, 1 - BIAS - MANTISSA_BITS as i32)
+                        } else {
+                            (frac | (1 << MANTISSA_BITS), exp_bits - BIAS - MANTISSA_BITS as i32)
+                        };
+                        // scale the mantissa to `WIDE_BITS` (or one less) bits keeping the
+                        // exponent even
+                        let mut shift = WIDE_BITS - ($utype::BITS - mantissa.leading_zeros());
+                        if (exp - shift as i32) % 2 != 0 {
+                            shift -= 1;
+                        }
+                        let wide = (mantissa as $wide) << shift;
+                        // Newton's iteration decreases monotonically to the integer root from any
+                        // initial value above the root
+                        let mut root: $wide = 1 << (($wide::BITS - wide.leading_zeros() + 1) / 2);
+                        loop {
+                            let next = (root + wide / root) >> 1;
+                            if next >= root {
+                                break;
+                            }
+                            root = next;
+                        }
+                        let sticky = (root * root != wide) as $wide;
+                        let scale = $ftype::from_bits(
+                            (((exp - shift as i32) / 2 + BIAS) as $utype) << MANTISSA_BITS,
+                        );
+                        // rounding happens only here since the root is wide enough to hold the
+                        // round and sticky bits
+                        (root | sticky) as $ftype * scale
+                    }))
+                }
+            }
+
+            #[cfg(any(target_feature = "sse4.1", target_arch = "aarch64"))]
+            #[inline(always)]
+            fn trunc(self) -> Self {
+                let x = self;
+                #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse4.1"))]
+                {
+                    const MODE: i32 = _MM_FROUND_TO_ZERO | _MM_FROUND_NO_EXC;
+                    x86_map!([x] =>
+                        sse: $sse_width, $mm_load, $mm_store, $mm_round::<MODE>(x);
+                        avx: $avx_width, $mm256_load, $mm256_store, $mm256_round::<MODE>(x);
+                        avx512: $avx512_width, $mm512_load, $mm512_store, $mm512_roundscale::<MODE>(x);
+                    )
+                }
+                #[cfg(target_arch = "aarch64")]
+                {
+                    arch_map!($neon_width, $vld, $vst, [x] => $vrnd(x))
+                }
+            }
+
+            #[cfg(any(target_feature = "sse4.1", target_arch = "aarch64"))]
+            #[inline(always)]
+            fn floor(self) -> Self {
+                let x = self;
+                #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse4.1"))]
+                {
+                    const MODE: i32 = _MM_FROUND_TO_NEG_INF | _MM_FROUND_NO_EXC;
+                    x86_map!([x] =>
+                        sse: $sse_width, $mm_load, $mm_store, $mm_round::<MODE>(x);
+                        avx: $avx_width, $mm256_load, $mm256_store, $mm256_round::<MODE>(x);
+                        avx512: $avx512_width, $mm512_load, $mm512_store, $mm512_roundscale::<MODE>(x);
+                    )
+                }
+                #[cfg(target_arch = "aarch64")]
+                {
+                    arch_map!($neon_width, $vld, $vst, [x] => $vrndm(x))
+                }
+            }
+
+            #[cfg(any(target_feature = "sse4.1", target_arch = "aarch64"))]
+            #[inline(always)]
+            fn ceil(self) -> Self {
+                let x = self;
+                #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse4.1"))]
+                {
+                    const MODE: i32 = _MM_FROUND_TO_POS_INF | _MM_FROUND_NO_EXC;
+                    x86_map!([x] =>
+                        sse: $sse_width, $mm_load, $mm_store, $mm_round::<MODE>(x);
+                        avx: $avx_width, $mm256_load, $mm256_store, $mm256_round::<MODE>(x);
+                        avx512: $avx512_width, $mm512_load, $mm512_store, $mm512_roundscale::<MODE>(x);
+                    )
+                }
+                #[cfg(target_arch = "aarch64")]
+                {
+                    arch_map!($neon_width, $vld, $vst, [x] => $vrndp(x))
+                }
+            }
+
+            #[cfg(any(target_feature = "sse4.1", target_arch = "aarch64"))]
+            #[inline(always)]
+            fn round_ties_even(self) -> Self {
+                let x = self;
+                #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse4.1"))]
+                {
+                    const MODE: i32 = _MM_FROUND_TO_NEAREST_INT | _MM_FROUND_NO_EXC;
+                    x86_map!([x] =>
+                        sse: $sse_width, $mm_load, $mm_store, $mm_round::<MODE>(x);
+                        avx: $avx_width, $mm256_load, $mm256_store, $mm256_round::<MODE>(x);
+                        avx512: $avx512_width, $mm512_load, $mm512_store, $mm512_roundscale::<MODE>(x);
+                    )
+                }
+                #[cfg(target_arch = "aarch64")]
+                {
+                    arch_map!($neon_width, $vld, $vst, [x] => $vrndn(x))
+                }
+            }
+        }
+    };
+}
+
+impl_float_vector!(
+    f32, u32, u64,
+    sse: 4, _mm_loadu_ps, _mm_storeu_ps, _mm_sqrt_ps, _mm_fmadd_ps, _mm_round_ps;
+    avx: 8, _mm256_loadu_ps, _mm256_storeu_ps, _mm256_sqrt_ps, _mm256_fmadd_ps, _mm256_round_ps;
+    avx512: 16, _mm512_loadu_ps, _mm512_storeu_ps, _mm512_sqrt_ps, _mm512_fmadd_ps,
+        _mm512_roundscale_ps;
+    aarch64: 4, vld1q_f32, vst1q_f32, vsqrtq_f32, vfmaq_f32, vrndq_f32, vrndmq_f32, vrndpq_f32,
+        vrndnq_f32
+);
+impl_float_vector!(
+    f64, u64, u128,
+    sse: 2, _mm_loadu_pd, _mm_storeu_pd, _mm_sqrt_pd, _mm_fmadd_pd, _mm_round_pd;
+    avx: 4, _mm256_loadu_pd, _mm256_storeu_pd, _mm256_sqrt_pd, _mm256_fmadd_pd, _mm256_round_pd;
+    avx512: 8, _mm512_loadu_pd, _mm512_storeu_pd, _mm512_sqrt_pd, _mm512_fmadd_pd,
+        _mm512_roundscale_pd;
+    aarch64: 2, vld1q_f64, vst1q_f64, vsqrtq_f64, vfmaq_f64, vrndq_f64, vrndmq_f64, vrndpq_f64,
+        vrndnq_f64
+);
@@ -0,0 +1,15 @@
+//! Vector types the crate is built upon.
+//!
+//! Re-exports `core::simd` if the compiler has it. Otherwise, with `stable` feature enabled,
+//! provides array-backed replacements for the subset of its API used by the crate instead, so the
+//! crate builds with a stable compiler.
+
+pub(crate) mod intrinsics;
+
+#[cfg(not(simd_shim))]
+pub use core::simd::*;
+
+#[cfg(simd_shim)]
+mod stable;
+#[cfg(simd_shim)]
+pub use stable::*;
@@ -0,0 +1,114 @@
+//! Traits for comparing vectors elementwise.
+
+use super::{LaneCount, Mask, Simd, SimdElement, SupportedLaneCount};
+
+/// Parallel `PartialEq`.
+pub trait SimdPartialEq {
+    /// The mask type returned by each comparison.
+    type Mask;
+
+    /// Test if each element is equal to the corresponding element in `other`.
+    fn simd_eq(self, other: Self) -> Self::Mask;
+
+    /// Test if each element is not equal to the corresponding element in `other`.
+    fn simd_ne(self, other: Self) -> Self::Mask;
+}
+
+/// Parallel `PartialOrd`.
+pub trait SimdPartialOrd: SimdPartialEq {
+    /// Test if each element is less than the corresponding element in `other`.
+    fn simd_lt(self, other: Self) -> Self::Mask;
+
+    /// Test if each element is less than or equal to the corresponding element in `other`.
+    fn simd_le(self, other: Self) -> Self::Mask;
+
+    /// Test if each element is greater than the corresponding element in `other`.
+    fn simd_gt(self, other: Self) -> Self::Mask;
+
+    /// Test if each element is greater than or equal to the corresponding element in `other`.
+    fn simd_ge(self, other: Self) -> Self::Mask;
+}
+
+/// Parallel `Ord`.
+pub trait SimdOrd: SimdPartialOrd {
+    /// Returns the elementwise maximum with `other`.
+    fn simd_max(self, other: Self) -> Self;
+
+    /// Returns the elementwise minimum with `other`.
+    fn simd_min(self, other: Self) -> Self;
+
+    /// Restrict each element to a certain interval.
+    ///
+    /// # Panics
+    /// Panics if `min > max` on any element.
+    fn simd_clamp(self, min: Self, max: Self) -> Self;
+}
+
+impl<T, const N: usize> SimdPartialEq for Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: SimdElement + PartialEq,
+{
+    type Mask = Mask<T::Mask, N>;
+
+    #[inline]
+    fn simd_eq(self, other: Self) -> Self::Mask {
+        self.test_zip(other, |a, b| a == b)
+    }
+
+    #[inline]
+    fn simd_ne(self, other: Self) -> Self::Mask {
+        self.test_zip(other, |a, b| a != b)
+    }
+}
+
+impl<T, const N: usize> SimdPartialOrd for Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: SimdElement + PartialOrd,
+{
+    #[inline]
+    fn simd_lt(self, other: Self) -> Self::Mask {
+        self.test_zip(other, |a, b| a < b)
+    }
+
+    #[inline]
+    fn simd_le(self, other: Self) -> Self::Mask {
+        self.test_zip(other, |a, b| a <= b)
+    }
+
+    #[inline]
+    fn simd_gt(self, other: Self) -> Self::Mask {
+        self.test_zip(other, |a, b| a > b)
+    }
+
+    #[inline]
+    fn simd_ge(self, other: Self) -> Self::Mask {
+        self.test_zip(other, |a, b| a >= b)
+    }
+}
+
+impl<T, const N: usize> SimdOrd for Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: SimdElement + Ord,
+{
+    #[inline]
+    fn simd_max(self, other: Self) -> Self {
+        self.zip(other, Ord::max)
+    }
+
+    #[inline]
+    fn simd_min(self, other: Self) -> Self {
+        self.zip(other, Ord::min)
+    }
+
+    #[inline]
+    fn simd_clamp(self, min: Self, max: Self) -> Self {
+        assert!(
+            min.simd_le(max).all(),
+            "each element in `min` must be less than or equal to the corresponding element in `max`",
+        );
+        self.simd_max(min).simd_min(max)
+    }
+}
@@ -0,0 +1,403 @@
+//! Array-backed vector types with the API of `core::simd`.
+//!
+//! Operations are implemented lane-wise and rely on autovectorization.
+
+use core::{fmt, marker::PhantomData};
+
+pub mod cmp;
+pub mod num;
+pub mod prelude;
+
+mod ops;
+mod swizzle;
+
+pub use swizzle::Swizzle;
+
+mod sealed {
+    pub trait Sealed {}
+}
+use sealed::Sealed;
+
+/// Specifies the number of lanes in a [`Simd`] vector as a type.
+pub struct LaneCount<const N: usize>;
+
+/// Statically guarantees that a lane count is supported, i.e. is a power of two up to 64.
+pub trait SupportedLaneCount: Sealed {}
+
+macro_rules! supported_lane_count {
+    ($( $lanes: literal ),*) => {
+        $(
+        impl Sealed for LaneCount<$lanes> {}
+        impl SupportedLaneCount for LaneCount<$lanes> {}
+        )*
+    };
+}
+
+supported_lane_count!(1, 2, 4, 8, 16, 32, 64);
+
+/// Marker trait for types which may be used as [`Mask`] elements.
+pub trait MaskElement: Sealed + Copy + PartialEq {}
+
+/// Marker trait for types which may be used as [`Simd`] elements.
+pub trait SimdElement: Sealed + Copy {
+    /// The mask element type corresponding to this element type.
+    type Mask: MaskElement;
+}
+
+/// Conversion of a lane from `T` with the semantics of `as` operator.
+pub trait SimdCast<T>: SimdElement {
+    fn cast_from(value: T) -> Self;
+}
+
+macro_rules! simd_element {
+    ($( $etype: ty => $mtype: ty ),*) => {
+        $(
+        impl Sealed for $etype {}
+        impl SimdElement for $etype {
+            type Mask = $mtype;
+        }
+        )*
+    };
+}
+
+simd_element!(
+    f32 => i32, f64 => i64, i8 => i8, u8 => i8, i16 => i16, u16 => i16, i32 => i32, u32 => i32,
+    i64 => i64, u64 => i64
+);
+
+impl MaskElement for i8 {}
+impl MaskElement for i16 {}
+impl MaskElement for i32 {}
+impl MaskElement for i64 {}
+
+macro_rules! simd_cast {
+    ($( $to: ty ),*) => {
+        simd_cast!(@to [$( $to ),*] [$( $to ),*]);
+    };
+    (@to [$( $to: ty ),*] $from: tt) => {
+        $( simd_cast!(@from $to, $from); )*
+    };
+    (@from $to: ty, [$( $from: ty ),*]) => {
+        $(
+        impl SimdCast<$from> for $to {
+            #[inline]
+            fn cast_from(value: $from) -> $to {
+                value as $to
+            }
+        }
+        )*
+    };
+}
+
+simd_cast!(f32, f64, i8, u8, i16, u16, i32, u32, i64, u64);
+
+/// A SIMD vector with the shape of `[T; N]`.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Simd<T, const N: usize>([T; N])
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: SimdElement;
+
+impl<T, const N: usize> Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: SimdElement,
+{
+    /// Number of elements in this vector.
+    pub const LEN: usize = N;
+
+    /// Constructs a new vector with all elements set to `value`.
+    #[inline]
+    pub fn splat(value: T) -> Self {
+        Self([value; N])
+    }
+
+    #[inline]
+    pub const fn from_array(array: [T; N]) -> Self {
+        Self(array)
+    }
+
+    #[inline]
+    pub const fn to_array(self) -> [T; N] {
+        self.0
+    }
+
+    #[inline]
+    pub const fn as_array(&self) -> &[T; N] {
+        &self.0
+    }
+
+    #[inline]
+    pub fn as_mut_array(&mut self) -> &mut [T; N] {
+        &mut self.0
+    }
+
+    /// Constructs a vector from the first `N` elements of `slice`.
+    ///
+    /// # Panics
+    /// Panics if the slice's length is less than `N`.
+    #[inline]
+    pub fn from_slice(slice: &[T]) -> Self {
+        assert!(
+            slice.len() >= N,
+            "slice length must be at least the number of elements"
+        );
+        let mut array = [slice[0]; N];
+        array.copy_from_slice(&slice[..N]);
+        Self(array)
+    }
+
+    /// Writes the elements to the first `N` elements of `slice`.
+    ///
+    /// # Panics
+    /// Panics if the slice's length is less than `N`.
+    #[inline]
+    pub fn copy_to_slice(self, slice: &mut [T]) {
+        assert!(
+            slice.len() >= N,
+            "slice length must be at least the number of elements"
+        );
+        slice[..N].copy_from_slice(&self.0);
+    }
+
+    #[inline]
+    pub(crate) fn map<U: SimdElement>(self, f: impl FnMut(T) -> U) -> Simd<U, N> {
+        Simd(self.0.map(f))
+    }
+
+    #[inline]
+    pub(crate) fn zip<U: SimdElement>(
+        self,
+        other: Self,
+        mut f: impl FnMut(T, T) -> U,
+    ) -> Simd<U, N> {
+        let mut i = 0;
+        Simd(self.0.map(|a| {
+            let b = other.0[i];
+            i += 1;
+            f(a, b)
+        }))
+    }
+
+    #[inline]
+    pub(crate) fn test_each<M: MaskElement>(self, f: impl FnMut(T) -> bool) -> Mask<M, N> {
+        Mask::from_array(self.0.map(f))
+    }
+
+    #[inline]
+    pub(crate) fn test_zip<M: MaskElement>(
+        self,
+        other: Self,
+        mut f: impl FnMut(T, T) -> bool,
+    ) -> Mask<M, N> {
+        let mut i = 0;
+        Mask::from_array(self.0.map(|a| {
+            let b = other.0[i];
+            i += 1;
+            f(a, b)
+        }))
+    }
+}
+
+impl<T, const N: usize> Default for Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: SimdElement + Default,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::splat(T::default())
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: SimdElement + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: SimdElement,
+{
+    #[inline]
+    fn from(array: [T; N]) -> Self {
+        Self(array)
+    }
+}
+
+impl<T, const N: usize> From<Simd<T, N>> for [T; N]
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: SimdElement,
+{
+    #[inline]
+    fn from(vector: Simd<T, N>) -> Self {
+        vector.0
+    }
+}
+
+impl<T, const N: usize> core::ops::Index<usize> for Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: SimdElement,
+{
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> core::ops::IndexMut<usize> for Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: SimdElement,
+{
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.0[index]
+    }
+}
+
+/// A SIMD vector mask for `N` elements of width specified by `T`.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Mask<T, const N: usize>([bool; N], PhantomData<T>)
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: MaskElement;
+
+impl<T, const N: usize> Mask<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: MaskElement,
+{
+    /// Constructs a mask by setting all elements to `value`.
+    #[inline]
+    pub fn splat(value: bool) -> Self {
+        Self([value; N], PhantomData)
+    }
+
+    #[inline]
+    pub const fn from_array(array: [bool; N]) -> Self {
+        Self(array, PhantomData)
+    }
+
+    #[inline]
+    pub const fn to_array(self) -> [bool; N] {
+        self.0
+    }
+
+    /// Tests the value of the specified element.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than or equal to the number of elements.
+    #[inline]
+    pub fn test(&self, index: usize) -> bool {
+        self.0[index]
+    }
+
+    /// Sets the value of the specified element.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than or equal to the number of elements.
+    #[inline]
+    pub fn set(&mut self, index: usize, value: bool) {
+        self.0[index] = value;
+    }
+
+    /// Returns true if any element is set.
+    #[inline]
+    pub fn any(self) -> bool {
+        self.0.iter().fold(false, |acc, &x| acc | x)
+    }
+
+    /// Returns true if all elements are set.
+    #[inline]
+    pub fn all(self) -> bool {
+        self.0.iter().fold(true, |acc, &x| acc & x)
+    }
+
+    /// Converts the mask to a mask of any other element width.
+    #[inline]
+    pub fn cast<U: MaskElement>(self) -> Mask<U, N> {
+        Mask(self.0, PhantomData)
+    }
+
+    /// Creates a bitmask from the mask. The bit `i` is set if the element `i` is set.
+    #[inline]
+    pub fn to_bitmask(self) -> u64 {
+        self.0
+            .iter()
+            .enumerate()
+            .fold(0, |bitmask, (i, &x)| bitmask | (u64::from(x) << i))
+    }
+
+    /// Creates a mask from a bitmask. The element `i` is set if the bit `i` is set.
+    #[inline]
+    pub fn from_bitmask(bitmask: u64) -> Self {
+        let mut i = 0;
+        Self::from_array([(); N].map(|()| {
+            i += 1;
+            bitmask & (1 << (i - 1)) != 0
+        }))
+    }
+
+    /// Chooses elements from two vectors: from `true_values` where the mask is set and from
+    /// `false_values` otherwise.
+    #[inline]
+    pub fn select<U>(self, true_values: Simd<U, N>, false_values: Simd<U, N>) -> Simd<U, N>
+    where
+        U: SimdElement<Mask = T>,
+    {
+        let mut i = 0;
+        Simd(self.0.map(|x| {
+            i += 1;
+            if x {
+                true_values.0[i - 1]
+            } else {
+                false_values.0[i - 1]
+            }
+        }))
+    }
+}
+
+impl<T, const N: usize> Default for Mask<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: MaskElement,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::splat(false)
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Mask<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: MaskElement,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T, const N: usize> From<[bool; N]> for Mask<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: MaskElement,
+{
+    #[inline]
+    fn from(array: [bool; N]) -> Self {
+        Self::from_array(array)
+    }
+}
@@ -0,0 +1,365 @@
+//! Traits for vectors of numbers.
+
+use super::{LaneCount, Mask, Simd, SimdCast, SimdElement, SupportedLaneCount};
+
+/// Operations on vectors of floats.
+pub trait SimdFloat: Copy {
+    /// Mask type used for manipulating this vector type.
+    type Mask;
+    /// Scalar type contained by this vector type.
+    type Scalar;
+    /// Bit representation of this vector type.
+    type Bits;
+    /// Vector of `T` with the same number of elements.
+    type Cast<T: SimdElement>;
+
+    /// Performs an elementwise conversion with the semantics of `as` operator.
+    fn cast<T: SimdCast<Self::Scalar>>(self) -> Self::Cast<T>;
+
+    /// Rounds toward zero and converts to the same-width integer type.
+    ///
+    /// # Safety
+    /// The value must not be NaN or infinite and must be representable in the target type
+    /// after truncation.
+    unsafe fn to_int_unchecked<I: SimdCast<Self::Scalar>>(self) -> Self::Cast<I>;
+
+    /// Raw transmutation to an unsigned integer vector type with the same size and number of
+    /// elements.
+    fn to_bits(self) -> Self::Bits;
+
+    /// Raw transmutation from an unsigned integer vector type with the same size and number of
+    /// elements.
+    fn from_bits(bits: Self::Bits) -> Self;
+
+    /// Produces a vector where every element has the absolute value of the corresponding element
+    /// in `self`.
+    fn abs(self) -> Self;
+
+    /// Takes the reciprocal (inverse) of each element, `1/x`.
+    fn recip(self) -> Self;
+
+    /// Returns true for each element if it is NaN.
+    fn is_nan(self) -> Self::Mask;
+
+    /// Returns true for each element if it is neither infinite nor NaN.
+    fn is_finite(self) -> Self::Mask;
+
+    /// Returns true for each element if it is positive or negative infinity.
+    fn is_infinite(self) -> Self::Mask;
+
+    /// Returns true for each element if it has a positive sign, including `+0.0`, NaNs with
+    /// positive sign bit and positive infinity.
+    fn is_sign_positive(self) -> Self::Mask;
+
+    /// Returns true for each element if it has a negative sign, including `-0.0`, NaNs with
+    /// negative sign bit and negative infinity.
+    fn is_sign_negative(self) -> Self::Mask;
+
+    /// Replaces each element with a number that represents its sign: `1.0` if the number is
+    /// positive, `+0.0` or `INFINITY`, `-1.0` if the number is negative, `-0.0` or
+    /// `NEG_INFINITY` and `NAN` if the number is `NAN`.
+    fn signum(self) -> Self;
+
+    /// Returns each element with the magnitude of `self` and the sign of `sign`.
+    fn copysign(self, sign: Self) -> Self;
+
+    /// Returns the minimum of each element. If one of the values is NaN, the other is returned.
+    fn simd_min(self, other: Self) -> Self;
+
+    /// Returns the maximum of each element. If one of the values is NaN, the other is returned.
+    fn simd_max(self, other: Self) -> Self;
+
+    /// Restrict each element to a certain interval unless it is NaN.
+    ///
+    /// # Panics
+    /// Panics if `min > max`, `min` is NaN, or `max` is NaN on any element.
+    fn simd_clamp(self, min: Self, max: Self) -> Self;
+
+    /// Returns the sum of the elements of the vector.
+    fn reduce_sum(self) -> Self::Scalar;
+
+    /// Returns the product of the elements of the vector.
+    fn reduce_product(self) -> Self::Scalar;
+
+    /// Returns the maximum element of the vector, ignoring NaNs.
+    fn reduce_max(self) -> Self::Scalar;
+
+    /// Returns the minimum element of the vector, ignoring NaNs.
+    fn reduce_min(self) -> Self::Scalar;
+}
+
+/// Operations on vectors of signed integers.
+pub trait SimdInt: Copy {
+    /// Mask type used for manipulating this vector type.
+    type Mask;
+    /// Scalar type contained by this vector type.
+    type Scalar;
+    /// Vector of `T` with the same number of elements.
+    type Cast<T: SimdElement>;
+
+    /// Performs an elementwise conversion with the semantics of `as` operator.
+    fn cast<T: SimdCast<Self::Scalar>>(self) -> Self::Cast<T>;
+
+    /// Lanewise absolute value, wrapping on overflow.
+    fn abs(self) -> Self;
+
+    /// Returns true for each positive element and false if it is zero or negative.
+    fn is_positive(self) -> Self::Mask;
+
+    /// Returns true for each negative element and false if it is zero or positive.
+    fn is_negative(self) -> Self::Mask;
+
+    /// Returns the sum of the elements of the vector, with wrapping addition.
+    fn reduce_sum(self) -> Self::Scalar;
+
+    /// Returns the maximum element of the vector.
+    fn reduce_max(self) -> Self::Scalar;
+
+    /// Returns the minimum element of the vector.
+    fn reduce_min(self) -> Self::Scalar;
+}
+
+/// Operations on vectors of unsigned integers.
+pub trait SimdUint: Copy {
+    /// Scalar type contained by this vector type.
+    type Scalar;
+    /// Vector of `T` with the same number of elements.
+    type Cast<T: SimdElement>;
+
+    /// Performs an elementwise conversion with the semantics of `as` operator.
+    fn cast<T: SimdCast<Self::Scalar>>(self) -> Self::Cast<T>;
+
+    /// Returns the sum of the elements of the vector, with wrapping addition.
+    fn reduce_sum(self) -> Self::Scalar;
+
+    /// Returns the maximum element of the vector.
+    fn reduce_max(self) -> Self::Scalar;
+
+    /// Returns the minimum element of the vector.
+    fn reduce_min(self) -> Self::Scalar;
+}
+
+macro_rules! impl_simd_float {
+    ($ftype: ident, $itype: ty, $utype: ident) => {
+        impl<const N: usize> SimdFloat for Simd<$ftype, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+        {
+            type Mask = Mask<$itype, N>;
+            type Scalar = $ftype;
+            type Bits = Simd<$utype, N>;
+            type Cast<T: SimdElement> = Simd<T, N>;
+
+            #[inline]
+            fn cast<T: SimdCast<$ftype>>(self) -> Simd<T, N> {
+                self.map(T::cast_from)
+            }
+
+            #[inline]
+            unsafe fn to_int_unchecked<I: SimdCast<$ftype>>(self) -> Simd<I, N> {
+                self.cast()
+            }
+
+            #[inline]
+            fn to_bits(self) -> Self::Bits {
+                self.map($ftype::to_bits)
+            }
+
+            #[inline]
+            fn from_bits(bits: Self::Bits) -> Self {
+                bits.map($ftype::from_bits)
+            }
+
+            #[inline]
+            fn abs(self) -> Self {
+                Self::from_bits(self.to_bits() & Simd::splat(!(1 << ($utype::BITS - 1))))
+            }
+
+            #[inline]
+            fn recip(self) -> Self {
+                Simd::splat(1.0) / self
+            }
+
+            #[inline]
+            fn is_nan(self) -> Self::Mask {
+                self.test_each($ftype::is_nan)
+            }
+
+            #[inline]
+            fn is_finite(self) -> Self::Mask {
+                self.test_each($ftype::is_finite)
+            }
+
+            #[inline]
+            fn is_infinite(self) -> Self::Mask {
+                self.test_each($ftype::is_infinite)
+            }
+
+            #[inline]
+            fn is_sign_positive(self) -> Self::Mask {
+                self.test_each($ftype::is_sign_positive)
+            }
+
+            #[inline]
+            fn is_sign_negative(self) -> Self::Mask {
+                self.test_each($ftype::is_sign_negative)
+            }
+
+            #[inline]
+            fn signum(self) -> Self {
+                self.is_nan().select(self, Simd::splat(1.0).copysign(self))
+            }
+
+            #[inline]
+            fn copysign(self, sign: Self) -> Self {
+                let sign_bit = Simd::splat(1 << ($utype::BITS - 1));
+                Self::from_bits((self.to_bits() & !sign_bit) | (sign.to_bits() & sign_bit))
+            }
+
+            #[inline]
+            fn simd_min(self, other: Self) -> Self {
+                self.zip(other, $ftype::min)
+            }
+
+            #[inline]
+            fn simd_max(self, other: Self) -> Self {
+                self.zip(other, $ftype::max)
+            }
+
+            #[inline]
+            fn simd_clamp(self, min: Self, max: Self) -> Self {
+                assert!(
+                    min.test_zip::<$itype>(max, |min, max| min <= max).all(),
+                    "each element in `min` must be less than or equal to the corresponding element in `max`",
+                );
+                self.zip(min, |x, min| if x < min { min } else { x })
+                    .zip(max, |x, max| if x > max { max } else { x })
+            }
+
+            #[inline]
+            fn reduce_sum(self) -> $ftype {
+                self.to_array().iter().fold(0.0, |acc, &x| acc + x)
+            }
+
+            #[inline]
+            fn reduce_product(self) -> $ftype {
+                self.to_array().iter().fold(1.0, |acc, &x| acc * x)
+            }
+
+            #[inline]
+            fn reduce_max(self) -> $ftype {
+                self.to_array().iter().fold($ftype::NAN, |acc, &x| acc.max(x))
+            }
+
+            #[inline]
+            fn reduce_min(self) -> $ftype {
+                self.to_array().iter().fold($ftype::NAN, |acc, &x| acc.min(x))
+            }
+        }
+    };
+}
+
+impl_simd_float!(f32, i32, u32);
+impl_simd_float!(f64, i64, u64);
+
+macro_rules! impl_simd_int {
+    ($itype: ident) => {
+        impl<const N: usize> SimdInt for Simd<$itype, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+        {
+            type Mask = Mask<$itype, N>;
+            type Scalar = $itype;
+            type Cast<T: SimdElement> = Simd<T, N>;
+
+            #[inline]
+            fn cast<T: SimdCast<$itype>>(self) -> Simd<T, N> {
+                self.map(T::cast_from)
+            }
+
+            #[inline]
+            fn abs(self) -> Self {
+                self.map($itype::wrapping_abs)
+            }
+
+            #[inline]
+            fn is_positive(self) -> Self::Mask {
+                self.test_each($itype::is_positive)
+            }
+
+            #[inline]
+            fn is_negative(self) -> Self::Mask {
+                self.test_each($itype::is_negative)
+            }
+
+            #[inline]
+            fn reduce_sum(self) -> $itype {
+                self.to_array()
+                    .iter()
+                    .fold(0, |acc, &x| acc.wrapping_add(x))
+            }
+
+            #[inline]
+            fn reduce_max(self) -> $itype {
+                self.to_array()
+                    .iter()
+                    .fold($itype::MIN, |acc, &x| acc.max(x))
+            }
+
+            #[inline]
+            fn reduce_min(self) -> $itype {
+                self.to_array()
+                    .iter()
+                    .fold($itype::MAX, |acc, &x| acc.min(x))
+            }
+        }
+    };
+}
+
+impl_simd_int!(i8);
+impl_simd_int!(i16);
+impl_simd_int!(i32);
+impl_simd_int!(i64);
+
+macro_rules! impl_simd_uint {
+    ($utype: ident) => {
+        impl<const N: usize> SimdUint for Simd<$utype, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+        {
+            type Scalar = $utype;
+            type Cast<T: SimdElement> = Simd<T, N>;
+
+            #[inline]
+            fn cast<T: SimdCast<$utype>>(self) -> Simd<T, N> {
+                self.map(T::cast_from)
+            }
+
+            #[inline]
+            fn reduce_sum(self) -> $utype {
+                self.to_array()
+                    .iter()
+                    .fold(0, |acc, &x| acc.wrapping_add(x))
+            }
+
+            #[inline]
+            fn reduce_max(self) -> $utype {
+                self.to_array()
+                    .iter()
+                    .fold($utype::MIN, |acc, &x| acc.max(x))
+            }
+
+            #[inline]
+            fn reduce_min(self) -> $utype {
+                self.to_array()
+                    .iter()
+                    .fold($utype::MAX, |acc, &x| acc.min(x))
+            }
+        }
+    };
+}
+
+impl_simd_uint!(u8);
+impl_simd_uint!(u16);
+impl_simd_uint!(u32);
+impl_simd_uint!(u64);
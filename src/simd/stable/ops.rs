@@ -0,0 +1,194 @@
+use core::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, DivAssign,
+    Mul, MulAssign, Neg, Not, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+};
+
+use super::{LaneCount, Mask, MaskElement, Simd, SupportedLaneCount};
+
+macro_rules! binary_op {
+    ($etype: ty, $trait: ident :: $fn: ident, $assign_trait: ident :: $assign_fn: ident, $op: expr) => {
+        impl<const N: usize> $trait for Simd<$etype, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self, rhs: Self) -> Self {
+                self.zip(rhs, $op)
+            }
+        }
+
+        impl<const N: usize> $trait<$etype> for Simd<$etype, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self, rhs: $etype) -> Self {
+                self.zip(Simd::splat(rhs), $op)
+            }
+        }
+
+        impl<const N: usize> $assign_trait for Simd<$etype, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+        {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = $trait::$fn(*self, rhs);
+            }
+        }
+
+        impl<const N: usize> $assign_trait<$etype> for Simd<$etype, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+        {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: $etype) {
+                *self = $trait::$fn(*self, rhs);
+            }
+        }
+    };
+}
+
+macro_rules! float_ops {
+    ($( $ftype: ty ),*) => {
+        $(
+        binary_op!($ftype, Add::add, AddAssign::add_assign, |a, b| a + b);
+        binary_op!($ftype, Sub::sub, SubAssign::sub_assign, |a, b| a - b);
+        binary_op!($ftype, Mul::mul, MulAssign::mul_assign, |a, b| a * b);
+        binary_op!($ftype, Div::div, DivAssign::div_assign, |a, b| a / b);
+        binary_op!($ftype, Rem::rem, RemAssign::rem_assign, |a, b| a % b);
+
+        impl<const N: usize> Neg for Simd<$ftype, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                self.map(|x| -x)
+            }
+        }
+        )*
+    };
+}
+
+float_ops!(f32, f64);
+
+// Integer operations wrap on overflow, shifts wrap the shift amount.
+macro_rules! int_ops {
+    ($( $itype: ty ),*) => {
+        $(
+        binary_op!($itype, Add::add, AddAssign::add_assign, <$itype>::wrapping_add);
+        binary_op!($itype, Sub::sub, SubAssign::sub_assign, <$itype>::wrapping_sub);
+        binary_op!($itype, Mul::mul, MulAssign::mul_assign, <$itype>::wrapping_mul);
+        binary_op!($itype, Div::div, DivAssign::div_assign, |a, b| a / b);
+        binary_op!($itype, Rem::rem, RemAssign::rem_assign, |a, b| a % b);
+        binary_op!($itype, BitAnd::bitand, BitAndAssign::bitand_assign, |a, b| a & b);
+        binary_op!($itype, BitOr::bitor, BitOrAssign::bitor_assign, |a, b| a | b);
+        binary_op!($itype, BitXor::bitxor, BitXorAssign::bitxor_assign, |a, b| a ^ b);
+        binary_op!($itype, Shl::shl, ShlAssign::shl_assign, |a, b| a.wrapping_shl(b as u32));
+        binary_op!($itype, Shr::shr, ShrAssign::shr_assign, |a, b| a.wrapping_shr(b as u32));
+
+        impl<const N: usize> Not for Simd<$itype, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn not(self) -> Self {
+                self.map(|x| !x)
+            }
+        }
+        )*
+    };
+}
+
+int_ops!(i8, u8, i16, u16, i32, u32, i64, u64);
+
+macro_rules! signed_int_neg {
+    ($( $itype: ty ),*) => {
+        $(
+        impl<const N: usize> Neg for Simd<$itype, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                self.map(<$itype>::wrapping_neg)
+            }
+        }
+        )*
+    };
+}
+
+signed_int_neg!(i8, i16, i32, i64);
+
+macro_rules! mask_op {
+    ($trait: ident :: $fn: ident, $assign_trait: ident :: $assign_fn: ident, $op: tt) => {
+        impl<T, const N: usize> $trait for Mask<T, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+            T: MaskElement,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self, rhs: Self) -> Self {
+                let mut i = 0;
+                Mask::from_array(self.to_array().map(|a| {
+                    i += 1;
+                    a $op rhs.test(i - 1)
+                }))
+            }
+        }
+
+        impl<T, const N: usize> $trait<bool> for Mask<T, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+            T: MaskElement,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self, rhs: bool) -> Self {
+                $trait::$fn(self, Mask::splat(rhs))
+            }
+        }
+
+        impl<T, const N: usize> $assign_trait for Mask<T, N>
+        where
+            LaneCount<N>: SupportedLaneCount,
+            T: MaskElement,
+        {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = $trait::$fn(*self, rhs);
+            }
+        }
+    };
+}
+
+mask_op!(BitAnd::bitand, BitAndAssign::bitand_assign, &);
+mask_op!(BitOr::bitor, BitOrAssign::bitor_assign, |);
+mask_op!(BitXor::bitxor, BitXorAssign::bitxor_assign, ^);
+
+impl<T, const N: usize> Not for Mask<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: MaskElement,
+{
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self {
+        Mask::from_array(self.to_array().map(|x| !x))
+    }
+}
@@ -0,0 +1,121 @@
+//! The portable SIMD prelude.
+
+#![allow(non_camel_case_types)]
+
+pub use super::{
+    cmp::{SimdOrd, SimdPartialEq, SimdPartialOrd},
+    num::{SimdFloat, SimdInt, SimdUint},
+    Mask, Simd,
+};
+
+pub type f32x1 = Simd<f32, 1>;
+pub type f32x2 = Simd<f32, 2>;
+pub type f32x4 = Simd<f32, 4>;
+pub type f32x8 = Simd<f32, 8>;
+pub type f32x16 = Simd<f32, 16>;
+pub type f32x32 = Simd<f32, 32>;
+pub type f32x64 = Simd<f32, 64>;
+
+pub type f64x1 = Simd<f64, 1>;
+pub type f64x2 = Simd<f64, 2>;
+pub type f64x4 = Simd<f64, 4>;
+pub type f64x8 = Simd<f64, 8>;
+pub type f64x16 = Simd<f64, 16>;
+pub type f64x32 = Simd<f64, 32>;
+pub type f64x64 = Simd<f64, 64>;
+
+pub type i8x1 = Simd<i8, 1>;
+pub type i8x2 = Simd<i8, 2>;
+pub type i8x4 = Simd<i8, 4>;
+pub type i8x8 = Simd<i8, 8>;
+pub type i8x16 = Simd<i8, 16>;
+pub type i8x32 = Simd<i8, 32>;
+pub type i8x64 = Simd<i8, 64>;
+
+pub type u8x1 = Simd<u8, 1>;
+pub type u8x2 = Simd<u8, 2>;
+pub type u8x4 = Simd<u8, 4>;
+pub type u8x8 = Simd<u8, 8>;
+pub type u8x16 = Simd<u8, 16>;
+pub type u8x32 = Simd<u8, 32>;
+pub type u8x64 = Simd<u8, 64>;
+
+pub type i16x1 = Simd<i16, 1>;
+pub type i16x2 = Simd<i16, 2>;
+pub type i16x4 = Simd<i16, 4>;
+pub type i16x8 = Simd<i16, 8>;
+pub type i16x16 = Simd<i16, 16>;
+pub type i16x32 = Simd<i16, 32>;
+pub type i16x64 = Simd<i16, 64>;
+
+pub type u16x1 = Simd<u16, 1>;
+pub type u16x2 = Simd<u16, 2>;
+pub type u16x4 = Simd<u16, 4>;
+pub type u16x8 = Simd<u16, 8>;
+pub type u16x16 = Simd<u16, 16>;
+pub type u16x32 = Simd<u16, 32>;
+pub type u16x64 = Simd<u16, 64>;
+
+pub type i32x1 = Simd<i32, 1>;
+pub type i32x2 = Simd<i32, 2>;
+pub type i32x4 = Simd<i32, 4>;
+pub type i32x8 = Simd<i32, 8>;
+pub type i32x16 = Simd<i32, 16>;
+pub type i32x32 = Simd<i32, 32>;
+pub type i32x64 = Simd<i32, 64>;
+
+pub type u32x1 = Simd<u32, 1>;
+pub type u32x2 = Simd<u32, 2>;
+pub type u32x4 = Simd<u32, 4>;
+pub type u32x8 = Simd<u32, 8>;
+pub type u32x16 = Simd<u32, 16>;
+pub type u32x32 = Simd<u32, 32>;
+pub type u32x64 = Simd<u32, 64>;
+
+pub type i64x1 = Simd<i64, 1>;
+pub type i64x2 = Simd<i64, 2>;
+pub type i64x4 = Simd<i64, 4>;
+pub type i64x8 = Simd<i64, 8>;
+pub type i64x16 = Simd<i64, 16>;
+pub type i64x32 = Simd<i64, 32>;
+pub type i64x64 = Simd<i64, 64>;
+
+pub type u64x1 = Simd<u64, 1>;
+pub type u64x2 = Simd<u64, 2>;
+pub type u64x4 = Simd<u64, 4>;
+pub type u64x8 = Simd<u64, 8>;
+pub type u64x16 = Simd<u64, 16>;
+pub type u64x32 = Simd<u64, 32>;
+pub type u64x64 = Simd<u64, 64>;
+
+pub type mask8x1 = Mask<i8, 1>;
+pub type mask8x2 = Mask<i8, 2>;
+pub type mask8x4 = Mask<i8, 4>;
+pub type mask8x8 = Mask<i8, 8>;
+pub type mask8x16 = Mask<i8, 16>;
+pub type mask8x32 = Mask<i8, 32>;
+pub type mask8x64 = Mask<i8, 64>;
+
+pub type mask16x1 = Mask<i16, 1>;
+pub type mask16x2 = Mask<i16, 2>;
+pub type mask16x4 = Mask<i16, 4>;
+pub type mask16x8 = Mask<i16, 8>;
+pub type mask16x16 = Mask<i16, 16>;
+pub type mask16x32 = Mask<i16, 32>;
+pub type mask16x64 = Mask<i16, 64>;
+
+pub type mask32x1 = Mask<i32, 1>;
+pub type mask32x2 = Mask<i32, 2>;
+pub type mask32x4 = Mask<i32, 4>;
+pub type mask32x8 = Mask<i32, 8>;
+pub type mask32x16 = Mask<i32, 16>;
+pub type mask32x32 = Mask<i32, 32>;
+pub type mask32x64 = Mask<i32, 64>;
+
+pub type mask64x1 = Mask<i64, 1>;
+pub type mask64x2 = Mask<i64, 2>;
+pub type mask64x4 = Mask<i64, 4>;
+pub type mask64x8 = Mask<i64, 8>;
+pub type mask64x16 = Mask<i64, 16>;
+pub type mask64x32 = Mask<i64, 32>;
+pub type mask64x64 = Mask<i64, 64>;
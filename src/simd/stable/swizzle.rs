@@ -0,0 +1,68 @@
+//! Rearrangement of vector elements.
+
+use super::{LaneCount, Simd, SimdElement, SupportedLaneCount};
+
+/// Creates a vector from the elements of another vector, the `i`-th element of the result is
+/// the element `INDEX[i]` of the input.
+pub trait Swizzle<const N: usize> {
+    /// Map from the element index of the output vector to the element index of the input vector.
+    const INDEX: [usize; N];
+
+    /// Creates a new vector from the elements of `vector`.
+    ///
+    /// # Panics
+    /// Panics if any index of `INDEX` is out of bounds of `vector`.
+    #[inline]
+    fn swizzle<T, const M: usize>(vector: Simd<T, M>) -> Simd<T, N>
+    where
+        T: SimdElement,
+        LaneCount<N>: SupportedLaneCount,
+        LaneCount<M>: SupportedLaneCount,
+    {
+        Simd::from_array(Self::INDEX.map(|i| vector[i]))
+    }
+
+    /// Creates a new vector from the elements of `first` and `second`, index `M + i` refers to the
+    /// element `i` of `second`.
+    ///
+    /// # Panics
+    /// Panics if any index of `INDEX` is out of bounds of the concatenation of the vectors.
+    #[inline]
+    fn concat_swizzle<T, const M: usize>(first: Simd<T, M>, second: Simd<T, M>) -> Simd<T, N>
+    where
+        T: SimdElement,
+        LaneCount<N>: SupportedLaneCount,
+        LaneCount<M>: SupportedLaneCount,
+    {
+        Simd::from_array(Self::INDEX.map(|i| if i < M { first[i] } else { second[i - M] }))
+    }
+}
+
+impl<T, const N: usize> Simd<T, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+    T: SimdElement,
+{
+    /// Reverses the order of the elements in the vector.
+    #[inline]
+    pub fn reverse(self) -> Self {
+        let mut array = self.to_array();
+        array.reverse();
+        Self::from_array(array)
+    }
+}
+
+impl<const N: usize> Simd<u8, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Swizzles the vector by indices of `idxs` known at runtime, the element `i` of the result
+    /// is the element `idxs[i]` of the vector, or zero if the index is out of bounds.
+    #[inline]
+    pub fn swizzle_dyn(self, idxs: Simd<u8, N>) -> Self {
+        Simd::from_array(
+            idxs.to_array()
+                .map(|i| self.as_array().get(i as usize).copied().unwrap_or_default()),
+        )
+    }
+}
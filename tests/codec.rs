@@ -0,0 +1,269 @@
+//! Tests of `codec` against the `base64` and `hex` crates. Valid text is corrupted randomly to
+//! check that the same inputs are rejected, and that the errors point at the invalid bytes.
+#![cfg_attr(not(simd_shim), feature(portable_simd))]
+
+#[allow(dead_code)]
+mod common;
+
+use base64::{
+    alphabet,
+    engine::{general_purpose, DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine,
+};
+use common::{corrupt, Corruption};
+use proptest::prelude::*;
+use simd_addons::codec::{
+    base64::{self as simd_base64, Alphabet, Config, Padding},
+    hex::{self as simd_hex, Case},
+    DecodeError,
+};
+
+/// Configurations of our and the reference codecs.
+fn configs() -> [(Config, GeneralPurpose); 6] {
+    let optional =
+        GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+    [
+        (Config::STANDARD, general_purpose::STANDARD),
+        (Config::STANDARD_NO_PAD, general_purpose::STANDARD_NO_PAD),
+        (Config::URL_SAFE, general_purpose::URL_SAFE),
+        (Config::URL_SAFE_NO_PAD, general_purpose::URL_SAFE_NO_PAD),
+        (
+            Config {
+                alphabet: Alphabet::Standard,
+                padding: Padding::Optional,
+            },
+            GeneralPurpose::new(&alphabet::STANDARD, optional),
+        ),
+        (
+            Config {
+                alphabet: Alphabet::UrlSafe,
+                padding: Padding::Optional,
+            },
+            GeneralPurpose::new(&alphabet::URL_SAFE, optional),
+        ),
+    ]
+}
+
+fn base64_decode(text: &[u8], config: Config) -> Result<Vec<u8>, DecodeError> {
+    // longer than the data, to check that the rest of it is intact
+    let mut data = vec![0xaa; simd_base64::decoded_len(text) + 32];
+    let len = simd_base64::decode(text, &mut data, config)?;
+    assert_eq!(len, simd_base64::decoded_len(text));
+    assert!(data[len..].iter().all(|&byte| byte == 0xaa));
+    data.truncate(len);
+    Ok(data)
+}
+
+fn check_base64_roundtrip(data: &[u8]) {
+    for (config, reference) in configs() {
+        let mut text = vec![0; simd_base64::encoded_len(data.len(), config) + 32];
+        let len = simd_base64::encode(data, &mut text, config);
+        assert_eq!(len, simd_base64::encoded_len(data.len(), config));
+        assert!(text[len..].iter().all(|&byte| byte == 0));
+        text.truncate(len);
+        assert_eq!(text, reference.encode(data).into_bytes(), "{:?}", config);
+        assert_eq!(base64_decode(&text, config), Ok(data.to_vec()));
+    }
+}
+
+/// Checks decoding of arbitrary text: the result is the same as the reference one, and errors
+/// are justified by the input.
+fn check_base64_decode(text: &[u8], config: Config, reference: &GeneralPurpose) {
+    let result = base64_decode(text, config);
+    let symbols = text.len() - text.iter().rev().take_while(|&&b| b == b'=').count().min(2);
+    match result {
+        Ok(data) => assert_eq!(Ok(data), reference.decode(text), "{:?}", config),
+        Err(DecodeError::InvalidByte { offset, byte }) => {
+            assert_eq!(text[offset], byte);
+            assert!(offset < symbols);
+            // the byte isn't decoded even in a valid group
+            let group = [byte, b'A', b'A', b'A'];
+            assert!(simd_base64::decode(&group, &mut [0; 3], config).is_err());
+            assert!(reference.decode(text).is_err(), "{:?}", text);
+        }
+        Err(DecodeError::InvalidLength { length }) => {
+            assert_eq!(length, text.len());
+            assert_eq!(symbols % 4, 1);
+            assert!(reference.decode(text).is_err());
+        }
+        Err(DecodeError::InvalidLastSymbol { offset, byte }) => {
+            assert_eq!(offset, symbols - 1);
+            assert_eq!(text[offset], byte);
+            assert!(reference.decode(text).is_err());
+        }
+        Err(DecodeError::InvalidPadding { offset }) => {
+            assert!(offset == symbols || offset == text.len());
+            // the reference accepts partial padding if the padding is optional
+            if config.padding != Padding::Optional || text.len() - symbols + symbols % 4 == 4 {
+                assert!(reference.decode(text).is_err(), "{:?}", text);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_base64_lengths() {
+    for len in 0..200 {
+        let data: Vec<u8> = (0..len).map(|i| (i * 73 + 11) as u8).collect();
+        check_base64_roundtrip(&data);
+    }
+}
+
+#[test]
+fn test_base64_errors() {
+    let mut data = [0; 75];
+    let decode = |text: &[u8], config, data: &mut [u8]| simd_base64::decode(text, data, config);
+    assert_eq!(
+        decode(b"Zm9v!mFy", Config::STANDARD, &mut data),
+        Err(DecodeError::InvalidByte {
+            offset: 4,
+            byte: b'!'
+        })
+    );
+    assert_eq!(
+        decode(b"Zm9v_mFy", Config::STANDARD, &mut data),
+        Err(DecodeError::InvalidByte {
+            offset: 4,
+            byte: b'_'
+        })
+    );
+    assert_eq!(decode(b"Zm9v_mFy", Config::URL_SAFE, &mut data), Ok(6));
+    assert_eq!(
+        decode(b"Zm9vY", Config::STANDARD_NO_PAD, &mut data),
+        Err(DecodeError::InvalidLength { length: 5 })
+    );
+    assert_eq!(
+        decode(b"Zm8", Config::STANDARD, &mut data),
+        Err(DecodeError::InvalidPadding { offset: 3 })
+    );
+    assert_eq!(
+        decode(b"Zm8=", Config::STANDARD_NO_PAD, &mut data),
+        Err(DecodeError::InvalidPadding { offset: 3 })
+    );
+    assert_eq!(
+        decode(b"Zg=", Config::STANDARD, &mut data),
+        Err(DecodeError::InvalidPadding { offset: 2 })
+    );
+    assert_eq!(
+        decode(b"Zm9=", Config::STANDARD, &mut data),
+        Err(DecodeError::InvalidLastSymbol {
+            offset: 2,
+            byte: b'9'
+        })
+    );
+    assert_eq!(
+        decode(b"Zm=v", Config::STANDARD, &mut data),
+        Err(DecodeError::InvalidByte {
+            offset: 2,
+            byte: b'='
+        })
+    );
+    // invalid bytes in the vectorized part of the text
+    let mut text = [b'A'; 100];
+    for i in 0..text.len() {
+        text[i] = b'.';
+        assert_eq!(
+            decode(&text, Config::STANDARD, &mut data),
+            Err(DecodeError::InvalidByte {
+                offset: i,
+                byte: b'.'
+            })
+        );
+        text[i] = b'A';
+    }
+}
+
+#[test]
+#[should_panic(expected = "output is shorter than the encoded data")]
+fn test_base64_encode_short() {
+    simd_base64::encode(b"hello", &mut [0; 7], Config::STANDARD);
+}
+
+#[test]
+#[should_panic(expected = "output is shorter than the decoded data")]
+fn test_base64_decode_short() {
+    let _ = simd_base64::decode(b"aGVsbG8=", &mut [0; 4], Config::STANDARD);
+}
+
+fn check_hex(data: &[u8]) {
+    for (case, reference) in [
+        (Case::Lower, hex::encode(data)),
+        (Case::Upper, hex::encode_upper(data)),
+    ] {
+        let mut text = vec![0; 2 * data.len()];
+        assert_eq!(simd_hex::encode(data, &mut text, case), text.len());
+        assert_eq!(text, reference.into_bytes());
+        let mut decoded = vec![0; data.len()];
+        assert_eq!(simd_hex::decode(&text, &mut decoded), Ok(data.len()));
+        assert_eq!(decoded, data);
+    }
+}
+
+fn check_hex_decode(text: &[u8]) {
+    let mut data = vec![0; text.len() / 2];
+    let result = simd_hex::decode(text, &mut data).map(|len| data[..len].to_vec());
+    let expected = hex::decode(text).map_err(|error| match error {
+        hex::FromHexError::InvalidHexCharacter { c, index } => DecodeError::InvalidByte {
+            offset: index,
+            byte: c as u8,
+        },
+        hex::FromHexError::OddLength => DecodeError::InvalidLength { length: text.len() },
+        hex::FromHexError::InvalidStringLength => unreachable!(),
+    });
+    assert_eq!(result, expected, "{:?}", text);
+}
+
+#[test]
+fn test_hex_lengths() {
+    for len in 0..200 {
+        let data: Vec<u8> = (0..len).map(|i| (i * 73 + 11) as u8).collect();
+        check_hex(&data);
+        let mut text = hex::encode(&data).into_bytes();
+        for i in 0..text.len() {
+            let digit = text[i];
+            text[i] = b'g';
+            check_hex_decode(&text);
+            text[i] = digit;
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "output is shorter than the decoded data")]
+fn test_hex_decode_short() {
+    let _ = simd_hex::decode(b"abcd", &mut [0; 1]);
+}
+
+fn corruption() -> impl Strategy<Value = Corruption> {
+    common::corruption(prop_oneof![Just(b'='), Just(b'+'), Just(b'_'), any::<u8>()])
+}
+
+proptest! {
+    #[test]
+    fn random_base64(
+        data in prop::collection::vec(any::<u8>(), 0..300),
+        corruptions in prop::collection::vec(corruption(), 1..3),
+    ) {
+        check_base64_roundtrip(&data);
+        for (config, reference) in configs() {
+            let mut text = reference.encode(&data).into_bytes();
+            for corruption in corruptions.iter().cloned() {
+                corrupt(&mut text, corruption);
+                check_base64_decode(&text, config, &reference);
+            }
+        }
+    }
+
+    #[test]
+    fn random_hex(
+        data in prop::collection::vec(any::<u8>(), 0..300),
+        corruptions in prop::collection::vec(corruption(), 1..3),
+    ) {
+        check_hex(&data);
+        let mut text = hex::encode(&data).into_bytes();
+        for corruption in corruptions {
+            corrupt(&mut text, corruption);
+            check_hex_decode(&text);
+        }
+    }
+}
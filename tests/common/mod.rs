@@ -1,3 +1,4 @@
+use proptest::prelude::*;
 use std::ops::Range;
 
 pub trait Linspace {
@@ -21,7 +22,7 @@ impl Linspace for Range<f64> {
 #[macro_export]
 macro_rules! simd_fn {
     ($x: tt $( .$func: tt ( $( $args: tt ),* )) *) => {
-        std::simd::Simd::<_, 1>::splat($x)$( .$func( $( std::simd::Simd::splat($args) ),* ) )*[0]
+        simd_addons::simd::Simd::<_, 1>::splat($x)$( .$func( $( simd_addons::simd::Simd::splat($args) ),* ) )*[0]
     };
 }
 
@@ -49,3 +50,83 @@ macro_rules! approx_test_simd_fn {
     };
 
 }
+
+/// Runs `$body` for every supported lane count, with the count bound to the constant `$lanes`.
+#[macro_export]
+macro_rules! for_each_lane_count {
+    (|$lanes: ident| $body: block) => {
+        for_each_lane_count!(@counts $lanes, $body, 1, 2, 4, 8, 16, 32, 64)
+    };
+
+    (@counts $lanes: ident, $body: block, $( $count: literal ),+) => {
+        $(
+        // `$body` is written for any lane count
+        #[allow(clippy::modulo_one)]
+        {
+            const $lanes: usize = $count;
+            $body
+        }
+        )+
+    };
+}
+
+/// Evaluates `$func` by vectors of `$lanes` lanes, with each `$x` bound to consecutive lanes of
+/// the corresponding `$values`, and collects the results. The last vectors are padded with the
+/// first values of their chunks.
+#[macro_export]
+macro_rules! simd_map {
+    ($lanes: expr, $( $x: ident in $values: expr ),+ => $func: expr) => {{
+        $( let $x: &[_] = &$values; )+
+        let len = [$( $x.len() ),+][0];
+        let mut result = Vec::with_capacity(len);
+        for start in (0..len).step_by($lanes) {
+            let end = len.min(start + $lanes);
+            let chunk_result = {
+                $(
+                let mut lanes = [$x[start]; $lanes];
+                lanes[..end - start].copy_from_slice(&$x[start..end]);
+                let $x = simd_addons::simd::Simd::<_, { $lanes }>::from_array(lanes);
+                )+
+                $func
+            };
+            result.extend_from_slice(&chunk_result.to_array()[..end - start]);
+        }
+        result
+    }};
+}
+
+/// Edits of valid text which (mostly) make it invalid.
+#[derive(Clone, Debug)]
+pub enum Corruption {
+    Replace(usize, u8),
+    Insert(usize, u8),
+    Remove(usize),
+    Truncate(usize),
+}
+
+/// Corruptions at any position, which replace or insert `bytes`.
+pub fn corruption(bytes: impl Strategy<Value = u8> + Clone) -> impl Strategy<Value = Corruption> {
+    prop_oneof![
+        (any::<usize>(), bytes.clone()).prop_map(|(i, byte)| Corruption::Replace(i, byte)),
+        (any::<usize>(), bytes).prop_map(|(i, byte)| Corruption::Insert(i, byte)),
+        any::<usize>().prop_map(Corruption::Remove),
+        any::<usize>().prop_map(Corruption::Truncate),
+    ]
+}
+
+/// Applies `corruption` to `text`, with its position taken modulo the length.
+pub fn corrupt(text: &mut Vec<u8>, corruption: Corruption) {
+    let position = |i: usize| i % (text.len() + 1);
+    match corruption {
+        Corruption::Replace(i, byte) if !text.is_empty() => {
+            let i = i % text.len();
+            text[i] = byte;
+        }
+        Corruption::Insert(i, byte) => text.insert(position(i), byte),
+        Corruption::Remove(i) if !text.is_empty() => {
+            text.remove(i % text.len());
+        }
+        Corruption::Truncate(i) => text.truncate(position(i)),
+        _ => {}
+    }
+}
@@ -0,0 +1,143 @@
+//! Tests of `algo::compress` against filtering by iterators. Every selection of lanes is checked
+//! for vectors of up to 8 lanes, which are permuted by lookup tables, and random ones for longer
+//! vectors.
+#![cfg_attr(not(simd_shim), feature(portable_simd))]
+
+#[allow(dead_code)]
+#[macro_use]
+mod common;
+
+use proptest::prelude::*;
+use simd_addons::{
+    algo::compress,
+    simd::{cmp::SimdPartialOrd, Mask, Simd},
+};
+
+/// Selections of lanes of a vector: all of them for short vectors, pseudo-random ones otherwise.
+fn selections(lanes: usize) -> Vec<u64> {
+    if lanes <= 8 {
+        return (0..1 << lanes).collect();
+    }
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let mut selections = vec![0, u64::MAX >> (64 - lanes)];
+    selections.extend((0..200).map(|_| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 11) & (u64::MAX >> (64 - lanes))
+    }));
+    selections
+}
+
+macro_rules! test_compress {
+    ($etype: ident, $strategy: expr) => {
+        paste::paste! {
+        mod [< compress_ $etype >] {
+            use super::*;
+
+            #[test]
+            fn lanes() {
+                for_each_lane_count!(|LANES| {
+                    let lanes = Simd::<$etype, LANES>::from_array(core::array::from_fn(|i| (i + 1) as $etype));
+                    let fallback = Simd::splat(100 as $etype);
+                    for bits in selections(LANES) {
+                        let mask = Mask::from_array(core::array::from_fn(|i| (bits >> i) & 1 == 1));
+                        let selected: Vec<$etype> = (0..LANES)
+                            .filter(|&i| mask.test(i))
+                            .map(|i| lanes[i])
+                            .collect();
+
+                        let (packed, count) = compress::compress(lanes, mask);
+                        assert_eq!(&packed.as_array()[..count], selected.as_slice(), "compress by {:b}", bits);
+
+                        // exact outputs and longer ones, whose rest must be intact (no lane is 77)
+                        for extra in [0, 1, LANES] {
+                            let mut out = vec![77 as $etype; count + extra];
+                            assert_eq!(compress::compress_store(lanes, mask, &mut out), count);
+                            assert_eq!(&out[..count], selected.as_slice());
+                            assert!(out[count..].iter().all(|&x| x == 77 as $etype));
+                        }
+
+                        let mut source = 0;
+                        let expected = Simd::<$etype, LANES>::from_array(core::array::from_fn(|i| {
+                            if mask.test(i) {
+                                source += 1;
+                                lanes[source - 1]
+                            } else {
+                                fallback[i]
+                            }
+                        }));
+                        assert_eq!(compress::expand(lanes, mask, fallback), expected, "expand by {:b}", bits);
+                        assert_eq!(compress::expand_load(&lanes.as_array()[..count], mask, fallback), expected);
+                    }
+                });
+            }
+
+            fn check_filter(data: &[$etype]) {
+                let threshold = data.first().copied().unwrap_or_default();
+                let accepted: Vec<$etype> = data.iter().copied().filter(|&x| x < threshold).collect();
+                let rejected: Vec<$etype> = data.iter().copied().filter(|&x| x >= threshold).collect();
+                for_each_lane_count!(|LANES| {
+                    let predicate = |v: Simd<$etype, LANES>| v.simd_lt(Simd::splat(threshold));
+
+                    // outputs of the exact length and the length of the input
+                    for len in [accepted.len(), data.len()] {
+                        let mut out = vec![0 as $etype; len];
+                        let count = compress::filter(data, &mut out, predicate);
+                        assert_eq!(&out[..count], accepted.as_slice(), "{} lanes", LANES);
+                    }
+
+                    let mut yes = vec![0 as $etype; accepted.len()];
+                    let mut no = vec![0 as $etype; data.len()];
+                    let count = compress::partition(data, &mut yes, &mut no, predicate);
+                    assert_eq!(&yes[..count], accepted.as_slice());
+                    assert_eq!(&no[..data.len() - count], rejected.as_slice());
+                });
+            }
+
+            #[test]
+            fn lengths() {
+                for len in 0..200 {
+                    let data: Vec<$etype> = (0..len).map(|i| ((i * 37 + 50) % 101) as $etype).collect();
+                    check_filter(&data);
+                }
+            }
+
+            proptest! {
+                #[test]
+                fn random(data in prop::collection::vec($strategy, 0..500)) {
+                    check_filter(&data);
+                }
+            }
+        }
+        }
+    };
+}
+
+test_compress!(i32, any::<i32>());
+test_compress!(u32, any::<u32>());
+test_compress!(f32, -1e6f32..1e6);
+test_compress!(i64, any::<i64>());
+test_compress!(u64, any::<u64>());
+test_compress!(f64, -1e6f64..1e6);
+
+#[test]
+#[should_panic(expected = "output is shorter than the number of selected lanes")]
+fn test_compress_store_short() {
+    let lanes = Simd::<f32, 4>::splat(1.0);
+    compress::compress_store(lanes, Mask::splat(true), &mut [0.0; 3]);
+}
+
+#[test]
+#[should_panic(expected = "input is shorter than the number of selected lanes")]
+fn test_expand_load_short() {
+    compress::expand_load(&[1u64], Mask::<i64, 2>::splat(true), Simd::splat(0));
+}
+
+#[test]
+#[should_panic(expected = "output is shorter than the number of selected elements")]
+fn test_filter_short() {
+    compress::filter(&[1, 2, 3], &mut [0; 2], |v: Simd<i32, 4>| {
+        v.simd_gt(Simd::splat(0))
+    });
+}
@@ -0,0 +1,224 @@
+//! Tests of `fft` against a naive DFT computed in `f64`, for all lengths from 1 to the length of
+//! the plan. Errors are measured relative to the root mean square of the exact spectrum and
+//! allowed to grow with the number of stages.
+#![cfg_attr(not(simd_shim), feature(portable_simd))]
+
+use proptest::{collection::vec, prelude::*};
+use simd_addons::fft::{Fft, FftFloat};
+
+const LEN: usize = 1024;
+
+/// The naive DFT, with the sign of the exponent being `sign`.
+fn dft(re: &[f64], im: &[f64], sign: f64) -> (Vec<f64>, Vec<f64>) {
+    let len = re.len();
+    (0..len)
+        .map(|k| {
+            (0..len).fold((0.0, 0.0), |(sum_re, sum_im), j| {
+                // the product is reduced modulo `len` to keep the angle exact
+                let angle = sign * 2.0 * std::f64::consts::PI * ((j * k) % len) as f64 / len as f64;
+                let (sin, cos) = angle.sin_cos();
+                (
+                    sum_re + re[j] * cos - im[j] * sin,
+                    sum_im + re[j] * sin + im[j] * cos,
+                )
+            })
+        })
+        .unzip()
+}
+
+/// Checks that `re` and `im` are close to `expected`, relative to its root mean square, by
+/// `epsilon` times the number of stages of a transform of length `len`.
+fn check_close(re: &[f64], im: &[f64], expected: (&[f64], &[f64]), len: usize, epsilon: f64) {
+    let square = |(x, y): (&f64, &f64)| x * x + y * y;
+    let norm = expected.0.iter().zip(expected.1).map(square).sum::<f64>();
+    let error_re = re.iter().zip(expected.0).map(|(x, y)| x - y);
+    let error_im = im.iter().zip(expected.1).map(|(x, y)| x - y);
+    let error = error_re
+        .zip(error_im)
+        .map(|(x, y)| x * x + y * y)
+        .sum::<f64>();
+    let tolerance = 2.0 * epsilon * (1 + len.trailing_zeros()) as f64;
+    assert!(
+        error.sqrt() <= tolerance * norm.sqrt(),
+        "relative error {} of length {len}",
+        (error / norm).sqrt()
+    );
+}
+
+fn to_f64<T: FftFloat + Into<f64>>(slice: &[T]) -> Vec<f64> {
+    slice.iter().map(|&x| x.into()).collect()
+}
+
+fn check_complex<T: FftFloat + Into<f64>>(fft: &Fft<T>, re: &[T], im: &[T], epsilon: f64) {
+    let len = re.len();
+    let (mut out_re, mut out_im) = (re.to_vec(), im.to_vec());
+    fft.forward(&mut out_re, &mut out_im);
+    let (expected_re, expected_im) = dft(&to_f64(re), &to_f64(im), -1.0);
+    let expected = (&expected_re[..], &expected_im[..]);
+    check_close(&to_f64(&out_re), &to_f64(&out_im), expected, len, epsilon);
+
+    fft.inverse(&mut out_re, &mut out_im);
+    let expected = (&to_f64(re)[..], &to_f64(im)[..]);
+    check_close(&to_f64(&out_re), &to_f64(&out_im), expected, len, epsilon);
+
+    let (mut out_re, mut out_im) = (re.to_vec(), im.to_vec());
+    fft.inverse(&mut out_re, &mut out_im);
+    let (expected_re, expected_im) = dft(&to_f64(re), &to_f64(im), 1.0);
+    let scale = 1.0 / re.len() as f64;
+    let expected_re: Vec<f64> = expected_re.iter().map(|x| x * scale).collect();
+    let expected_im: Vec<f64> = expected_im.iter().map(|x| x * scale).collect();
+    let expected = (&expected_re[..], &expected_im[..]);
+    check_close(&to_f64(&out_re), &to_f64(&out_im), expected, len, epsilon);
+}
+
+fn check_real<T: FftFloat + Into<f64>>(fft: &Fft<T>, input: &[T], epsilon: f64) {
+    let len = input.len();
+    let half = len / 2 + 1;
+    let zero = T::from(0.0);
+    let (mut re, mut im) = (vec![zero; half], vec![zero; half]);
+    fft.forward_real(input, &mut re, &mut im);
+    let (expected_re, expected_im) = dft(&to_f64(input), &vec![0.0; input.len()], -1.0);
+    let expected = (&expected_re[..half], &expected_im[..half]);
+    check_close(&to_f64(&re), &to_f64(&im), expected, len, epsilon);
+
+    let mut output = vec![zero; input.len()];
+    fft.inverse_real(&mut re, &mut im, &mut output);
+    let zeros = vec![0.0; input.len()];
+    let expected = (&to_f64(input)[..], &zeros[..]);
+    check_close(&to_f64(&output), &zeros, expected, len, epsilon);
+}
+
+/// Zeroed buffers of the twiddle factors of a plan of length `len`.
+fn table<T: FftFloat>(len: usize) -> (Vec<T>, Vec<T>) {
+    (vec![T::from(0.0); len], vec![T::from(0.0); len])
+}
+
+fn lengths() -> impl Strategy<Value = usize> {
+    (0..=LEN.trailing_zeros()).prop_map(|bits| 1 << bits)
+}
+
+fn signals<T: Arbitrary + Clone>(
+    values: impl Strategy<Value = T> + Clone,
+) -> impl Strategy<Value = (Vec<T>, Vec<T>)> {
+    lengths().prop_flat_map(move |len| (vec(values.clone(), len), vec(values.clone(), len)))
+}
+
+#[test]
+fn test_known_spectra() {
+    let (mut twiddles_re, mut twiddles_im) = table::<f64>(16);
+    let fft = Fft::new(&mut twiddles_re, &mut twiddles_im);
+
+    // an impulse has a flat spectrum
+    let (mut re, mut im) = ([0.0; 16], [0.0; 16]);
+    re[0] = 1.0;
+    fft.forward(&mut re, &mut im);
+    assert_eq!((re, im), ([1.0; 16], [0.0; 16]));
+
+    // a complex exponential of frequency 3 is a single line
+    let (mut re, mut im): (Vec<f64>, Vec<f64>) = (0..16)
+        .map(|j| (2.0 * std::f64::consts::PI * 3.0 * j as f64 / 16.0).sin_cos())
+        .map(|(sin, cos)| (cos, sin))
+        .unzip();
+    fft.forward(&mut re, &mut im);
+    for k in 0..16 {
+        let expected = if k == 3 { 16.0 } else { 0.0 };
+        assert!(
+            (re[k] - expected).abs() < 1e-13 && im[k].abs() < 1e-13,
+            "{k}"
+        );
+    }
+
+    // a cosine of frequency 5 is two lines of half amplitude
+    let input: Vec<f64> = (0..16)
+        .map(|j| (2.0 * std::f64::consts::PI * 5.0 * j as f64 / 16.0).cos())
+        .collect();
+    let (mut re, mut im) = ([0.0; 9], [0.0; 9]);
+    fft.forward_real(&input, &mut re, &mut im);
+    for k in 0..9 {
+        let expected = if k == 5 { 8.0 } else { 0.0 };
+        assert!(
+            (re[k] - expected).abs() < 1e-13 && im[k].abs() < 1e-13,
+            "{k}"
+        );
+    }
+}
+
+#[test]
+fn test_shorter_plans() {
+    // the tables of shorter plans are the beginnings of the longer ones, so they give the same
+    // results
+    let (mut long_table, mut short_table) = (table::<f32>(64), table::<f32>(16));
+    let long = Fft::new(&mut long_table.0, &mut long_table.1);
+    let short = Fft::new(&mut short_table.0, &mut short_table.1);
+    let input: Vec<f32> = (0..16).map(|j| (j * j % 7) as f32 - 3.0).collect();
+    let reversed: Vec<f32> = input.iter().rev().copied().collect();
+    let (mut long_re, mut long_im) = (input, reversed);
+    let (mut short_re, mut short_im) = (long_re.clone(), long_im.clone());
+    long.forward(&mut long_re, &mut long_im);
+    short.forward(&mut short_re, &mut short_im);
+    assert_eq!((long_re, long_im), (short_re, short_im));
+}
+
+#[test]
+#[should_panic(expected = "the length of the plan isn't a power of two")]
+fn test_plan_length() {
+    let (mut re, mut im) = table::<f32>(12);
+    Fft::new(&mut re, &mut im);
+}
+
+#[test]
+#[should_panic(expected = "the real and the imaginary parts of the table differ in length")]
+fn test_table_length() {
+    let (mut re, mut im) = (table::<f32>(16).0, table::<f32>(8).1);
+    Fft::new(&mut re, &mut im);
+}
+
+#[test]
+#[should_panic(expected = "isn't a power of two up to the length of the plan")]
+fn test_transform_length() {
+    let (mut re, mut im) = ([0.0; 32], [0.0; 32]);
+    let (mut twiddles_re, mut twiddles_im) = table::<f64>(16);
+    Fft::new(&mut twiddles_re, &mut twiddles_im).forward(&mut re, &mut im);
+}
+
+#[test]
+#[should_panic(expected = "the real and the imaginary parts differ in length")]
+fn test_parts_length() {
+    let (mut re, mut im) = ([0.0; 8], [0.0; 4]);
+    let (mut twiddles_re, mut twiddles_im) = table::<f64>(16);
+    Fft::new(&mut twiddles_re, &mut twiddles_im).inverse(&mut re, &mut im);
+}
+
+#[test]
+#[should_panic(expected = "the length of the spectrum isn't half the length of the transform")]
+fn test_spectrum_length() {
+    let (mut re, mut im) = ([0.0; 8], [0.0; 8]);
+    let (mut twiddles_re, mut twiddles_im) = table::<f32>(16);
+    Fft::new(&mut twiddles_re, &mut twiddles_im).forward_real(&[0.0; 8], &mut re, &mut im);
+}
+
+proptest! {
+    #[test]
+    fn random_f32((re, im) in signals(-1.0f32..1.0)) {
+        let (mut twiddles_re, mut twiddles_im) = table(LEN);
+        check_complex(&Fft::new(&mut twiddles_re, &mut twiddles_im), &re, &im, f32::EPSILON as f64);
+    }
+
+    #[test]
+    fn random_f64((re, im) in signals(-1.0f64..1.0)) {
+        let (mut twiddles_re, mut twiddles_im) = table(LEN);
+        check_complex(&Fft::new(&mut twiddles_re, &mut twiddles_im), &re, &im, f64::EPSILON);
+    }
+
+    #[test]
+    fn random_real_f32((input, _) in signals(-1.0f32..1.0).prop_filter("too short", |s| s.0.len() >= 2)) {
+        let (mut twiddles_re, mut twiddles_im) = table(LEN);
+        check_real(&Fft::new(&mut twiddles_re, &mut twiddles_im), &input, f32::EPSILON as f64);
+    }
+
+    #[test]
+    fn random_real_f64((input, _) in signals(-1.0f64..1.0).prop_filter("too short", |s| s.0.len() >= 2)) {
+        let (mut twiddles_re, mut twiddles_im) = table(LEN);
+        check_real(&Fft::new(&mut twiddles_re, &mut twiddles_im), &input, f64::EPSILON);
+    }
+}
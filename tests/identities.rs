@@ -0,0 +1,151 @@
+//! Property-based tests of mathematical identities on random vectors of several lane counts.
+//!
+//! Every identity is checked lane by lane, so the failure message names the lane and its input,
+//! and the shrunk counterexample is a vector in which only the failing lane matters.
+#![cfg_attr(not(simd_shim), feature(portable_simd))]
+
+use proptest::prelude::*;
+use simd_addons::{
+    math::{Exponent, Trigonometry},
+    simd::Simd,
+};
+
+/// Random vector of `N` lanes from `range`.
+fn vector<const N: usize>(range: std::ops::Range<f64>) -> impl Strategy<Value = [f64; N]> {
+    proptest::array::uniform(range)
+}
+
+macro_rules! test_identities {
+    ($ftype: ident, $( $lanes: literal ),+) => {
+        $(
+        paste::paste! {
+        mod [< identities_ $ftype _ $lanes >] {
+            use super::*;
+
+            const EPS: f64 = $ftype::EPSILON as f64;
+
+            fn simd(x: [f64; $lanes]) -> Simd<$ftype, $lanes> {
+                Simd::from_array(x.map(|x| x as $ftype))
+            }
+
+            proptest! {
+                /// `sin²(x) + cos²(x) = 1` up to `4ε`: each square is within 2 ULPs of the exact
+                /// value and the sum rounds once more.
+                #[test]
+                fn sin_cos_pythagorean(x in vector::<$lanes>(-1e4..1e4)) {
+                    let x = simd(x);
+                    let (sin, cos) = (Trigonometry::sin(x), Trigonometry::cos(x));
+                    for i in 0..$lanes {
+                        let sum = (sin[i] * sin[i] + cos[i] * cos[i]) as f64;
+                        prop_assert!((sum - 1.0).abs() <= 4.0 * EPS, "lane {}: x = {:e}, sum = {}", i, x[i], sum);
+                    }
+                }
+
+                /// `sin(-x) = -sin(x)` exactly, including the sign of zero.
+                #[test]
+                fn sin_odd(x in vector::<$lanes>(-1e4..1e4)) {
+                    let x = simd(x);
+                    let (sin, sin_neg) = (Trigonometry::sin(x), Trigonometry::sin(-x));
+                    for i in 0..$lanes {
+                        prop_assert_eq!(sin_neg[i].to_bits(), (-sin[i]).to_bits(), "lane {}: x = {:e}", i, x[i]);
+                    }
+                }
+
+                /// `exp(a + b) = exp(a) exp(b)` up to the relative error `(|a + b| / 2 + 6)ε`:
+                /// rounding of `a + b` changes the exponent by up to `|a + b| ε / 2`, and the three
+                /// `exp`s and the product add up to 6 ULPs.
+                #[test]
+                fn exp_sum(a in vector::<$lanes>(-40.0..40.0), b in vector::<$lanes>(-40.0..40.0)) {
+                    let (a, b) = (simd(a), simd(b));
+                    let (exp_sum, exp_a, exp_b) = (Exponent::exp(a + b), Exponent::exp(a), Exponent::exp(b));
+                    for i in 0..$lanes {
+                        let product = (exp_a[i] * exp_b[i]) as f64;
+                        let tolerance = ((a[i] + b[i]).abs() as f64 / 2.0 + 6.0) * EPS;
+                        prop_assert!(
+                            (exp_sum[i] as f64 / product - 1.0).abs() <= tolerance,
+                            "lane {}: a = {:e}, b = {:e}", i, a[i], b[i]
+                        );
+                    }
+                }
+
+                /// `atan2(y, x)` lies in the quadrant of `(x, y)`: its sign is the sign of `y`, and
+                /// it is at most `π/2` in magnitude iff `x` is positive. The bound `π/2` is relaxed
+                /// by `2ε`, the error of the rounded constant and the function.
+                #[test]
+                fn atan2_quadrant(x in vector::<$lanes>(-1e3..1e3), y in vector::<$lanes>(-1e3..1e3)) {
+                    let (x, y) = (simd(x), simd(y));
+                    let atan2 = y.atan2(x);
+                    let half_pi = std::f64::consts::FRAC_PI_2;
+                    for i in 0..$lanes {
+                        let angle = atan2[i] as f64;
+                        prop_assert_eq!(
+                            angle.is_sign_negative(),
+                            y[i].is_sign_negative(),
+                            "lane {}: x = {:e}, y = {:e}, atan2 = {}", i, x[i], y[i], angle
+                        );
+                        if x[i] > 0.0 {
+                            prop_assert!(angle.abs() <= half_pi * (1.0 + 2.0 * EPS), "lane {}: x = {:e}, y = {:e}, atan2 = {}", i, x[i], y[i], angle);
+                        } else if x[i] < 0.0 {
+                            prop_assert!(angle.abs() >= half_pi * (1.0 - 2.0 * EPS), "lane {}: x = {:e}, y = {:e}, atan2 = {}", i, x[i], y[i], angle);
+                        }
+                    }
+                }
+
+                /// `asin(sin(x)) = x` on `[-π/2, π/2]` up to `4ε(|x| + 1 / cos(x))`: an error of 2
+                /// ULPs in `sin(x)` is amplified by `asin' = 1 / cos(x)`, `asin` adds 2 ULPs of `x`.
+                #[test]
+                fn asin_sin(x in vector::<$lanes>(-std::f64::consts::FRAC_PI_2..std::f64::consts::FRAC_PI_2)) {
+                    let x = simd(x);
+                    let asin_sin = Trigonometry::sin(x).asin();
+                    for i in 0..$lanes {
+                        let x = x[i] as f64;
+                        let tolerance = 4.0 * EPS * (x.abs() + 1.0 / x.cos());
+                        prop_assert!((asin_sin[i] as f64 - x).abs() <= tolerance, "lane {}: x = {:e}", i, x);
+                    }
+                }
+
+                /// `exp` and `atan` are non-decreasing: exactly, on sorted lanes and on adjacent
+                /// floats.
+                #[test]
+                fn monotonic(x in vector::<$lanes>(-80.0..80.0), y in vector::<$lanes>(-1e3..1e3)) {
+                    let mut x = simd(x).to_array();
+                    x.sort_by($ftype::total_cmp);
+                    let x = Simd::from_array(x);
+                    let mut y = simd(y).to_array();
+                    y.sort_by($ftype::total_cmp);
+                    let y = Simd::from_array(y);
+
+                    let (exp, atan) = (Exponent::exp(x), y.atan());
+                    for (i, pair) in exp.as_array().windows(2).enumerate() {
+                        prop_assert!(pair[0] <= pair[1], "lanes {}, {}: x = {:e}, {:e}", i, i + 1, x[i], x[i + 1]);
+                    }
+                    for (i, pair) in atan.as_array().windows(2).enumerate() {
+                        prop_assert!(pair[0] <= pair[1], "lanes {}, {}: x = {:e}, {:e}", i, i + 1, y[i], y[i + 1]);
+                    }
+
+                    let next = |x: Simd<$ftype, $lanes>| {
+                        Simd::from_array(x.to_array().map(|x| {
+                            if x == 0.0 {
+                                $ftype::from_bits(1)
+                            } else if x > 0.0 {
+                                $ftype::from_bits(x.to_bits() + 1)
+                            } else {
+                                $ftype::from_bits(x.to_bits() - 1)
+                            }
+                        }))
+                    };
+                    let (exp_next, atan_next) = (Exponent::exp(next(x)), next(y).atan());
+                    for i in 0..$lanes {
+                        prop_assert!(exp[i] <= exp_next[i], "lane {}: x = {:e}", i, x[i]);
+                        prop_assert!(atan[i] <= atan_next[i], "lane {}: x = {:e}", i, y[i]);
+                    }
+                }
+            }
+        }
+        }
+        )+
+    };
+}
+
+test_identities!(f32, 1, 4, 16, 64);
+test_identities!(f64, 1, 2, 8, 64);
@@ -0,0 +1,460 @@
+//! Tests of `linalg` against scalar computations done lane by lane in `f64`. Matrices are random
+//! and diagonally dominant, so they are well-conditioned and their inverses are accurate.
+#![cfg_attr(not(simd_shim), feature(portable_simd))]
+
+use std::f32::consts::FRAC_1_SQRT_2;
+
+use proptest::prelude::*;
+use simd_addons::{
+    linalg::{Mat2, Mat3, Mat4, Quat, Vec2, Vec3, Vec4},
+    simd::Simd,
+};
+
+const LANES: usize = 4;
+
+type Lanes = Simd<f64, LANES>;
+
+/// Scalar matrices, lane `l` of each element is the element of the `l`-th matrix.
+type Scalar<const D: usize> = [[[f64; D]; D]; LANES];
+
+fn lanes<const D: usize>(matrices: &Scalar<D>) -> [[Lanes; D]; D] {
+    core::array::from_fn(|i| core::array::from_fn(|j| Simd::from_array(matrices.map(|m| m[i][j]))))
+}
+
+fn lane<const D: usize>(rows: [[Lanes; D]; D], l: usize) -> [[f64; D]; D] {
+    rows.map(|row| row.map(|x| x[l]))
+}
+
+fn multiply<const D: usize>(a: &[[f64; D]; D], b: &[[f64; D]; D]) -> [[f64; D]; D] {
+    core::array::from_fn(|i| core::array::from_fn(|j| (0..D).map(|k| a[i][k] * b[k][j]).sum()))
+}
+
+fn identity<const D: usize>() -> [[f64; D]; D] {
+    core::array::from_fn(|i| core::array::from_fn(|j| if i == j { 1.0 } else { 0.0 }))
+}
+
+/// Determinant by the Laplace expansion along the first row.
+fn determinant(m: &[Vec<f64>]) -> f64 {
+    if m.len() == 1 {
+        return m[0][0];
+    }
+    (0..m.len())
+        .map(|j| {
+            let minor: Vec<Vec<f64>> = m[1..]
+                .iter()
+                .map(|row| [&row[..j], &row[j + 1..]].concat())
+                .collect();
+            let sign = if j % 2 == 0 { 1.0 } else { -1.0 };
+            sign * m[0][j] * determinant(&minor)
+        })
+        .sum()
+}
+
+fn assert_close<const D: usize>(result: [[f64; D]; D], expected: [[f64; D]; D], tolerance: f64) {
+    for (result, expected) in result.iter().flatten().zip(expected.iter().flatten()) {
+        assert!(
+            (result - expected).abs() <= tolerance * expected.abs().max(1.0),
+            "{:?} instead of {:?}",
+            result,
+            expected
+        );
+    }
+}
+
+macro_rules! check_matrices {
+    ($matrix: ident, $vector: ident, $dim: literal, $a: expr, $b: expr, $v: expr) => {{
+        let (a, b, v): (Scalar<$dim>, Scalar<$dim>, [[f64; $dim]; LANES]) = ($a, $b, $v);
+        let ma = $matrix::from_rows(lanes(&a));
+        let mb = $matrix::from_rows(lanes(&b));
+        let vector =
+            $vector::from_array(core::array::from_fn(|i| Simd::from_array(v.map(|v| v[i]))));
+
+        let product = (ma * mb).to_rows();
+        let transposed = ma.transpose().to_rows();
+        let applied = (ma * vector).to_array();
+        let det = ma.determinant();
+        let inverse = ma.inverse().to_rows();
+        for l in 0..LANES {
+            assert_close(lane(product, l), multiply(&a[l], &b[l]), 1e-14);
+            assert_eq!(
+                lane(transposed, l),
+                core::array::from_fn(|i| core::array::from_fn(|j| a[l][j][i]))
+            );
+            for i in 0..$dim {
+                let expected: f64 = (0..$dim).map(|j| a[l][i][j] * v[l][j]).sum();
+                assert!((applied[i][l] - expected).abs() <= 1e-14 * expected.abs().max(1.0));
+            }
+            let rows: Vec<Vec<f64>> = a[l].iter().map(|row| row.to_vec()).collect();
+            let expected = determinant(&rows);
+            assert!(
+                (det[l] - expected).abs() <= 1e-13 * expected.abs(),
+                "{} instead of {}",
+                det[l],
+                expected
+            );
+            assert_close(multiply(&a[l], &lane(inverse, l)), identity(), 1e-13);
+        }
+        assert_eq!($matrix::from_rows(lanes(&a)) * $matrix::identity(), ma);
+    }};
+}
+
+/// Random diagonally dominant matrices.
+fn matrices<const D: usize>() -> impl Strategy<Value = Scalar<D>> {
+    prop::array::uniform4(prop::array::uniform(prop::array::uniform(-1.0..1.0))).prop_map(
+        |mut matrices: Scalar<D>| {
+            for m in &mut matrices {
+                for (i, row) in m.iter_mut().enumerate() {
+                    row[i] += if row[i] < 0.0 { -(D as f64) } else { D as f64 };
+                }
+            }
+            matrices
+        },
+    )
+}
+
+fn vectors<const D: usize>() -> impl Strategy<Value = [[f64; D]; LANES]> {
+    prop::array::uniform4(prop::array::uniform(-10.0..10.0))
+}
+
+#[test]
+fn test_vectors() {
+    let a = Vec3::new(Lanes::splat(1.0), Lanes::splat(2.0), Lanes::splat(2.0));
+    let b = Vec3::new(Lanes::splat(0.0), Lanes::splat(3.0), Lanes::splat(-4.0));
+    assert_eq!(a.dot(b), Lanes::splat(-2.0));
+    assert_eq!(a.length(), Lanes::splat(3.0));
+    assert_eq!((a + b).to_array(), [1.0, 5.0, -2.0].map(Lanes::splat));
+    assert_eq!((a - b).to_array(), [1.0, -1.0, 6.0].map(Lanes::splat));
+    assert_eq!(
+        (-a * Lanes::splat(2.0)).to_array(),
+        [-2.0, -4.0, -4.0].map(Lanes::splat)
+    );
+    assert_eq!(
+        (b / Lanes::splat(2.0)).to_array(),
+        [0.0, 1.5, -2.0].map(Lanes::splat)
+    );
+    let cross = a.cross(b);
+    assert_eq!(cross.to_array(), [-14.0, 4.0, 3.0].map(Lanes::splat));
+    assert_eq!(cross.dot(a), Lanes::splat(0.0));
+    assert_eq!(cross.dot(b), Lanes::splat(0.0));
+
+    let unit = a.normalize().to_array();
+    for (x, expected) in unit.iter().zip([1.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0]) {
+        assert!((x[0] - expected).abs() < 1e-15);
+    }
+    assert_eq!(Vec2::<Lanes>::ZERO.normalize(), Vec2::ZERO);
+
+    // the squared lengths of these overflow, underflow or are subnormal
+    for x in [3e19, 1e-21, 1e-40, f32::MAX, -f32::MIN_POSITIVE / 4.0] {
+        let (x, zero) = (Simd::<f32, 4>::splat(x), Simd::splat(0.0));
+        let sign = x[0].signum();
+        for (v, expected) in [
+            (Vec3::new(x, zero, zero), [sign, 0.0, 0.0]),
+            (
+                Vec3::new(x, zero, x),
+                [sign * FRAC_1_SQRT_2, 0.0, sign * FRAC_1_SQRT_2],
+            ),
+        ] {
+            let unit = v.normalize().to_array().map(|c| c[0]);
+            for (c, expected) in unit.into_iter().zip(expected) {
+                assert!(
+                    (c - expected).abs() < 1e-7,
+                    "{:?} normalized to {:?}",
+                    v,
+                    unit
+                );
+            }
+        }
+    }
+    let v = Vec4::from_array([3.0, 4.0, 12.0, 84.0].map(Simd::<f32, 8>::splat));
+    assert_eq!(v.length(), Simd::splat(85.0));
+}
+
+#[test]
+fn test_rotations() {
+    let angles = [0.0, 0.5, -1.0, 3.0];
+    let (sin, cos) = (angles.map(f64::sin), angles.map(f64::cos));
+    let plane = Mat2::from_angle(Lanes::from_array(angles)).to_rows();
+    for l in 0..LANES {
+        assert_close(lane(plane, l), [[cos[l], -sin[l]], [sin[l], cos[l]]], 1e-15);
+    }
+
+    let axes: [[f64; 3]; LANES] = [
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 0.0],
+        [0.6, 0.0, 0.8],
+        [2.0, -1.0, 2.0],
+    ];
+    let axis = Vec3::from_array(core::array::from_fn(|i| {
+        Lanes::from_array(axes.map(|a| a[i]))
+    }))
+    .normalize();
+    let rotation = Mat3::from_axis_angle(axis, Lanes::from_array(angles));
+    let rows = rotation.to_rows();
+    let transposed = rotation.transpose().to_rows();
+    let det = rotation.determinant();
+    let rotated_axis = (rotation * axis).to_array();
+    let axis = axis.to_array();
+    for l in 0..LANES {
+        // orthogonal with determinant 1, and the axis is fixed
+        assert_close(
+            multiply(&lane(rows, l), &lane(transposed, l)),
+            identity(),
+            1e-15,
+        );
+        assert!((det[l] - 1.0).abs() < 1e-15);
+        for i in 0..3 {
+            assert!((rotated_axis[i][l] - axis[i][l]).abs() < 1e-15);
+        }
+    }
+    // around the z axis (of lane 0) it is the rotation of the plane
+    let m = lane(rows, 0);
+    assert_close(
+        [[m[0][0], m[0][1]], [m[1][0], m[1][1]]],
+        lane(plane, 0),
+        1e-15,
+    );
+    assert_eq!(
+        [m[0][2], m[1][2], m[2][0], m[2][1], m[2][2]],
+        [0.0, 0.0, 0.0, 0.0, 1.0]
+    );
+
+    // a third of a turn around the diagonal permutes the axes
+    let diagonal = Vec3::from_array([Simd::<f32, 4>::splat(1.0); 3]).normalize();
+    let third = Mat3::from_axis_angle(diagonal, Simd::splat(2.0 * std::f32::consts::PI / 3.0));
+    let x = Vec3::new(Simd::splat(1.0), Simd::splat(0.0), Simd::splat(0.0));
+    let y = (third * x).to_array();
+    for (y, expected) in y.iter().zip([0.0, 1.0, 0.0]) {
+        assert!((y[0] - expected).abs() < 1e-6);
+    }
+}
+
+/// Scalar quaternions `[x, y, z, w]`.
+type Q = [f64; 4];
+
+fn q_mul([x1, y1, z1, w1]: Q, [x2, y2, z2, w2]: Q) -> Q {
+    [
+        w1 * x2 + x1 * w2 + y1 * z2 - z1 * y2,
+        w1 * y2 - x1 * z2 + y1 * w2 + z1 * x2,
+        w1 * z2 + x1 * y2 - y1 * x2 + z1 * w2,
+        w1 * w2 - x1 * x2 - y1 * y2 - z1 * z2,
+    ]
+}
+
+fn q_from_axis_angle(axis: [f64; 3], angle: f64) -> Q {
+    let length = axis.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let [x, y, z] = axis.map(|x| x / length * (angle / 2.0).sin());
+    [x, y, z, (angle / 2.0).cos()]
+}
+
+/// Rotation of `v` by `q v q*`.
+fn q_rotate(q: Q, [x, y, z]: [f64; 3]) -> [f64; 3] {
+    let conjugate = [-q[0], -q[1], -q[2], q[3]];
+    let [x, y, z, _] = q_mul(q_mul(q, [x, y, z, 0.0]), conjugate);
+    [x, y, z]
+}
+
+fn q_dot(a: Q, b: Q) -> f64 {
+    a.iter().zip(&b).map(|(a, b)| a * b).sum()
+}
+
+fn q_normalize(q: Q) -> Q {
+    q.map(|x| x / q_dot(q, q).sqrt())
+}
+
+fn q_nlerp(a: Q, b: Q, t: f64) -> Q {
+    let b = if q_dot(a, b) < 0.0 { b.map(|x| -x) } else { b };
+    q_normalize(core::array::from_fn(|i| a[i] * (1.0 - t) + b[i] * t))
+}
+
+fn q_slerp(a: Q, b: Q, t: f64) -> Q {
+    let (b, cos) = match q_dot(a, b) {
+        cos if cos < 0.0 => (b.map(|x| -x), -cos),
+        cos => (b, cos),
+    };
+    if cos >= 1.0 {
+        return q_nlerp(a, b, t);
+    }
+    let angle = cos.acos();
+    let (wa, wb) = (((1.0 - t) * angle).sin(), (t * angle).sin());
+    core::array::from_fn(|i| (a[i] * wa + b[i] * wb) / angle.sin())
+}
+
+/// Random unit quaternions.
+fn quats() -> impl Strategy<Value = [Q; LANES]> {
+    prop::array::uniform4(
+        (
+            prop::array::uniform3(0.1..1.0),
+            prop::array::uniform3(any::<bool>()),
+            -7.0..7.0,
+        )
+            .prop_map(|(axis, signs, angle): ([f64; 3], [bool; 3], f64)| {
+                let axis = core::array::from_fn(|i| if signs[i] { -axis[i] } else { axis[i] });
+                q_from_axis_angle(axis, angle)
+            }),
+    )
+}
+
+macro_rules! check_quats {
+    ($ftype: ident, $tolerance: expr, $a: expr, $b: expr, $v: expr, $t: expr) => {{
+        let (a, b, v, t): ([Q; LANES], [Q; LANES], [[f64; 3]; LANES], [f64; LANES]) =
+            ($a, $b, $v, $t);
+        let tolerance: f64 = $tolerance;
+        let quat = |q: [Q; LANES]| {
+            Quat::from_array(core::array::from_fn(|i| {
+                Simd::from_array(q.map(|q| q[i] as $ftype))
+            }))
+        };
+        let lane =
+            |q: Quat<Simd<$ftype, LANES>>, l: usize| -> Q { q.to_array().map(|x| x[l] as f64) };
+        let assert_close = |result: &[f64], expected: &[f64], what: &str| {
+            for (x, y) in result.iter().zip(expected) {
+                assert!(
+                    (x - y).abs() <= tolerance,
+                    "{what}: {result:?} instead of {expected:?}"
+                );
+            }
+        };
+        let (qa, qb) = (quat(a), quat(b));
+        let vector = Vec3::from_array(core::array::from_fn(|i| {
+            Simd::from_array(v.map(|v| v[i] as $ftype))
+        }));
+        let t_lanes = Simd::from_array(t.map(|t| t as $ftype));
+
+        let product = qa * qb;
+        let rotated = (qa * vector).to_array();
+        let by_matrix = (qa.to_mat3() * vector).to_array();
+        let by_mat4 =
+            (qa.to_mat4() * Vec4::new(vector.x, vector.y, vector.z, Simd::splat(1.0))).to_array();
+        let from_mat3 = Quat::from_mat3(qa.to_mat3());
+        let from_mat4 = Quat::from_mat4(qa.to_mat4());
+        let identity = qa * qa.inverse();
+        let nlerp = qa.nlerp(qb, t_lanes);
+        let slerp = qa.slerp(qb, t_lanes);
+        let (start, end) = (
+            qa.slerp(qb, Simd::splat(0.0)),
+            qa.slerp(qb, Simd::splat(1.0)),
+        );
+        for l in 0..LANES {
+            assert_close(&lane(product, l), &q_mul(a[l], b[l]), "product");
+            // the vectors are scaled to the unit range of the tolerance
+            let expected = q_rotate(a[l], v[l]).map(|x| x / 10.0);
+            let lanes = |v: &[Simd<$ftype, LANES>]| -> Vec<f64> {
+                v.iter().map(|x| x[l] as f64 / 10.0).collect()
+            };
+            assert_close(&lanes(&rotated), &expected, "rotation");
+            assert_close(&lanes(&by_matrix), &expected, "matrix");
+            assert_close(&lanes(&by_mat4[..3]), &expected, "mat4");
+            assert_eq!(by_mat4[3][l], 1.0);
+            // the quaternions of the same rotation differ by the sign
+            let sign = if a[l][3] < 0.0 { -1.0 } else { 1.0 };
+            assert_close(&lane(from_mat3, l), &a[l].map(|x| x * sign), "from_mat3");
+            assert_close(&lane(from_mat4, l), &a[l].map(|x| x * sign), "from_mat4");
+            assert_close(&lane(identity, l), &[0.0, 0.0, 0.0, 1.0], "inverse");
+            assert_close(&lane(nlerp, l), &q_nlerp(a[l], b[l], t[l]), "nlerp");
+            assert_close(&lane(slerp, l), &q_slerp(a[l], b[l], t[l]), "slerp");
+            assert_close(&lane(start, l), &a[l], "slerp at 0");
+            assert_close(&lane(end, l), &q_slerp(a[l], b[l], 1.0), "slerp at 1");
+        }
+    }};
+}
+
+#[test]
+fn test_quat_rotations() {
+    let axes: [[f64; 3]; LANES] = [
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 0.0],
+        [0.6, 0.0, 0.8],
+        [2.0, -1.0, 2.0],
+    ];
+    let angles = [0.0, 0.5, -1.0, 3.0];
+    let axis = Vec3::from_array(core::array::from_fn(|i| {
+        Lanes::from_array(axes.map(|a| a[i]))
+    }))
+    .normalize();
+    let angle_lanes = Lanes::from_array(angles);
+    let quat = Quat::from_axis_angle(axis, angle_lanes);
+    let matrix = Mat3::from_axis_angle(axis, angle_lanes).to_rows();
+    let by_quat = quat.to_mat3().to_rows();
+    for l in 0..LANES {
+        assert_close(lane(by_quat, l), lane(matrix, l), 1e-15);
+        let expected = q_from_axis_angle(axes[l], angles[l]);
+        for (i, x) in quat.to_array().iter().enumerate() {
+            assert!((x[l] - expected[i]).abs() < 1e-15);
+        }
+    }
+    assert_eq!(Quat::IDENTITY * quat, quat);
+    assert_eq!(quat.conjugate() * Quat::<Lanes>::IDENTITY, quat.conjugate());
+
+    // nearly and exactly parallel quaternions
+    for angle in [1e-3, 1e-7, 0.0] {
+        let b = q_from_axis_angle(axes[3], angles[3] + angle);
+        let b_lanes = Quat::from_array(b.map(Lanes::splat));
+        let single = Quat::from_array(quat.to_array().map(|x| Lanes::splat(x[3])));
+        for t in [0.0, 0.3, 1.0] {
+            let slerp = single.slerp(b_lanes, Lanes::splat(t)).to_array();
+            let expected = q_slerp(single.to_array().map(|x| x[0]), b, t);
+            for (x, y) in slerp.iter().zip(expected) {
+                assert!((x[0] - y).abs() < 1e-15, "{} instead of {}", x[0], y);
+            }
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn random_mat2(a in matrices::<2>(), b in matrices::<2>(), v in vectors::<2>()) {
+        check_matrices!(Mat2, Vec2, 2, a, b, v);
+    }
+
+    #[test]
+    fn random_mat3(a in matrices::<3>(), b in matrices::<3>(), v in vectors::<3>()) {
+        check_matrices!(Mat3, Vec3, 3, a, b, v);
+    }
+
+    #[test]
+    fn random_mat4(a in matrices::<4>(), b in matrices::<4>(), v in vectors::<4>()) {
+        check_matrices!(Mat4, Vec4, 4, a, b, v);
+    }
+
+    #[test]
+    fn random_quat_f32(
+        a in quats(),
+        b in quats(),
+        v in vectors::<3>(),
+        t in prop::array::uniform4(0.0..1.0),
+    ) {
+        check_quats!(f32, 1e-5, a, b, v, t);
+    }
+
+    #[test]
+    fn random_quat_f64(
+        a in quats(),
+        b in quats(),
+        v in vectors::<3>(),
+        t in prop::array::uniform4(0.0..1.0),
+    ) {
+        check_quats!(f64, 1e-13, a, b, v, t);
+    }
+
+    #[test]
+    fn random_cross(a in vectors::<3>(), b in vectors::<3>()) {
+        let to_lanes = |v: [[f64; 3]; LANES]| {
+            Vec3::from_array(core::array::from_fn(|i| Lanes::from_array(v.map(|v| v[i]))))
+        };
+        let cross = to_lanes(a).cross(to_lanes(b));
+        let normal = cross.normalize();
+        for (l, (a, b)) in a.iter().zip(&b).enumerate() {
+            let expected = [
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+            ];
+            let length = expected.iter().map(|x| x * x).sum::<f64>().sqrt();
+            for (i, component) in cross.to_array().iter().enumerate() {
+                prop_assert_eq!(component[l], expected[i]);
+                let unit = normal.to_array()[i][l];
+                prop_assert!((unit - expected[i] / length).abs() < 1e-15);
+            }
+        }
+    }
+}
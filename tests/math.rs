@@ -1,5 +1,6 @@
-#![feature(portable_simd)]
+#![cfg_attr(not(simd_shim), feature(portable_simd))]
 
+#[allow(dead_code)]
 mod common;
 
 use common::Linspace;
@@ -38,10 +39,59 @@ fn test_simd_atan2_f32() {
     const VALUES: [f32; 6] = [0.0, -0.0, 1.0, -1.0, f32::INFINITY, -f32::INFINITY];
     for y in VALUES {
         for x in VALUES {
-            assert_eq!(y.atan2(x), simd_fn!(y.atan2(x)), "atan2({}, {})", x, y);
+            assert_eq!(
+                y.atan2(x).to_bits(),
+                simd_fn!(y.atan2(x)).to_bits(),
+                "atan2({}, {})",
+                y,
+                x
+            );
         }
     }
 
+    // NaNs, subnormals and values near the overflow of the reduction
+    const EXTREMES: [f32; 8] = [
+        f32::NAN,
+        f32::MAX,
+        -f32::MAX,
+        f32::MAX / 2.0,
+        f32::MIN_POSITIVE,
+        1e-45,
+        -1e-45,
+        f32::INFINITY,
+    ];
+    for y in EXTREMES.into_iter().chain(VALUES) {
+        for x in EXTREMES.into_iter().chain(VALUES) {
+            let (expected, actual) = (y.atan2(x), simd_fn!(y.atan2(x)));
+            assert_eq!(expected.is_nan(), actual.is_nan(), "atan2({}, {})", y, x);
+            if !expected.is_nan() {
+                approx::assert_ulps_eq!(expected, actual);
+                assert_eq!(
+                    expected.is_sign_negative(),
+                    actual.is_sign_negative(),
+                    "atan2({}, {})",
+                    y,
+                    x
+                );
+            }
+        }
+    }
+
+    // lanes near `MAX` are reduced separately, which doesn't change the other lanes
+    let y = simd_addons::simd::Simd::from_array([f32::MAX, 1e-45, 1.0, f32::INFINITY]);
+    let x = simd_addons::simd::Simd::from_array([f32::MAX / 2.0, 3.0 * 1e-45, -2.0, f32::INFINITY]);
+    let result = y.atan2(x);
+    for (i, (y, x)) in y.to_array().into_iter().zip(x.to_array()).enumerate() {
+        let expected = simd_fn!(y.atan2(x));
+        assert_eq!(
+            result[i].to_bits(),
+            expected.to_bits(),
+            "atan2({}, {})",
+            y,
+            x
+        );
+    }
+
     for x in (-10.0..10.0f32).linspace(1_000) {
         for y in (-10.0..10.0f32).linspace(1_000) {
             approx::assert_ulps_eq!(y.atan2(x), simd_fn!(y.atan2(x)));
@@ -54,13 +104,450 @@ fn test_simd_atan2_f64() {
     const VALUES: [f64; 6] = [0.0, -0.0, 1.0, -1.0, f64::INFINITY, -f64::INFINITY];
     for y in VALUES {
         for x in VALUES {
-            assert_eq!(y.atan2(x), simd_fn!(y.atan2(x)), "atan2({}, {})", x, y);
+            assert_eq!(
+                y.atan2(x).to_bits(),
+                simd_fn!(y.atan2(x)).to_bits(),
+                "atan2({}, {})",
+                y,
+                x
+            );
         }
     }
 
+    // NaNs, subnormals and values near the overflow of the reduction
+    const EXTREMES: [f64; 8] = [
+        f64::NAN,
+        f64::MAX,
+        -f64::MAX,
+        f64::MAX / 2.0,
+        f64::MIN_POSITIVE,
+        5e-324,
+        -5e-324,
+        f64::INFINITY,
+    ];
+    for y in EXTREMES.into_iter().chain(VALUES) {
+        for x in EXTREMES.into_iter().chain(VALUES) {
+            let (expected, actual) = (y.atan2(x), simd_fn!(y.atan2(x)));
+            assert_eq!(expected.is_nan(), actual.is_nan(), "atan2({}, {})", y, x);
+            if !expected.is_nan() {
+                approx::assert_ulps_eq!(expected, actual);
+                assert_eq!(
+                    expected.is_sign_negative(),
+                    actual.is_sign_negative(),
+                    "atan2({}, {})",
+                    y,
+                    x
+                );
+            }
+        }
+    }
+
+    // lanes near `MAX` are reduced separately, which doesn't change the other lanes
+    let y = simd_addons::simd::Simd::from_array([f64::MAX, 5e-324, 1.0, f64::INFINITY]);
+    let x =
+        simd_addons::simd::Simd::from_array([f64::MAX / 2.0, 3.0 * 5e-324, -2.0, f64::INFINITY]);
+    let result = y.atan2(x);
+    for (i, (y, x)) in y.to_array().into_iter().zip(x.to_array()).enumerate() {
+        let expected = simd_fn!(y.atan2(x));
+        assert_eq!(
+            result[i].to_bits(),
+            expected.to_bits(),
+            "atan2({}, {})",
+            y,
+            x
+        );
+    }
+
     for x in (-10.0..10.0f64).linspace(1_000) {
         for y in (-10.0..10.0f64).linspace(1_000) {
             approx::assert_ulps_eq!(y.atan2(x), simd_fn!(y.atan2(x)));
         }
     }
 }
+
+macro_rules! test_scalar_matches_simd {
+    ($ftype: ty, $values: expr) => {
+        paste::paste! {
+        #[test]
+        fn [< test_scalar_matches_simd_ $ftype >]() {
+            for x in $values {
+                assert_eq!(Exponent::exp(x).to_bits(), simd_fn!(x.exp()).to_bits(), "exp({})", x);
+                assert_eq!(Exponent::exp2(x).to_bits(), simd_fn!(x.exp2()).to_bits(), "exp2({})", x);
+                assert_eq!(Exponent::exp_m1(x).to_bits(), simd_fn!(x.exp_m1()).to_bits(), "exp_m1({})", x);
+                assert_eq!(Trigonometry::sin(x).to_bits(), simd_fn!(x.sin()).to_bits(), "sin({})", x);
+                assert_eq!(Trigonometry::cos(x).to_bits(), simd_fn!(x.cos()).to_bits(), "cos({})", x);
+                assert_eq!(Trigonometry::tan(x).to_bits(), simd_fn!(x.tan()).to_bits(), "tan({})", x);
+                assert_eq!(Trigonometry::asin(x).to_bits(), simd_fn!(x.asin()).to_bits(), "asin({})", x);
+                assert_eq!(Trigonometry::acos(x).to_bits(), simd_fn!(x.acos()).to_bits(), "acos({})", x);
+                assert_eq!(Trigonometry::atan(x).to_bits(), simd_fn!(x.atan()).to_bits(), "atan({})", x);
+                assert_eq!(
+                    Trigonometry::atan2(x, 1.5).to_bits(),
+                    simd_fn!(x.atan2(1.5)).to_bits(),
+                    "atan2({}, 1.5)",
+                    x
+                );
+            }
+        }
+        }
+    };
+}
+
+test_scalar_matches_simd!(
+    f32,
+    (-100.0..100.0f32)
+        .linspace(10_000)
+        .chain([f32::NAN, f32::INFINITY])
+);
+test_scalar_matches_simd!(
+    f64,
+    (-100.0..100.0f64)
+        .linspace(10_000)
+        .chain([f64::NAN, f64::INFINITY])
+);
+
+#[test]
+fn test_sign_bit() {
+    use simd_addons::{
+        math::util::FloatBitUtils,
+        simd::{num::SimdFloat, Simd},
+    };
+
+    // `SimdFloat::Bits` isn't ambiguous for vectors, which implement both traits
+    fn to_bits<T: SimdFloat + FloatBitUtils>(x: T) -> T::Bits {
+        x.to_bits()
+    }
+
+    let x = Simd::from_array([1.0f32, -0.0, -2.0, -f32::NAN]);
+    assert_eq!(to_bits(x) & Simd::splat(1 << 31), x.sign_bit());
+    for lane in x.to_array() {
+        assert_eq!(lane.sign_bit(), lane.to_bits() & (1 << 31));
+        assert_eq!(lane.sign_combine(-1.0).to_bits(), (-lane).to_bits());
+    }
+}
+
+macro_rules! test_masked_matches_unmasked {
+    ($ftype: ty) => {
+        paste::paste! {
+        #[test]
+        fn [< test_masked_matches_unmasked_ $ftype >]() {
+            use simd_addons::simd::prelude::*;
+
+            // lanes which would trigger special values handling are inactive
+            let x = Simd::<$ftype, 8>::from_array([
+                0.5, $ftype::NAN, -0.25, 1e6, 0.75, -$ftype::INFINITY, -0.5, $ftype::INFINITY,
+            ]);
+            let mask = Mask::from_array([true, false, true, false, true, false, true, false]);
+            let fallback = Simd::splat(42.0);
+            let check = |masked: Simd<$ftype, 8>, unmasked: Simd<$ftype, 8>| {
+                for i in 0..8 {
+                    let expected = if mask.test(i) { unmasked[i] } else { fallback[i] };
+                    assert_eq!(masked[i].to_bits(), expected.to_bits(), "lane {}", i);
+                }
+            };
+
+            check(x.exp_masked(mask, fallback), Exponent::exp(x));
+            check(x.exp2_masked(mask, fallback), Exponent::exp2(x));
+            check(x.exp_m1_masked(mask, fallback), x.exp_m1());
+            check(x.sin_masked(mask, fallback), Trigonometry::sin(x));
+            check(x.cos_masked(mask, fallback), Trigonometry::cos(x));
+            check(x.tan_masked(mask, fallback), x.tan());
+            check(x.asin_masked(mask, fallback), x.asin());
+            check(x.acos_masked(mask, fallback), x.acos());
+            check(x.atan_masked(mask, fallback), x.atan());
+            check(x.atan2_masked(x.abs(), mask, fallback), x.atan2(x.abs()));
+            let (sin, cos) = x.sin_cos_masked(mask, (fallback, fallback));
+            check(sin, Trigonometry::sin(x));
+            check(cos, Trigonometry::cos(x));
+
+            assert_eq!(Exponent::exp_masked(1.0 as $ftype, false, 2.0), 2.0);
+            assert_eq!(Exponent::exp_masked(1.0 as $ftype, true, 2.0), Exponent::exp(1.0 as $ftype));
+
+            // every masked variant is available under the same bounds
+            fn tan_or_default<T: Trigonometry + Default>(x: T, active: bool) -> T {
+                x.tan_masked(active, T::default())
+            }
+            assert_eq!(tan_or_default(1.0 as $ftype, false), 0.0);
+            assert_eq!(tan_or_default(1.0 as $ftype, true), Trigonometry::tan(1.0 as $ftype));
+        }
+        }
+    };
+}
+
+test_masked_matches_unmasked!(f32);
+test_masked_matches_unmasked!(f64);
+
+macro_rules! test_checked_flags {
+    ($ftype: ty) => {
+        paste::paste! {
+        #[test]
+        fn [< test_checked_flags_ $ftype >]() {
+            use simd_addons::simd::prelude::*;
+
+            let x = Simd::<$ftype, 8>::from_array([
+                0.0, 1.0, -1.0, 2.0, 1e4, -1e4, $ftype::INFINITY, $ftype::NAN,
+            ]);
+            let lanes = |mask: Mask<_, 8>| (0..8).filter(|&i| mask.test(i)).collect::<Vec<_>>();
+
+            let (exp, flags) = x.exp_checked();
+            assert_eq!(exp.to_bits(), Exponent::exp(x).to_bits());
+            assert_eq!(lanes(flags.overflow), [4]);
+            assert_eq!(lanes(flags.underflow), [5]);
+            assert_eq!(lanes(flags.any()), [4, 5]);
+
+            let (exp_m1, flags) = x.exp_m1_checked();
+            assert_eq!(exp_m1.to_bits(), x.exp_m1().to_bits());
+            assert_eq!(lanes(flags.overflow), [4]);
+            assert!(!flags.underflow.any());
+
+            let (sin, flags) = x.sin_checked();
+            assert_eq!(sin.to_bits(), Trigonometry::sin(x).to_bits());
+            assert_eq!(lanes(flags.invalid), [6]);
+            assert_eq!(lanes(Trigonometry::sin_checked(Simd::splat(1e20 as $ftype)).1.invalid), (0..8).collect::<Vec<_>>());
+
+            let (asin, flags) = x.asin_checked();
+            assert_eq!(asin.to_bits(), x.asin().to_bits());
+            assert_eq!(lanes(flags.invalid), [3, 4, 5, 6]);
+            assert_eq!(lanes(x.acos_checked().1.invalid), [3, 4, 5, 6]);
+
+            assert!(!x.atan2_checked(x).1.any().any());
+            assert!(!x.tan_checked().1.divide_by_zero.any());
+
+            for i in 0..8 {
+                let (exp, flags) = Exponent::exp_checked(x[i]);
+                assert_eq!(exp.to_bits(), Exponent::exp(x[i]).to_bits());
+                assert_eq!(flags, x.exp_checked().1.map(|mask| mask.test(i)));
+                assert_eq!(Trigonometry::asin_checked(x[i]).1, x.asin_checked().1.map(|mask| mask.test(i)));
+            }
+
+            // flags agree with the results, also near the thresholds of overflow and underflow
+            let tiny = $ftype::MIN_POSITIVE;
+            let values = (-1100.0..1100.0 as $ftype)
+                .linspace(100_001)
+                .chain((-760.0..-700.0 as $ftype).linspace(100_001))
+                .chain((-130.0..-80.0 as $ftype).linspace(100_001))
+                .chain([tiny, tiny / 2.0, -tiny / 2.0, 0.0, -0.0]);
+            for x in values {
+                let results = [
+                    ("exp", Exponent::exp_checked(x)),
+                    ("exp2", Exponent::exp2_checked(x)),
+                    ("exp_m1", Exponent::exp_m1_checked(x)),
+                ];
+                for (name, (result, flags)) in results {
+                    assert_eq!(flags.overflow, result.is_infinite(), "{}({})", name, x);
+                    let underflow = x != 0.0 && result.abs() < tiny;
+                    assert_eq!(flags.underflow, underflow, "{}({})", name, x);
+                    assert!(!flags.divide_by_zero, "{}({})", name, x);
+                }
+            }
+        }
+        }
+    };
+}
+
+test_checked_flags!(f32);
+test_checked_flags!(f64);
+
+macro_rules! test_rounding {
+    ($ftype: ty) => {
+        paste::paste! {
+        #[test]
+        fn [< test_rounding_ $ftype >]() {
+            use simd_addons::math::{util::FastRound, SimdFloatMath};
+            use simd_addons::simd::Simd;
+
+            let special = [
+                0.0, -0.0, 0.5, -0.5, 1.5, -1.5, 2.5, -2.5, 0.49999997, -0.49999997,
+                $ftype::MIN_POSITIVE / 2.0, $ftype::EPSILON, $ftype::MAX, $ftype::MIN,
+                $ftype::INFINITY, -$ftype::INFINITY, $ftype::NAN,
+                (1u64 << ($ftype::MANTISSA_DIGITS - 1)) as $ftype + 1.0,
+                (1u64 << ($ftype::MANTISSA_DIGITS - 2)) as $ftype + 0.5,
+            ];
+            for x in (-1e3..1e3 as $ftype).linspace(100_001).chain(special) {
+                let v = Simd::<$ftype, 1>::splat(x);
+                assert_eq!(SimdFloatMath::trunc(v)[0].to_bits(), x.trunc().to_bits(), "trunc({})", x);
+                assert_eq!(SimdFloatMath::floor(v)[0].to_bits(), x.floor().to_bits(), "floor({})", x);
+                assert_eq!(SimdFloatMath::ceil(v)[0].to_bits(), x.ceil().to_bits(), "ceil({})", x);
+                assert_eq!(SimdFloatMath::round(v)[0].to_bits(), x.round().to_bits(), "round({})", x);
+                assert_eq!(v.fast_round()[0].to_bits(), x.round_ties_even().to_bits(), "fast_round({})", x);
+                if x.is_finite() {
+                    assert_eq!(SimdFloatMath::fract(v)[0].to_bits(), x.fract().to_bits(), "fract({})", x);
+                }
+            }
+        }
+        }
+    };
+}
+
+test_rounding!(f32);
+test_rounding!(f64);
+
+macro_rules! test_mul_add {
+    ($ftype: ident, $utype: ident) => {
+        paste::paste! {
+        #[test]
+        fn [< test_mul_add_ $ftype >]() {
+            use rand_core::{RngCore, SeedableRng};
+            use simd_addons::math::SimdFloatMath;
+            use simd_addons::simd::Simd;
+
+            const BITS: u32 = $utype::BITS;
+            const MANTISSA_BITS: u32 = $ftype::MANTISSA_DIGITS - 1;
+            // random mantissas and signs, with exponents around `exp`
+            let mut rng = rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(7);
+            let mut random = |exp: i32| {
+                let bits = rng.next_u64() as $utype;
+                let exp = (exp + (bits >> (BITS - 5)) as i32 - 16).clamp(1 - $ftype::MAX_EXP, $ftype::MAX_EXP - 1);
+                let biased = (exp + $ftype::MAX_EXP - 1) as $utype;
+                $ftype::from_bits(bits & !(!0 << MANTISSA_BITS) | biased << MANTISSA_BITS | bits >> 4 << (BITS - 1))
+            };
+            let special = [
+                0.0, -0.0, 1.0, -1.0, $ftype::MIN_POSITIVE, $ftype::MIN_POSITIVE / 3.0, $ftype::EPSILON,
+                $ftype::MAX, $ftype::MIN, $ftype::INFINITY, -$ftype::INFINITY, $ftype::NAN,
+            ];
+            let mut cases = vec![];
+            for &a in &special {
+                for &b in &special {
+                    for &c in &special {
+                        cases.push([a, b, c]);
+                    }
+                }
+            }
+            // the product cancelled but for a bit far below the addend
+            let (a, b) = (1.0 + (2.0 as $ftype).powi(14 - MANTISSA_BITS as i32), 1.0 + (2.0 as $ftype).powi(12 - MANTISSA_BITS as i32));
+            cases.push([a, b, -(a * b)]);
+            let max_exp = $ftype::MAX_EXP;
+            for (a_exp, b_exp) in [(0, 0), (max_exp / 2, max_exp / 2), (-max_exp / 2, -max_exp / 2), (max_exp - 8, 8), (-max_exp, 20), (max_exp, -max_exp)] {
+                for _ in 0..20_000 {
+                    let (a, b) = (random(a_exp), random(b_exp));
+                    // addends of any size, and ones cancelling the product, or most of it
+                    let c = random(a_exp + b_exp);
+                    let cancelling = -(a * b) * (1.0 + random(-(MANTISSA_BITS as i32)).abs());
+                    let large = a * b * random(2 * MANTISSA_BITS as i32);
+                    cases.extend([[a, b, c], [a, b, cancelling], [a, b, -(a * b)], [a, b, large]]);
+                    // products of halves of mantissas, which are often halfway between two floats,
+                    // and addends too small to be seen but in the rounding of the halfway cases
+                    let short = |x: $ftype| $ftype::from_bits(x.to_bits() & !0 << (MANTISSA_BITS - $ftype::MANTISSA_DIGITS / 2));
+                    let (a, b) = (short(a), short(b));
+                    cases.push([a, b, a * b * random(-3 * MANTISSA_BITS as i32)]);
+                }
+            }
+            for chunk in cases.chunks(8) {
+                let lanes = |i: usize| Simd::<$ftype, 8>::from_array(core::array::from_fn(|j| chunk[j % chunk.len()][i]));
+                let result = SimdFloatMath::mul_add(lanes(0), lanes(1), lanes(2));
+                for (j, &[a, b, c]) in chunk.iter().enumerate() {
+                    let expected = a.mul_add(b, c);
+                    assert!(
+                        result[j].to_bits() == expected.to_bits() || result[j].is_nan() && expected.is_nan(),
+                        "mul_add({:e}, {:e}, {:e}) = {:e}, expected {:e}", a, b, c, result[j], expected
+                    );
+                }
+            }
+        }
+        }
+    };
+}
+
+test_mul_add!(f32, u32);
+test_mul_add!(f64, u64);
+
+macro_rules! test_rsqrt {
+    ($ftype: ident, $max_ulps: literal) => {
+        paste::paste! {
+        #[test]
+        fn [< test_rsqrt_ $ftype >]() {
+            use simd_addons::math::SimdFloatMath;
+            use simd_addons::simd::Simd;
+
+            // all binades of normal numbers, with different mantissas
+            let mantissas = (1.0..2.0 as $ftype).linspace(1_001);
+            for (i, mantissa) in mantissas.enumerate() {
+                let x = mantissa * (2.0 as $ftype).powi(i as i32 % 250 - 125);
+                let result = SimdFloatMath::rsqrt(Simd::<$ftype, 1>::splat(x))[0] as f64;
+                let exact = 1.0 / (x as f64).sqrt();
+                let ulps = ((result - exact) / exact).abs() / $ftype::EPSILON as f64;
+                assert!(ulps <= $max_ulps, "rsqrt({}) = {} is {} ulps off", x, result, ulps);
+            }
+
+            // all binades of subnormal numbers
+            let mut x = $ftype::MIN_POSITIVE * 0.75;
+            while x > 0.0 {
+                let result = SimdFloatMath::rsqrt(Simd::<$ftype, 1>::splat(x))[0] as f64;
+                let exact = 1.0 / (x as f64).sqrt();
+                let ulps = ((result - exact) / exact).abs() / $ftype::EPSILON as f64;
+                assert!(ulps <= $max_ulps, "rsqrt({}) = {} is {} ulps off", x, result, ulps);
+                x /= 2.0;
+            }
+
+            let (inf, nan) = ($ftype::INFINITY, $ftype::NAN);
+            let special = Simd::<$ftype, 8>::from_array([0.0, -0.0, inf, -1.0, -inf, nan, 1.0, 1.0]);
+            let result = SimdFloatMath::rsqrt(special).to_array();
+            assert_eq!(result[..3], [$ftype::INFINITY, $ftype::NEG_INFINITY, 0.0]);
+            assert!(result[3..6].iter().all(|x| x.is_nan()));
+        }
+        }
+    };
+}
+
+test_rsqrt!(f32, 2.0);
+test_rsqrt!(f64, 2.0);
+
+/// Checks that every lane of `$func` evaluated with each lane count is the same as with one lane.
+macro_rules! assert_lanes_match_single_lane {
+    ($name: literal, $( $x: ident in $values: expr ),+ => $func: expr) => {
+        let single = simd_map!(1, $( $x in $values ),+ => $func);
+        for_each_lane_count!(|LANES| {
+            let result = simd_map!(LANES, $( $x in $values ),+ => $func);
+            for (i, (result, single)) in std::iter::zip(result, &single).enumerate() {
+                assert_eq!(
+                    result.to_bits(),
+                    single.to_bits(),
+                    "{}({:?}) with {} lanes, lane {}",
+                    $name,
+                    ($( $values[i] ),+),
+                    LANES,
+                    i % LANES,
+                );
+            }
+        });
+    };
+}
+
+macro_rules! test_lanes_match_single_lane {
+    ($ftype: ident) => {
+        paste::paste! {
+        #[test]
+        fn [< test_lanes_match_single_lane_ $ftype >]() {
+            let specials = [
+                $ftype::NAN, $ftype::INFINITY, -$ftype::INFINITY, 0.0, -0.0, $ftype::MAX, $ftype::MIN,
+                $ftype::MIN_POSITIVE / 2.0, 1e30, -1e30, 1e3, -1e3,
+            ];
+            // every third value is special, so vectors of any width mix special and normal lanes
+            // as well as lanes inside and outside of the ranges of the fast paths
+            let values: Vec<$ftype> = (-1.5..1.5 as $ftype)
+                .linspace(1_000)
+                .chain((-100.0..100.0 as $ftype).linspace(2_000))
+                .chain((-1e4..1e4 as $ftype).linspace(1_000))
+                .enumerate()
+                .map(|(i, x)| if i % 3 == 0 { specials[i / 3 % specials.len()] } else { x })
+                .collect();
+            let rotated: Vec<$ftype> = values[7..].iter().chain(&values[..7]).copied().collect();
+
+            assert_lanes_match_single_lane!("exp", x in values => Exponent::exp(x));
+            assert_lanes_match_single_lane!("exp2", x in values => Exponent::exp2(x));
+            assert_lanes_match_single_lane!("exp_m1", x in values => x.exp_m1());
+            assert_lanes_match_single_lane!("sin", x in values => Trigonometry::sin(x));
+            assert_lanes_match_single_lane!("cos", x in values => Trigonometry::cos(x));
+            assert_lanes_match_single_lane!("tan", x in values => x.tan());
+            assert_lanes_match_single_lane!("sin_cos.0", x in values => x.sin_cos().0);
+            assert_lanes_match_single_lane!("sin_cos.1", x in values => x.sin_cos().1);
+            assert_lanes_match_single_lane!("asin", x in values => x.asin());
+            assert_lanes_match_single_lane!("acos", x in values => x.acos());
+            assert_lanes_match_single_lane!("atan", x in values => x.atan());
+            assert_lanes_match_single_lane!("atan2", x in values, y in rotated => y.atan2(x));
+        }
+        }
+    };
+}
+
+test_lanes_match_single_lane!(f32);
+test_lanes_match_single_lane!(f64);
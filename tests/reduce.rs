@@ -0,0 +1,232 @@
+//! Tests of `algo::reduce`. Reductions of `f32` slices are checked against sequential ones in
+//! `f64`, which are exact enough to measure their errors.
+#![cfg_attr(not(simd_shim), feature(portable_simd))]
+
+use proptest::prelude::*;
+use simd_addons::algo::reduce::{self, Summation};
+
+const SUMMATIONS: [Summation; 3] = [
+    Summation::Naive,
+    Summation::Pairwise,
+    Summation::Compensated,
+];
+
+/// Error bound of `summation` of `n` terms with the sum of absolute values `magnitude`, for sums
+/// of `f32`.
+fn error_bound(summation: Summation, n: usize, sum: f64, magnitude: f64) -> f64 {
+    let epsilon = f32::EPSILON as f64;
+    match summation {
+        // the lanes and the accumulators are added up at the end, which adds a few roundings
+        Summation::Naive => (n as f64 + 8.0) * epsilon * magnitude,
+        Summation::Pairwise => (32.0 + (n.max(1) as f64).log2() + 8.0) * epsilon * magnitude,
+        Summation::Compensated => {
+            epsilon * sum.abs() + 2.0 * (n as f64 + 8.0).powi(2) * epsilon * epsilon * magnitude
+        }
+    }
+}
+
+fn check_sum(data: &[f32]) {
+    let exact: f64 = data.iter().map(|&x| x as f64).sum();
+    let magnitude: f64 = data.iter().map(|&x| (x as f64).abs()).sum();
+    for summation in SUMMATIONS {
+        let sum = reduce::sum(data, summation) as f64;
+        assert!(
+            (sum - exact).abs() <= error_bound(summation, data.len(), exact, magnitude),
+            "{:?} sum of {} elements is {}, expected {}",
+            summation,
+            data.len(),
+            sum,
+            exact
+        );
+    }
+}
+
+fn check_dot(a: &[f32], b: &[f32]) {
+    let exact: f64 = a.iter().zip(b).map(|(&x, &y)| x as f64 * y as f64).sum();
+    let magnitude: f64 = a
+        .iter()
+        .zip(b)
+        .map(|(&x, &y)| (x as f64 * y as f64).abs())
+        .sum();
+    for summation in SUMMATIONS {
+        let dot = reduce::dot(a, b, summation) as f64;
+        assert!(
+            (dot - exact).abs() <= error_bound(summation, a.len(), exact, 2.0 * magnitude),
+            "{:?} dot product of {} elements is {}, expected {}",
+            summation,
+            a.len(),
+            dot,
+            exact
+        );
+    }
+}
+
+#[test]
+fn test_sum_lengths() {
+    for len in (0..200).chain([1000, 4097, 100_000]) {
+        let data: Vec<f32> = (0..len).map(|i| (i as f32 * 0.37).sin() * 100.0).collect();
+        check_sum(&data);
+        let other: Vec<f32> = (0..len).map(|i| (i as f32 * 0.11).cos()).collect();
+        check_dot(&data, &other);
+    }
+    assert_eq!(reduce::sum::<f64>(&[], Summation::default()), 0.0);
+    assert_eq!(reduce::dot::<f64>(&[], &[], Summation::default()), 0.0);
+}
+
+#[test]
+fn test_ill_conditioned() {
+    // every `1.0` is lost by naive summation in `f64`, but not by compensated one
+    for len in [3, 17, 64, 1001] {
+        let mut data = vec![1.0; len];
+        data[0] = 1e17;
+        data[len / 2] = -1e17;
+        let exact = (len - 2) as f64;
+        assert_eq!(reduce::sum(&data, Summation::Compensated), exact);
+        let ones = vec![1.0; len];
+        assert_eq!(reduce::dot(&data, &ones, Summation::Compensated), exact);
+    }
+
+    // the products have rounding errors, which are kept
+    let x = 1.0 + f64::EPSILON;
+    let a = [x, -1.0, x, -1.0];
+    let b = [x, 1.0 + 2.0 * f64::EPSILON, -x, 1.0];
+    let exact = -2.0 - 2.0 * f64::EPSILON;
+    assert_eq!(reduce::dot(&a, &b, Summation::Compensated), exact);
+    assert_eq!(
+        reduce::dot(&a[..2], &b[..2], Summation::Compensated),
+        f64::EPSILON.powi(2)
+    );
+}
+
+#[test]
+fn test_norm2() {
+    for summation in SUMMATIONS {
+        for len in [1, 2, 7, 100] {
+            for scale in [1.0, 1e-30, 1e30, f32::MIN_POSITIVE, 1e-45, f32::MAX / 16.0] {
+                let data = vec![scale; len];
+                let expected = (scale as f64 * (len as f64).sqrt()) as f32;
+                let norm = reduce::norm2(&data, summation);
+                let tolerance = if scale == 1e-45 {
+                    0.5
+                } else {
+                    4.0 * f32::EPSILON
+                };
+                assert!(
+                    ((norm - expected) / expected).abs() <= tolerance,
+                    "{:?} norm of {} elements {} is {}, expected {}",
+                    summation,
+                    len,
+                    scale,
+                    norm,
+                    expected
+                );
+            }
+        }
+        assert_eq!(reduce::norm2(&[3e300, -4e300], summation), 5e300);
+        assert_eq!(reduce::norm2(&[3e-300, -4e-300], summation), 5e-300);
+        assert_eq!(reduce::norm2::<f64>(&[], summation), 0.0);
+        assert_eq!(reduce::norm2(&[0.0, -0.0], summation), 0.0);
+        assert_eq!(
+            reduce::norm2(&[1.0, f32::NEG_INFINITY], summation),
+            f32::INFINITY
+        );
+        assert_eq!(
+            reduce::norm2(&[f32::NAN, f32::INFINITY], summation),
+            f32::INFINITY
+        );
+        assert!(reduce::norm2(&[1.0, f32::NAN], summation).is_nan());
+    }
+}
+
+#[test]
+fn test_mean_variance() {
+    // a large offset makes the textbook formula lose all digits
+    for len in (1..200).chain([1000, 4097]) {
+        let data: Vec<f32> = (0..len).map(|i| 1e4 + (i as f32 * 0.73).sin()).collect();
+        let mean: f64 = data.iter().map(|&x| x as f64).sum::<f64>() / len as f64;
+        let variance: f64 =
+            data.iter().map(|&x| (x as f64 - mean).powi(2)).sum::<f64>() / len as f64;
+        let (result_mean, result_variance) = reduce::mean_variance(&data);
+        assert!(
+            (result_mean as f64 - mean).abs() <= 4.0 * f32::EPSILON as f64 * mean,
+            "mean of {} elements is {}, expected {}",
+            len,
+            result_mean,
+            mean
+        );
+        assert!(
+            (result_variance as f64 - variance).abs() <= 1e-3 * variance.max(1e-3),
+            "variance of {} elements is {}, expected {}",
+            len,
+            result_variance,
+            variance
+        );
+        assert_eq!(reduce::mean(&data), result_mean);
+        assert_eq!(reduce::variance(&data), result_variance);
+    }
+    assert_eq!(reduce::mean_variance(&[2.0; 100]), (2.0, 0.0));
+    let (mean, variance) = reduce::mean_variance::<f64>(&[]);
+    assert!(mean.is_nan() && variance.is_nan());
+}
+
+#[test]
+fn test_min_max_index() {
+    for len in 1..200 {
+        for position in [0, len / 3, len - 1] {
+            let mut data: Vec<f64> = (0..len).map(|i| ((i * 7) % 13) as f64).collect();
+            data[position] = -1.0;
+            assert_eq!(reduce::min_index(&data), Some((position, -1.0)));
+            data[position] = 100.0;
+            assert_eq!(reduce::max_index(&data), Some((position, 100.0)));
+        }
+    }
+
+    // ties resolve to the first index, NaNs are ignored
+    let mut data = vec![f32::NAN; 100];
+    assert_eq!(reduce::min_index(&data), None);
+    assert_eq!(reduce::max_index(&data), None);
+    data[70] = 5.0;
+    data[33] = 5.0;
+    data[90] = 5.0;
+    assert_eq!(reduce::min_index(&data), Some((33, 5.0)));
+    assert_eq!(reduce::max_index(&data), Some((33, 5.0)));
+    data[10] = f32::NEG_INFINITY;
+    data[99] = f32::INFINITY;
+    assert_eq!(reduce::min_index(&data), Some((10, f32::NEG_INFINITY)));
+    assert_eq!(reduce::max_index(&data), Some((99, f32::INFINITY)));
+    assert_eq!(reduce::min_index::<f32>(&[]), None);
+}
+
+#[test]
+#[should_panic(expected = "slices must have the same length")]
+fn test_dot_lengths() {
+    reduce::dot(&[1.0, 2.0], &[1.0], Summation::default());
+}
+
+proptest! {
+    #[test]
+    fn random_sum(data in prop::collection::vec(-1e6f32..1e6, 0..3000)) {
+        check_sum(&data);
+    }
+
+    #[test]
+    fn random_dot(pairs in prop::collection::vec((-1e3f32..1e3, -1e3f32..1e3), 0..3000)) {
+        let (a, b): (Vec<f32>, Vec<f32>) = pairs.into_iter().unzip();
+        check_dot(&a, &b);
+    }
+
+    #[test]
+    fn random_min_max(data in prop::collection::vec(prop_oneof![any::<f32>(), Just(f32::NAN)], 0..500)) {
+        let numbers = data.iter().enumerate().filter(|(_, x)| !x.is_nan());
+        let min = numbers.clone().fold(None, |best: Option<(usize, f32)>, (i, &x)| match best {
+            Some((_, y)) if y <= x => best,
+            _ => Some((i, x)),
+        });
+        let max = numbers.fold(None, |best: Option<(usize, f32)>, (i, &x)| match best {
+            Some((_, y)) if y >= x => best,
+            _ => Some((i, x)),
+        });
+        prop_assert_eq!(reduce::min_index(&data).map(|(i, _)| i), min.map(|(i, _)| i));
+        prop_assert_eq!(reduce::max_index(&data).map(|(i, _)| i), max.map(|(i, _)| i));
+    }
+}
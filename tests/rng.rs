@@ -0,0 +1,270 @@
+//! Tests of `rng`: the lanes of the generators against the scalar ones of `rand_xoshiro` and
+//! `rand_pcg` and the known answers of Philox, the distributions by their moments and CDFs at a few
+//! points. Sample estimates are allowed to deviate by 5 standard errors.
+#![cfg_attr(not(simd_shim), feature(portable_simd))]
+
+#[allow(dead_code)]
+#[macro_use]
+mod common;
+
+use rand_core::{RngCore, SeedableRng};
+use simd_addons::{
+    rng::{self, philox4x32, Pcg32, Philox4x32, RandomFloat, SimdRng, Xoshiro256PlusPlus},
+    simd::{LaneCount, Simd, SupportedLaneCount},
+};
+
+/// Checks that lane `i` of `rng` matches `reference(i)`, with both sizes of numbers interleaved.
+fn check_lanes<R: SimdRng<N>, S: RngCore, const N: usize>(
+    mut rng: R,
+    reference: impl Fn(usize) -> S,
+) where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let mut references: Vec<S> = (0..N).map(reference).collect();
+    for step in 0..100 {
+        if step % 3 == 0 {
+            let lanes = rng.next_u32();
+            for (i, reference) in references.iter_mut().enumerate() {
+                assert_eq!(
+                    lanes[i],
+                    reference.next_u32(),
+                    "lane {i} of {N}, step {step}"
+                );
+            }
+        } else {
+            let lanes = rng.next_u64();
+            for (i, reference) in references.iter_mut().enumerate() {
+                assert_eq!(
+                    lanes[i],
+                    reference.next_u64(),
+                    "lane {i} of {N}, step {step}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_xoshiro() {
+    for seed in [0, 1, 42, u64::MAX] {
+        for_each_lane_count!(|N| {
+            check_lanes(Xoshiro256PlusPlus::<N>::seed_from_u64(seed), |i| {
+                let mut reference = rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(seed);
+                for _ in 0..i {
+                    reference.jump();
+                }
+                reference
+            });
+        });
+    }
+    let state = [1, 2, 3, 4];
+    let bytes: Vec<u8> = state.iter().flat_map(|x: &u64| x.to_le_bytes()).collect();
+    let reference = rand_xoshiro::Xoshiro256PlusPlus::from_seed(bytes.try_into().unwrap());
+    check_lanes(Xoshiro256PlusPlus::<1>::from_state(state), |_| {
+        reference.clone()
+    });
+}
+
+#[test]
+#[should_panic(expected = "the state of xoshiro256++ is all zeros")]
+fn test_xoshiro_zero() {
+    Xoshiro256PlusPlus::<4>::from_state([0; 4]);
+}
+
+#[test]
+fn test_pcg() {
+    for (state, stream) in [(0, 0), (42, 54), (u64::MAX, u64::MAX - 3)] {
+        for_each_lane_count!(|N| {
+            check_lanes(Pcg32::<N>::new(state, stream), |i| {
+                rand_pcg::Pcg32::new(state, stream.wrapping_add(i as u64))
+            });
+        });
+    }
+}
+
+#[test]
+fn test_philox() {
+    // known answers of the reference implementation, Random123
+    let cases: [([u32; 4], [u32; 2], [u32; 4]); 3] = [
+        (
+            [0; 4],
+            [0; 2],
+            [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8],
+        ),
+        (
+            [u32::MAX; 4],
+            [u32::MAX; 2],
+            [0x408f276d, 0x41c83b0e, 0xa20bc7c6, 0x6d5451fd],
+        ),
+        (
+            [0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344],
+            [0xa4093822, 0x299f31d0],
+            [0xd16cfe09, 0x94fdcceb, 0x5001e420, 0x24126ea1],
+        ),
+    ];
+    for (counter, key, expected) in cases {
+        let words = philox4x32(counter.map(Simd::<u32, 4>::splat), key);
+        assert_eq!(words, expected.map(Simd::splat));
+    }
+
+    // the generator encrypts consecutive counters of its lanes
+    let (key, stream) = (0x0123_4567_89ab_cdef_u64, 0xfedc_ba98_7654_3210_u64);
+    let block = |counter: u64| {
+        let counter = [
+            counter as u32,
+            (counter >> 32) as u32,
+            stream as u32,
+            (stream >> 32) as u32,
+        ];
+        philox4x32(
+            counter.map(Simd::<u32, 1>::splat),
+            [key as u32, (key >> 32) as u32],
+        )
+        .map(|word| word[0])
+    };
+    for_each_lane_count!(|N| {
+        let mut rng = Philox4x32::<N>::new(key, stream);
+        for b in 0..5 {
+            let words = [
+                rng.next_u32(),
+                rng.next_u32(),
+                rng.next_u32(),
+                rng.next_u32(),
+            ];
+            for i in 0..N {
+                let expected = block(b * N as u64 + i as u64);
+                assert_eq!(words.map(|word| word[i]), expected, "lane {i} of {N}");
+            }
+        }
+        rng.seek(3);
+        let words = rng.next_u64();
+        for i in 0..N {
+            let expected = block(3 * N as u64 + i as u64);
+            assert_eq!(words[i], expected[0] as u64 | (expected[1] as u64) << 32);
+        }
+    });
+}
+
+#[test]
+fn test_unit() {
+    let bits = Simd::from_array([0, 1 << 31, 1 << 9, u32::MAX]);
+    assert_eq!(
+        rng::unit_f32(bits).to_array(),
+        [0.0, 0.5, f32::EPSILON, 1.0 - f32::EPSILON]
+    );
+    let bits = Simd::from_array([0, 1 << 63, 1 << 12, u64::MAX]);
+    assert_eq!(
+        rng::unit_f64(bits).to_array(),
+        [0.0, 0.5, f64::EPSILON, 1.0 - f64::EPSILON]
+    );
+}
+
+const SAMPLES: usize = 1 << 20;
+
+/// Draws `SAMPLES` numbers of a distribution from `rng`.
+fn sample<T: RandomFloat + Into<f64>, R: SimdRng<N>, const N: usize>(
+    mut rng: R,
+    distribution: fn(&mut R) -> Simd<T, N>,
+) -> Vec<f64>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    (0..SAMPLES / N)
+        .flat_map(|_| distribution(&mut rng).to_array())
+        .map(Into::into)
+        .collect()
+}
+
+fn assert_close(estimate: f64, expected: f64, standard_error: f64) {
+    assert!(
+        (estimate - expected).abs() < 5.0 * standard_error,
+        "{estimate} instead of {expected}, standard error {standard_error}"
+    );
+}
+
+/// Checks the mean, the variance and the fractions of the samples below the points of the CDF.
+fn check_distribution(samples: &[f64], mean: f64, variance: f64, cdf: &[(f64, f64)]) {
+    let n = samples.len() as f64;
+    assert!(samples.iter().all(|x| x.is_finite()));
+    let sample_mean = samples.iter().sum::<f64>() / n;
+    assert_close(sample_mean, mean, (variance / n).sqrt());
+    let sample_variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+    // the standard error is estimated by the fourth moment of the sample
+    let fourth = samples.iter().map(|x| (x - mean).powi(4)).sum::<f64>() / n;
+    assert_close(
+        sample_variance,
+        variance,
+        ((fourth - variance * variance) / n).sqrt(),
+    );
+    for &(x, p) in cdf {
+        let fraction = samples.iter().filter(|&&y| y < x).count() as f64 / n;
+        assert_close(fraction, p, (p * (1.0 - p) / n).sqrt());
+    }
+}
+
+/// `P(X < x)` of the standard normal distribution, `erfc(-x / √2) / 2` computed beforehand.
+const NORMAL_CDF: [(f64, f64); 12] = [
+    (-4.0, 3.16712418331e-5),
+    (-3.442619855899, 2.88054256196e-4),
+    (-3.0, 1.34989803163e-3),
+    (-2.0, 2.27501319482e-2),
+    (-1.0, 0.15865525393145707),
+    (-0.5, 0.3085375387259869),
+    (0.0, 0.5),
+    (0.5, 0.6914624612740131),
+    (1.0, 0.8413447460685429),
+    (2.0, 0.9772498680518208),
+    (3.0, 0.9986501019683699),
+    (3.442619855899, 0.9997119457438042),
+];
+
+fn check_normal(samples: &[f64]) {
+    check_distribution(samples, 0.0, 1.0, &NORMAL_CDF);
+}
+
+fn check_exponential(samples: &[f64]) {
+    assert!(samples.iter().all(|&x| x >= 0.0));
+    let cdf = [0.01, 0.1, 0.5, 1.0, 2.0, 4.0, 7.0, 7.69711747013105, 9.0]
+        .map(|x: f64| (x, 1.0 - (-x).exp()));
+    check_distribution(samples, 1.0, 1.0, &cdf);
+}
+
+fn check_uniform(samples: &[f64]) {
+    assert!(samples.iter().all(|&x| (0.0..1.0).contains(&x)));
+    let cdf = [0.001, 0.1, 0.25, 0.5, 0.9, 0.999].map(|x| (x, x));
+    check_distribution(samples, 0.5, 1.0 / 12.0, &cdf);
+}
+
+#[test]
+fn test_distributions_f32() {
+    check_uniform(&sample(
+        Xoshiro256PlusPlus::<8>::seed_from_u64(1),
+        rng::uniform::<f32, _, 8>,
+    ));
+    check_normal(&sample(
+        Xoshiro256PlusPlus::<8>::seed_from_u64(2),
+        rng::normal::<f32, _, 8>,
+    ));
+    check_normal(&sample(
+        Philox4x32::<16>::new(3, 0),
+        rng::normal::<f32, _, 16>,
+    ));
+    check_exponential(&sample(
+        Pcg32::<4>::new(4, 0),
+        rng::exponential::<f32, _, 4>,
+    ));
+}
+
+#[test]
+fn test_distributions_f64() {
+    check_uniform(&sample(Pcg32::<8>::new(5, 0), rng::uniform::<f64, _, 8>));
+    check_normal(&sample(Pcg32::<2>::new(6, 0), rng::normal::<f64, _, 2>));
+    check_exponential(&sample(
+        Xoshiro256PlusPlus::<8>::seed_from_u64(7),
+        rng::exponential::<f64, _, 8>,
+    ));
+    check_exponential(&sample(
+        Philox4x32::<1>::new(8, 0),
+        rng::exponential::<f64, _, 1>,
+    ));
+}
@@ -0,0 +1,167 @@
+//! Tests of `algo::scan` against sequential scans. Floats are integers small enough to be summed
+//! exactly, so the order of additions doesn't matter.
+#![cfg_attr(not(simd_shim), feature(portable_simd))]
+
+#[allow(dead_code)]
+#[macro_use]
+mod common;
+
+use proptest::prelude::*;
+use simd_addons::{
+    algo::scan::{self, Max, Min, Product, ScanOp, SimdScan, Sum},
+    simd::{cmp::SimdPartialEq, LaneCount, Simd, SupportedLaneCount},
+};
+
+/// Inclusive and exclusive sequential scans of `data` by `op` with `identity`, and the total.
+fn sequential<T: Copy>(data: &[T], identity: T, op: impl Fn(T, T) -> T) -> (Vec<T>, Vec<T>, T) {
+    let mut inclusive = Vec::new();
+    let mut exclusive = Vec::new();
+    let mut total = identity;
+    for &x in data {
+        exclusive.push(total);
+        total = op(total, x);
+        inclusive.push(total);
+    }
+    (inclusive, exclusive, total)
+}
+
+/// Takes the last non-zero element, which isn't commutative.
+struct LastNonZero;
+
+impl ScanOp<u32> for LastNonZero {
+    const IDENTITY: u32 = 0;
+
+    fn apply<const N: usize>(a: Simd<u32, N>, b: Simd<u32, N>) -> Simd<u32, N>
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        b.simd_eq(Simd::splat(0)).select(a, b)
+    }
+}
+
+/// Checks scans of lanes and slices by `$op` against the sequential ones by `$scalar_op`.
+macro_rules! check_scan {
+    ($etype: ident, $op: ident, $data: expr, $identity: expr, $scalar_op: expr) => {{
+        let data: Vec<$etype> = $data;
+        let (inclusive, exclusive, total) = sequential(&data, $identity, $scalar_op);
+
+        let mut result = data.clone();
+        scan::scan::<$op, _>(&mut result);
+        assert_eq!(result, inclusive, "{} scan of {:?}", stringify!($op), data);
+        let mut result = data.clone();
+        let result_total = scan::exclusive_scan::<$op, _>(&mut result);
+        assert_eq!(
+            result,
+            exclusive,
+            "{} exclusive scan of {:?}",
+            stringify!($op),
+            data
+        );
+        assert_eq!(result_total, total);
+
+        for_each_lane_count!(|LANES| {
+            if data.len() >= LANES {
+                let lanes = Simd::<$etype, LANES>::from_slice(&data);
+                let (inclusive, exclusive, _) = sequential(&data[..LANES], $identity, $scalar_op);
+                assert_eq!(lanes.scan::<$op>().to_array().as_slice(), inclusive);
+                assert_eq!(
+                    lanes.exclusive_scan::<$op>().to_array().as_slice(),
+                    exclusive
+                );
+            }
+        });
+    }};
+}
+
+macro_rules! test_scan {
+    ($etype: ident, $strategy: expr) => {
+        paste::paste! {
+        mod [< scan_ $etype >] {
+            use super::*;
+
+            fn check(data: Vec<$etype>) {
+                check_scan!($etype, Sum, data.clone(), 0 as $etype, |a, b| a + b);
+                check_scan!($etype, Max, data.clone(), <Max as ScanOp<$etype>>::IDENTITY, |a, b| if b > a { b } else { a });
+                check_scan!($etype, Min, data.clone(), <Min as ScanOp<$etype>>::IDENTITY, |a, b| if b < a { b } else { a });
+                // a few products don't overflow or round
+                let small: Vec<$etype> = data.iter().map(|&x| (x as i64 % 3) as $etype).collect();
+                check_scan!($etype, Product, small, 1 as $etype, |a, b| a * b);
+            }
+
+            #[test]
+            fn lengths() {
+                for len in (0..300).chain([1000, 4097]) {
+                    check((0..len).map(|i| ((i * 37) % 101) as $etype).collect());
+                }
+            }
+
+            proptest! {
+                #[test]
+                fn random(data in prop::collection::vec($strategy, 0..1000)) {
+                    check(data);
+                }
+            }
+        }
+        }
+    };
+}
+
+test_scan!(i32, -1000i32..1000);
+test_scan!(u32, 0u32..1000);
+test_scan!(i64, -1000i64..1000);
+test_scan!(u64, 0u64..1000);
+test_scan!(f32, (-1000i32..1000).prop_map(|x| x as f32));
+test_scan!(f64, (-1000i32..1000).prop_map(|x| x as f64));
+
+#[test]
+fn test_prefix_sum() {
+    let mut data: Vec<u32> = (1..=100).collect();
+    scan::prefix_sum(&mut data);
+    assert!(data
+        .iter()
+        .enumerate()
+        .all(|(i, &x)| x as usize == (i + 1) * (i + 2) / 2));
+
+    let mut data = vec![1u64; 100];
+    assert_eq!(scan::exclusive_prefix_sum(&mut data), 100);
+    assert!(data.iter().enumerate().all(|(i, &x)| x == i as u64));
+
+    let lanes = Simd::from_array([5i32, -1, 2, 0, 7, 3, -4, 1]);
+    assert_eq!(lanes.prefix_sum().to_array(), [5, 4, 6, 6, 13, 16, 12, 13]);
+    assert_eq!(
+        lanes.exclusive_prefix_sum().to_array(),
+        [0, 5, 4, 6, 6, 13, 16, 12]
+    );
+}
+
+#[test]
+fn test_wrapping() {
+    let mut data = [u32::MAX, 1, 1];
+    scan::prefix_sum(&mut data);
+    assert_eq!(data, [u32::MAX, 0, 1]);
+}
+
+#[test]
+fn test_float_specials() {
+    // the identity of addition keeps negative zeros
+    let mut data = [-0.0f32, -0.0, 1.0];
+    scan::prefix_sum(&mut data);
+    assert_eq!(
+        data.map(f32::to_bits),
+        [-0.0f32, -0.0, 1.0].map(f32::to_bits)
+    );
+
+    let mut data = [1.0, f64::NAN, 3.0, f64::NAN, 2.0];
+    scan::scan::<Max, _>(&mut data);
+    assert_eq!(data, [1.0, 1.0, 3.0, 3.0, 3.0]);
+}
+
+#[test]
+fn test_custom_op() {
+    for len in 0..100 {
+        let data: Vec<u32> = (0..len)
+            .map(|i| if i % 7 == 3 { i as u32 } else { 0 })
+            .collect();
+        check_scan!(u32, LastNonZero, data, 0, |a, b| if b == 0 { a } else { b });
+    }
+}
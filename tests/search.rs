@@ -0,0 +1,175 @@
+//! Tests of `algo::search` against naive search. Random haystacks are made of a few distinct
+//! bytes, so there are many matches and partial matches of substrings.
+#![cfg_attr(not(simd_shim), feature(portable_simd))]
+
+use proptest::prelude::*;
+use simd_addons::algo::search;
+
+fn naive_matches(haystack: &[u8], needles: &[u8]) -> Vec<usize> {
+    (0..haystack.len())
+        .filter(|&i| needles.contains(&haystack[i]))
+        .collect()
+}
+
+/// Non-overlapping occurrences of `needle`, like `str::match_indices`.
+fn naive_find_iter(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while start + needle.len() <= haystack.len() {
+        if haystack[start..].starts_with(needle) {
+            matches.push(start);
+            start += needle.len().max(1);
+        } else {
+            start += 1;
+        }
+    }
+    matches
+}
+
+fn check_bytes(haystack: &[u8], needles: &[u8]) {
+    let expected = naive_matches(haystack, needles);
+    let (first, last) = (expected.first().copied(), expected.last().copied());
+    let message = format!("{:?} in {:?}", needles, haystack);
+    match *needles {
+        [a] => {
+            assert_eq!(search::find_byte(haystack, a), first, "{}", message);
+            assert_eq!(search::rfind_byte(haystack, a), last, "{}", message);
+            assert_eq!(
+                search::find_byte_iter(haystack, a).collect::<Vec<_>>(),
+                expected
+            );
+        }
+        [a, b] => {
+            assert_eq!(search::find_any_of(haystack, [a, b]), first, "{}", message);
+            assert_eq!(search::rfind_any_of(haystack, [a, b]), last, "{}", message);
+            let reversed: Vec<usize> = expected.iter().rev().copied().collect();
+            assert_eq!(
+                search::find_any_of_iter(haystack, [a, b])
+                    .rev()
+                    .collect::<Vec<_>>(),
+                reversed
+            );
+        }
+        [a, b, c] => {
+            assert_eq!(
+                search::find_any_of(haystack, [a, b, c]),
+                first,
+                "{}",
+                message
+            );
+            assert_eq!(
+                search::rfind_any_of(haystack, [a, b, c]),
+                last,
+                "{}",
+                message
+            );
+            // alternating ends of the iterator meet in the middle
+            let mut matches = search::find_any_of_iter(haystack, [a, b, c]);
+            let (mut front, mut back) = (Vec::new(), Vec::new());
+            loop {
+                match (matches.next(), matches.next_back()) {
+                    (Some(i), Some(j)) => {
+                        front.push(i);
+                        back.push(j);
+                    }
+                    (Some(i), None) => front.push(i),
+                    (None, _) => break,
+                }
+            }
+            front.extend(back.into_iter().rev());
+            assert_eq!(front, expected, "{}", message);
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn check_substring(haystack: &[u8], needle: &[u8]) {
+    let expected = naive_find_iter(haystack, needle);
+    assert_eq!(
+        search::find(haystack, needle),
+        expected.first().copied(),
+        "{:?} in {:?}",
+        needle,
+        haystack
+    );
+    assert_eq!(
+        search::find_iter(haystack, needle).collect::<Vec<_>>(),
+        expected
+    );
+}
+
+#[test]
+fn test_lengths() {
+    // a single match at every position of haystacks of every length around the blocks
+    for len in 0..300 {
+        let mut haystack = vec![b'a'; len];
+        check_bytes(&haystack, b"b");
+        for i in 0..len {
+            haystack[i] = b'b';
+            check_bytes(&haystack, b"b");
+            check_bytes(&haystack, b"cb");
+            check_bytes(&haystack, b"cdb");
+            haystack[i] = b'a';
+        }
+    }
+}
+
+#[test]
+fn test_substring_positions() {
+    let needle = b"needle";
+    for len in 0..200 {
+        let mut haystack = vec![b'n'; len];
+        check_substring(&haystack, needle);
+        for i in 0..len.saturating_sub(needle.len() - 1) {
+            haystack[i..i + needle.len()].copy_from_slice(needle);
+            check_substring(&haystack, needle);
+            check_substring(&haystack, b"ne");
+            check_substring(&haystack, b"e");
+            haystack[i..i + needle.len()].fill(b'n');
+        }
+    }
+}
+
+#[test]
+fn test_empty_needle() {
+    assert_eq!(search::find(b"abc", b""), Some(0));
+    assert_eq!(
+        search::find_iter(b"abc", b"").collect::<Vec<_>>(),
+        [0, 1, 2, 3]
+    );
+    assert_eq!(search::find_iter(b"", b"").collect::<Vec<_>>(), [0]);
+    assert_eq!(search::find(b"", b"a"), None);
+    assert_eq!(search::find_any_of(b"abc", []), None);
+}
+
+#[test]
+fn test_overlapping() {
+    assert_eq!(
+        search::find_iter(b"aaaaa", b"aa").collect::<Vec<_>>(),
+        [0, 2]
+    );
+    assert_eq!(search::find(b"abababc", b"ababc"), Some(2));
+}
+
+proptest! {
+    #[test]
+    fn random_bytes(
+        haystack in prop::collection::vec(0u8..6, 0..500),
+        needles in prop::collection::vec(0u8..8, 1..=3),
+    ) {
+        check_bytes(&haystack, &needles);
+    }
+
+    #[test]
+    fn random_substring(
+        haystack in prop::collection::vec(0u8..3, 0..500),
+        needle in prop::collection::vec(0u8..3, 0..8),
+    ) {
+        check_substring(&haystack, &needle);
+    }
+
+    #[test]
+    fn random_any_bytes(haystack in prop::collection::vec(any::<u8>(), 0..500), needle in any::<u8>()) {
+        check_bytes(&haystack, &[needle]);
+    }
+}
@@ -0,0 +1,138 @@
+//! Compares accuracy of the functions with SLEEF's 1.0-ULP and 3.5-ULP versions. Errors are
+//! measured in ULPs relative to `std` on the samples used by the benchmarks, run with
+//! `--nocapture` to see them.
+#![cfg(feature = "sleef_bench")]
+#![feature(portable_simd)]
+
+#[allow(dead_code)]
+mod common;
+use common::Linspace;
+
+#[allow(dead_code, unused_macros)]
+#[macro_use]
+#[path = "../benches/sample/mod.rs"]
+mod sample;
+use sample::{generate_atan2_bench_sample, BENCH_POINTS};
+
+#[path = "../benches/cpp/sleefbench.rs"]
+mod sleefbench;
+
+use simd_addons::{
+    math::{Exponent, Trigonometry},
+    simd::Simd,
+};
+
+/// Maximum distance in ULPs between the corresponding elements, NaNs are equal to each other only.
+macro_rules! max_ulps {
+    ($ftype: ident, $utype: ident, $actual: expr, $expected: expr) => {{
+        let key = |x: $ftype| {
+            let bits = x.to_bits() as $utype;
+            if bits < 0 {
+                $utype::MIN - bits
+            } else {
+                bits
+            }
+        };
+        std::iter::zip($actual, $expected)
+            .map(|(&actual, &expected): (&$ftype, &$ftype)| {
+                match (actual.is_nan(), expected.is_nan()) {
+                    (true, true) => 0,
+                    (false, false) => key(actual).abs_diff(key(expected)),
+                    _ => <$utype>::MAX.unsigned_abs(),
+                }
+            })
+            .max()
+            .unwrap_or(0)
+    }};
+}
+
+macro_rules! compare_with_sleef {
+    (
+        $ftype: ident, $utype: ident, $name: ident, $expected: expr, $ours: expr,
+        |$sleef: ident, $result: ident| $call: expr
+    ) => {
+        paste::paste! {{
+            let expected: Vec<$ftype> = $expected;
+            let ours = max_ulps!($ftype, $utype, &$ours, &expected);
+            let mut $result: Vec<$ftype> = vec![0.0; BENCH_POINTS];
+            let $sleef = sleefbench::[< $name _ $ftype _sleefu10 >];
+            unsafe { $call };
+            let sleefu10 = max_ulps!($ftype, $utype, &$result, &expected);
+            let $sleef = sleefbench::[< $name _ $ftype _sleefu35 >];
+            unsafe { $call };
+            let sleefu35 = max_ulps!($ftype, $utype, &$result, &expected);
+
+            println!("{:>12} {:>8} {:>8} {:>8}", stringify!($name), ours, sleefu10, sleefu35);
+            // accuracy of our functions is checked in `math.rs`, here it is only reported since
+            // relative errors near zeros of the functions are large; this checks the bridge
+            assert!(sleefu10 <= 2, "{} (SLEEF u10): {} ULPs", stringify!($name), sleefu10);
+        }}
+    };
+}
+
+macro_rules! test_sleef {
+    ($ftype: ident, $utype: ident, $( $range: expr => $func: ident ),+ $(,)?) => {
+        paste::paste! {
+        #[test]
+        fn [< test_compare_with_sleef_ $ftype >]() {
+            println!("{:>12} {:>8} {:>8} {:>8}", stringify!($ftype), "ours", "sleefu10", "sleefu35");
+            $(
+            let range: std::ops::Range<$ftype> = $range;
+            let data: Vec<$ftype> = range.linspace(BENCH_POINTS).collect();
+            compare_with_sleef!(
+                $ftype,
+                $utype,
+                $func,
+                data.iter().map(|x| x.$func()).collect(),
+                data.chunks_exact(16)
+                    .flat_map(|x| Simd::<$ftype, 16>::from_slice(x).$func().to_array())
+                    .collect::<Vec<_>>(),
+                |sleef, result| sleef(data.as_ptr(), result.as_mut_ptr())
+            );
+            )+
+
+            let (x, y) = sample_as!(generate_atan2_bench_sample, $ftype);
+            compare_with_sleef!(
+                $ftype,
+                $utype,
+                atan2,
+                std::iter::zip(&x, &y).map(|(x, y)| y.atan2(*x)).collect(),
+                std::iter::zip(x.chunks_exact(16), y.chunks_exact(16))
+                    .flat_map(|(x, y)| {
+                        Simd::<$ftype, 16>::from_slice(y)
+                            .atan2(Simd::from_slice(x))
+                            .to_array()
+                    })
+                    .collect::<Vec<_>>(),
+                |sleef, result| sleef(x.as_ptr(), y.as_ptr(), result.as_mut_ptr())
+            );
+        }
+        }
+    };
+}
+
+test_sleef!(
+    f32, i32,
+    -50.0..50.0 => exp,
+    -50.0..50.0 => exp2,
+    -50.0..50.0 => exp_m1,
+    -1e4..1e4 => sin,
+    -1e4..1e4 => cos,
+    -1e4..1e4 => tan,
+    -1.0..1.0 => asin,
+    -1.0..1.0 => acos,
+    -1e3..1e3 => atan,
+);
+
+test_sleef!(
+    f64, i64,
+    -50.0..50.0 => exp,
+    -50.0..50.0 => exp2,
+    -50.0..50.0 => exp_m1,
+    -1e4..1e4 => sin,
+    -1e4..1e4 => cos,
+    -1e4..1e4 => tan,
+    -1.0..1.0 => asin,
+    -1.0..1.0 => acos,
+    -1e3..1e3 => atan,
+);
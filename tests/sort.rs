@@ -0,0 +1,212 @@
+//! Tests of `algo::sort` against sorting by `std`. Floats are compared bitwise, which also checks
+//! the order of zeros and NaNs of different signs.
+#![cfg_attr(not(simd_shim), feature(portable_simd))]
+
+#[allow(dead_code)]
+#[macro_use]
+mod common;
+
+use proptest::prelude::*;
+use simd_addons::{
+    algo::sort::{self, SimdSort},
+    simd::Simd,
+};
+
+/// Sorts `data` by `std` in the same order as `algo::sort`.
+macro_rules! std_sorted {
+    (float, $data: expr) => {{
+        let mut data = $data.to_vec();
+        data.sort_by(|a, b| a.total_cmp(b));
+        data.into_iter().map(|x| x.to_bits()).collect::<Vec<_>>()
+    }};
+    (int, $data: expr) => {{
+        let mut data = $data.to_vec();
+        data.sort();
+        data
+    }};
+}
+
+/// Comparable representation of sorted elements: bits for floats and values for integers.
+macro_rules! comparable {
+    (float, $data: expr) => {
+        $data.iter().map(|x| x.to_bits()).collect::<Vec<_>>()
+    };
+    (int, $data: expr) => {
+        $data.to_vec()
+    };
+}
+
+/// Checks that `keys` and `values` are `original` sorted together with its indices.
+macro_rules! assert_sorted_pairs {
+    ($kind: ident, $original: expr, $keys: expr, $values: expr) => {{
+        let (original, keys, values) = (&$original, &$keys, &$values);
+        assert_eq!(comparable!($kind, keys), std_sorted!($kind, original));
+        let mut seen = vec![false; original.len()];
+        for (key, &index) in keys.iter().zip(values.iter()) {
+            let index = index as usize;
+            assert!(!seen[index], "index {} is repeated", index);
+            seen[index] = true;
+            assert_eq!(
+                comparable!($kind, [original[index]]),
+                comparable!($kind, [*key]),
+                "key of index {}",
+                index
+            );
+        }
+    }};
+}
+
+/// Special patterns of `len` elements made of `values`: sorted, reversed, constant, organ pipe
+/// and few distinct values.
+fn patterns<T: Copy>(len: usize, values: &[T]) -> Vec<Vec<T>> {
+    let at = |i: usize| values[i % values.len()];
+    vec![
+        (0..len).map(at).collect(),
+        (0..len).rev().map(at).collect(),
+        vec![at(0); len],
+        (0..len).map(|i| at(i.min(len - 1 - i))).collect(),
+        (0..len).map(|i| at(i * 7 % 3)).collect(),
+    ]
+}
+
+macro_rules! test_sort {
+    ($kind: ident, $etype: ident, $strategy: expr, $values: expr) => {
+        paste::paste! {
+        mod [< sort_ $etype >] {
+            use super::*;
+
+            /// Values in ascending order, so the patterns are sorted where expected.
+            fn values() -> Vec<$etype> {
+                let mut values: Vec<$etype> = $values.to_vec();
+                values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+                values
+            }
+
+            #[test]
+            fn lanes() {
+                let data: Vec<$etype> = values().into_iter().rev().cycle().take(64).collect();
+                for_each_lane_count!(|LANES| {
+                    let lanes = Simd::<$etype, LANES>::from_slice(&data);
+                    let indices = Simd::from_array(std::array::from_fn(|i| i as _));
+                    let sorted = lanes.sort_lanes();
+                    assert_eq!(comparable!($kind, sorted.to_array()), std_sorted!($kind, lanes.to_array()));
+                    let (sorted, values) = lanes.sort_lanes_with(indices);
+                    assert_sorted_pairs!($kind, lanes.to_array(), sorted.to_array(), values.to_array());
+                });
+            }
+
+            #[test]
+            fn patterns() {
+                for len in (1..300).chain([1000, 4097, 20_000]) {
+                    for data in super::patterns(len, &values()) {
+                        let mut sorted = data.clone();
+                        sort::sort(&mut sorted);
+                        assert_eq!(comparable!($kind, sorted), std_sorted!($kind, data), "length {}", len);
+
+                        let mut keys = data.clone();
+                        let mut indices = vec![0; len];
+                        sort::argsort(&mut keys, &mut indices);
+                        assert_sorted_pairs!($kind, data, keys, indices);
+                    }
+                }
+            }
+
+            proptest! {
+                #[test]
+                fn random(data in prop::collection::vec($strategy, 0..3000)) {
+                    let mut sorted = data.clone();
+                    sort::sort(&mut sorted);
+                    prop_assert_eq!(comparable!($kind, sorted), std_sorted!($kind, data));
+                }
+
+                #[test]
+                fn random_argsort(data in prop::collection::vec($strategy, 0..3000)) {
+                    let mut keys = data.clone();
+                    let mut indices = vec![0; data.len()];
+                    sort::argsort(&mut keys, &mut indices);
+                    assert_sorted_pairs!($kind, data, keys, indices);
+                }
+
+                #[test]
+                fn random_duplicates(data in prop::collection::vec(prop::sample::select(values()), 0..3000)) {
+                    let mut sorted = data.clone();
+                    sort::sort(&mut sorted);
+                    prop_assert_eq!(comparable!($kind, sorted), std_sorted!($kind, data));
+                }
+            }
+        }
+        }
+    };
+}
+
+test_sort!(
+    int,
+    i32,
+    any::<i32>(),
+    [i32::MIN, -7, -1, 0, 1, 2, 100, i32::MAX]
+);
+test_sort!(
+    int,
+    u32,
+    any::<u32>(),
+    [0, 1, 2, 3, 1000, u32::MAX - 1, u32::MAX]
+);
+test_sort!(
+    int,
+    i64,
+    any::<i64>(),
+    [i64::MIN, -7, -1, 0, 1, 2, 100, i64::MAX]
+);
+test_sort!(
+    int,
+    u64,
+    any::<u64>(),
+    [0, 1, 2, 3, 1000, u64::MAX - 1, u64::MAX]
+);
+test_sort!(
+    float,
+    f32,
+    any::<f32>(),
+    [
+        f32::NEG_INFINITY,
+        f32::MIN,
+        -1.0,
+        -0.0,
+        0.0,
+        1e-40,
+        1.0,
+        f32::MAX,
+        f32::INFINITY
+    ]
+);
+test_sort!(
+    float,
+    f64,
+    any::<f64>(),
+    [
+        f64::NEG_INFINITY,
+        f64::MIN,
+        -1.0,
+        -0.0,
+        0.0,
+        1e-310,
+        1.0,
+        f64::MAX,
+        f64::INFINITY
+    ]
+);
+
+#[test]
+fn test_nan_placement() {
+    let negative_nan = -f32::NAN;
+    let mut data = [1.0, f32::NAN, -f32::INFINITY, negative_nan, -0.0, 0.0];
+    sort::sort(&mut data);
+    let expected = [negative_nan, -f32::INFINITY, -0.0, 0.0, 1.0, f32::NAN];
+    assert_eq!(data.map(f32::to_bits), expected.map(f32::to_bits));
+}
+
+#[test]
+#[should_panic(expected = "keys and values must have the same length")]
+fn test_sort_pairs_lengths() {
+    sort::sort_pairs(&mut [1, 2, 3], &mut [0u32, 1]);
+}
@@ -0,0 +1,132 @@
+//! Tests of `algo::utf8` against `core`. Sequences of bytes are checked exhaustively at the
+//! boundaries of vectors, random text is corrupted to get every kind of error at random positions.
+#![cfg_attr(not(simd_shim), feature(portable_simd))]
+
+#[allow(dead_code)]
+mod common;
+
+use common::{corrupt, Corruption};
+use proptest::prelude::*;
+use simd_addons::algo::utf8;
+
+fn check(bytes: &[u8]) {
+    assert_eq!(
+        utf8::from_utf8(bytes),
+        core::str::from_utf8(bytes),
+        "{:x?}",
+        bytes
+    );
+    assert_eq!(utf8::is_utf8(bytes), core::str::from_utf8(bytes).is_ok());
+    assert_eq!(utf8::is_ascii(bytes), bytes.is_ascii());
+}
+
+/// Continuations of sequences, which make each of them valid or invalid in some way.
+const SUFFIXES: [&[u8]; 12] = [
+    b"",
+    b"a",
+    b"\x80",
+    b"\x8f",
+    b"\x90",
+    b"\xbf",
+    b"\xc0",
+    b"\x80\x80",
+    b"\xa0\x80",
+    b"\x80\x80\x80",
+    b"\x80\x80\x80\x80",
+    b"\xbf\xbf\xc2\xa9",
+];
+
+#[test]
+fn test_sequences() {
+    // every pair of bytes followed by a suffix, at the start and across the first two vectors,
+    // all ASCII second bytes are alike
+    for offset in [0, 30] {
+        for first in 0..=255u8 {
+            for second in 0x7e..=255u8 {
+                for suffix in SUFFIXES {
+                    let mut bytes = vec![b'x'; offset];
+                    bytes.extend([first, second]);
+                    bytes.extend_from_slice(suffix);
+                    check(&bytes);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_positions() {
+    // a sequence at every position of texts of every length around the vectors
+    let sequences: [&[u8]; 8] = [
+        "é".as_bytes(),
+        "€".as_bytes(),
+        "😀".as_bytes(),
+        b"\xe2\x82",
+        b"\xf0\x9f\x98",
+        b"\x80",
+        b"\xed\xa0\x80",
+        b"\xf4\x90\x80\x80",
+    ];
+    for len in 0..140 {
+        for sequence in sequences {
+            for i in 0..=len {
+                let mut bytes = vec![b'x'; len];
+                bytes.splice(i..i, sequence.iter().copied());
+                check(&bytes);
+                // non-ASCII text around
+                let mut text = "ä".repeat(len / 2).into_bytes();
+                let i = i / 2 * 2;
+                text.splice(i..i, sequence.iter().copied());
+                check(&text);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_ascii_case() {
+    let bytes: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+    for len in 0..bytes.len() {
+        let mut upper = bytes[..len].to_vec();
+        let mut lower = upper.clone();
+        utf8::make_ascii_uppercase(&mut upper);
+        utf8::make_ascii_lowercase(&mut lower);
+        assert_eq!(upper, bytes[..len].to_ascii_uppercase());
+        assert_eq!(lower, bytes[..len].to_ascii_lowercase());
+    }
+}
+
+fn corruption() -> impl Strategy<Value = Corruption> {
+    // bytes above ASCII make most of the edits invalid
+    common::corruption(prop_oneof![0x80u8.., any::<u8>()])
+}
+
+proptest! {
+    #[test]
+    fn random_text(text in ".{0,200}", corruptions in prop::collection::vec(corruption(), 0..3)) {
+        assert_eq!(utf8::count_chars(&text), text.chars().count());
+        let mut bytes = text.into_bytes();
+        check(&bytes);
+        for corruption in corruptions {
+            corrupt(&mut bytes, corruption);
+            check(&bytes);
+        }
+    }
+
+    #[test]
+    fn random_bytes(bytes in prop::collection::vec(any::<u8>(), 0..200)) {
+        check(&bytes);
+        let mut upper = bytes.clone();
+        utf8::make_ascii_uppercase(&mut upper);
+        prop_assert_eq!(upper, bytes.to_ascii_uppercase());
+    }
+
+    #[test]
+    fn random_ascii(text in "[ -~]{0,300}") {
+        check(text.as_bytes());
+        assert_eq!(utf8::count_chars(&text), text.len());
+        let mut lower = text.clone().into_bytes();
+        utf8::make_ascii_lowercase(&mut lower);
+        prop_assert_eq!(lower, text.to_ascii_lowercase().into_bytes());
+    }
+}